@@ -1,29 +1,108 @@
-use clap::Parser;
-use croissant_crossword::crossword::{Crossword, CrosswordSolutions};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, ValueEnum};
+use croissant_crossword::crossword::{
+    Crossword, CrosswordSolutions, CubeAndConquer, Diversity, Splitting,
+};
+use croissant_crossword::encoding::{DirectEncoding, Encoding, MddEncoding, WordLevelEncoding};
+use croissant_solver::{SearchLimits, SolverError, SolverFactory};
+use croissant_solver_backtrack::BacktrackSolverBuilder;
 #[cfg(feature = "solver-cadical")]
 use croissant_solver_cadical::CadicalSolver;
+use croissant_solver_external::ExternalSolver;
+use croissant_solver_localsearch::LocalSearchSolverBuilder;
 use croissant_solver_logicng::LogicngSolverBuilder;
+use croissant_solver_portfolio::PortfolioSolverBuilder;
 #[cfg(feature = "solver-splr")]
-use croissant_solver_splr::SplrSolverBuilder;
+use croissant_solver_splr::SplrSolver;
+#[cfg(feature = "solver-varisat")]
+use croissant_solver_varisat::VarisatSolver;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Read};
+use std::io::{BufRead, BufReader, BufWriter, Read};
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// 🥐 Welcome to Croissant, a crossword solver that smells good.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// The grid as a string; Each new line is a new row, '.' is a blank, '#' is a block.
-    grid: String,
+    #[arg(required = true)]
+    grid: Option<String>,
     /// The path to the word list; File must contain one word per line and nothing else.
     #[arg(short, long)]
     wordlist: Option<PathBuf>,
     /// The solver to use.
     #[arg(short, long, default_value_t, value_enum)]
     solver: SolverId,
+    /// The command running the external solver, e.g. "kissat -q"; It must read DIMACS on its standard input.
+    #[arg(short = 'x', long, required_if_eq("solver", "external"))]
+    external: Option<String>,
     /// The desired number of solutions.
     #[arg(short, long, default_value_t = 1)]
     count: usize,
+    /// The encoding of the problem given to the solver.
+    #[arg(short, long, default_value_t, value_enum)]
+    encoding: EncodingId,
+    /// The minimal number of slots in which each solution differs from the previous ones.
+    #[arg(short, long)]
+    diversity: Option<usize>,
+    /// The seed randomizing the solutions; The same seed gives the same solutions.
+    #[arg(long)]
+    seed: Option<u64>,
+    /// The maximal time to search each solution, in seconds; Not supported by all the solvers.
+    #[arg(short, long)]
+    timeout: Option<u64>,
+    /// Prints the statistics of the solver after each solution.
+    #[arg(long)]
+    stats: bool,
+    /// Prints the progress of the solver while searching, on the standard error.
+    #[arg(long)]
+    progress: bool,
+    /// Splits the grid into many sub-problems solved concurrently, on the words of the most constrained slots or on
+    /// the letters of the most crossed cells; The solver must support solving under assumptions.
+    #[arg(long, value_enum)]
+    cubes: Option<SplittingId>,
+    /// The number of threads solving the sub-problems; Default is the number of cores.
+    #[arg(short = 'j', long, requires = "cubes")]
+    threads: Option<usize>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Explains why the grid has no solution.
+    Explain {
+        /// The grid as a string; Each new line is a new row, '.' is a blank, '#' is a block.
+        grid: String,
+        /// The path to the word list; File must contain one word per line and nothing else.
+        #[arg(short, long)]
+        wordlist: Option<PathBuf>,
+        /// The solver to use; It must support solving under assumptions.
+        #[arg(short, long, default_value_t, value_enum)]
+        solver: SolverId,
+        /// The command running the external solver, e.g. "kissat -q"; It must read DIMACS on its standard input.
+        #[arg(short = 'x', long, required_if_eq("solver", "external"))]
+        external: Option<String>,
+    },
+    /// Exports the problem in DIMACS CNF format, with the meaning of its variables in JSON next to it.
+    ExportCnf {
+        /// The grid as a string; Each new line is a new row, '.' is a blank, '#' is a block.
+        grid: String,
+        /// The path to the CNF file to write; The JSON file has the same path, with the 'json' extension.
+        #[arg(short, long)]
+        output: PathBuf,
+        /// The path to the word list; File must contain one word per line and nothing else.
+        #[arg(short, long)]
+        wordlist: Option<PathBuf>,
+        /// The encoding of the problem.
+        #[arg(short, long, default_value_t, value_enum)]
+        encoding: EncodingId,
+        /// The seed shuffling the words; The same seed gives the same problem.
+        #[arg(long)]
+        seed: Option<u64>,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone, Debug, Default)]
@@ -37,14 +116,188 @@ enum SolverId {
     /// The slowest and buggiest, but that's why we love it ❤️
     #[cfg(feature = "solver-splr")]
     Splr,
+    /// The pure Rust one, which also runs in the browser.
+    #[cfg(feature = "solver-varisat")]
+    Varisat,
+    /// All of the above at once, on separate threads; The first to answer wins.
+    Portfolio,
+    /// Any program speaking DIMACS, e.g. kissat or glucose; See --external.
+    External,
+    /// No SAT at all: A word-level backtracking filler, to compare with the others.
+    Backtrack,
+    /// No SAT either: A min-conflicts local search, fast on open grids but unable to prove there is no solution.
+    LocalSearch,
+}
+
+/// The solver chosen on the command line.
+#[derive(Clone, Debug)]
+struct SolverChoice {
+    /// The solver to use.
+    id: SolverId,
+    /// The command running the external solver, if any.
+    external: Option<String>,
+}
+
+impl SolverChoice {
+    /// Returns the external solver running the external command, or an error if there is no command or if its program
+    /// cannot be found.
+    fn external_solver(&self) -> Result<ExternalSolver, SolverError> {
+        let command = self.external.as_deref().unwrap_or_default();
+        let mut words = command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or_else(|| SolverError::new("External command is required"))?;
+        ExternalSolver::new(program, words.map(String::from).collect())
+    }
+
+    /// Returns whether the solver enforces the given limits, see
+    /// [SolverConfigurator::supports_limits](croissant_solver::SolverConfigurator::supports_limits). The native solvers
+    /// enforce them all.
+    fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        match self.id {
+            SolverId::Backtrack | SolverId::LocalSearch => true,
+            // An invalid external command is reported when solving
+            _ => factory(self).map_or(true, |factory| factory.supports_limits(limits)),
+        }
+    }
+
+    /// Returns the name of the solver, as given on the command line.
+    fn name(&self) -> String {
+        let value = self.id.to_possible_value().expect("Solver not skipped");
+        value.get_name().to_string()
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Debug, Default)]
+enum EncodingId {
+    /// One variable per (slot,word) pair; Simple, but huge on big word lists.
+    #[default]
+    Direct,
+    /// A decision diagram of the words per slot; Smaller, and usually faster.
+    Mdd,
+    /// Words only, no letters; Think like a constructor.
+    WordLevel,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum SplittingId {
+    /// On the candidate words of the slots having the fewest.
+    Words,
+    /// On the candidate letters of the cells crossing the longest slots.
+    Letters,
+}
+
+impl SplittingId {
+    /// Returns the corresponding [Splitting].
+    fn splitting(&self) -> Splitting {
+        match self {
+            SplittingId::Words => Splitting::Words,
+            SplittingId::Letters => Splitting::Letters,
+        }
+    }
+}
+
+impl EncodingId {
+    /// Returns the corresponding [Encoding].
+    fn encoding(&self) -> Box<dyn Encoding> {
+        match self {
+            EncodingId::Direct => Box::new(DirectEncoding),
+            EncodingId::Mdd => Box::new(MddEncoding),
+            EncodingId::WordLevel => Box::new(WordLevelEncoding),
+        }
+    }
 }
 
 fn main() {
     let args = Args::parse();
+    match args.command {
+        Some(Command::Explain {
+            grid,
+            wordlist,
+            solver,
+            external,
+        }) => {
+            let words = wordlist.map(read_words_at).unwrap_or_else(ukacd);
+            let crossword = Crossword::try_from(grid.as_str(), &words).unwrap();
+            let solver = SolverChoice {
+                id: solver,
+                external,
+            };
+            explain_and_print(crossword, &solver);
+            return;
+        }
+        Some(Command::ExportCnf {
+            grid,
+            output,
+            wordlist,
+            encoding,
+            seed,
+        }) => {
+            let words = wordlist.map(read_words_at).unwrap_or_else(ukacd);
+            let mut crossword = Crossword::try_from(grid.as_str(), &words)
+                .unwrap()
+                .with_encoding(encoding.encoding());
+            if let Some(seed) = seed {
+                crossword = crossword.with_seed(seed);
+            }
+            export_and_print(crossword, output);
+            return;
+        }
+        None => {}
+    }
     let words = args.wordlist.map(read_words_at).unwrap_or_else(ukacd);
-    let crossword = Crossword::try_from(args.grid.as_str(), &words).unwrap();
-    let mut solutions = solve(crossword, args.solver);
-    iterate_and_print(args.count, &mut solutions);
+    let grid = args.grid.expect("Grid is required");
+    let solver = SolverChoice {
+        id: args.solver,
+        external: args.external,
+    };
+    let mut crossword = Crossword::try_from(grid.as_str(), &words)
+        .unwrap()
+        .with_encoding(args.encoding.encoding());
+    if let Some(seed) = args.seed {
+        crossword = crossword.with_seed(seed);
+    }
+    if let Some(timeout) = args.timeout {
+        let limits = SearchLimits {
+            timeout: Some(Duration::from_secs(timeout)),
+            ..SearchLimits::default()
+        };
+        if !solver.supports_limits(&limits) {
+            let message = format!("the {} solver does not support --timeout", solver.name());
+            Args::command()
+                .error(ErrorKind::ArgumentConflict, message)
+                .exit();
+        }
+        crossword = crossword.with_limits(limits);
+    }
+    if args.progress {
+        crossword = crossword.with_progress(Box::new(|stats| eprintln!("Searching… {stats}")));
+    }
+    if let Some(splitting) = args.cubes {
+        let mut options = CubeAndConquer::default().with_splitting(splitting.splitting());
+        if let Some(thread_count) = args.threads {
+            options = options.with_thread_count(thread_count);
+        }
+        solve_by_cubes_and_print(crossword, &solver, args.count, &options);
+        return;
+    }
+    let mut solutions = match solve(crossword, &solver) {
+        Ok(solutions) => solutions,
+        Err(error) => {
+            println!("Cannot solve: {error}.");
+            return;
+        }
+    };
+    if let Some(slot_count) = args.diversity {
+        solutions = match solutions.with_diversity(Diversity::Slots(slot_count)) {
+            Ok(solutions) => solutions,
+            Err(error) => {
+                println!("Cannot diversify solutions: {error}.");
+                return;
+            }
+        };
+    }
+    iterate_and_print(args.count, &mut solutions, args.stats);
 }
 
 /// Reads words from the file at given path. Panics if no such file exists.
@@ -71,9 +324,12 @@ fn read<T: Read>(data: T) -> Vec<String> {
         .collect()
 }
 
-/// Solves (lazily) the grid with the solver
-fn solve(crossword: Crossword, solver_id: SolverId) -> CrosswordSolutions {
-    match solver_id {
+/// The error of the features needing a solver working on clauses, when the solver is a native one.
+const UNSUPPORTED_BY_NATIVE_SOLVER: &str = "Solver does not support solving under assumptions";
+
+/// Solves (lazily) the grid with the solver, or returns an error if the solver cannot be created.
+fn solve(crossword: Crossword, solver: &SolverChoice) -> Result<CrosswordSolutions, SolverError> {
+    match solver.id {
         #[cfg(feature = "solver-cadical")]
         SolverId::Cadical => crossword.solve_with(Box::new(CadicalSolver::new())),
         SolverId::Logicng => {
@@ -81,24 +337,162 @@ fn solve(crossword: Crossword, solver_id: SolverId) -> CrosswordSolutions {
             crossword.solve_with_solver_built_by(solver_builder)
         }
         #[cfg(feature = "solver-splr")]
-        SolverId::Splr => {
-            let solver_builder = Box::new(SplrSolverBuilder::new());
-            crossword.solve_with_solver_built_by(solver_builder)
+        SolverId::Splr => crossword.solve_with(Box::new(SplrSolver::new())),
+        #[cfg(feature = "solver-varisat")]
+        SolverId::Varisat => crossword.solve_with(Box::new(VarisatSolver::new())),
+        SolverId::Portfolio => crossword.solve_with_solver_built_by(Box::new(portfolio())),
+        SolverId::External => crossword.solve_with(Box::new(solver.external_solver()?)),
+        SolverId::Backtrack => {
+            crossword.solve_natively_with(Box::new(BacktrackSolverBuilder::new()))
+        }
+        SolverId::LocalSearch => {
+            crossword.solve_natively_with(Box::new(LocalSearchSolverBuilder::new()))
         }
     }
 }
 
-/// Iterates on given [CrosswordSolutions] and prints as many solutions as given `count` and as possible.
-fn iterate_and_print(count: usize, solutions: &mut CrosswordSolutions) {
+/// Returns the factory of the solver, or an error if the solver does not work on clauses.
+fn factory(solver: &SolverChoice) -> Result<SolverFactory, SolverError> {
+    let factory = match solver.id {
+        #[cfg(feature = "solver-cadical")]
+        SolverId::Cadical => {
+            SolverFactory::Configurable(Box::new(|| Box::new(CadicalSolver::new())))
+        }
+        SolverId::Logicng => {
+            SolverFactory::Builder(Box::new(|| Box::new(LogicngSolverBuilder::new())))
+        }
+        #[cfg(feature = "solver-splr")]
+        SolverId::Splr => SolverFactory::Configurable(Box::new(|| Box::new(SplrSolver::new()))),
+        #[cfg(feature = "solver-varisat")]
+        SolverId::Varisat => {
+            SolverFactory::Configurable(Box::new(|| Box::new(VarisatSolver::new())))
+        }
+        SolverId::Portfolio => SolverFactory::Builder(Box::new(|| Box::new(portfolio()))),
+        SolverId::External => {
+            // Checked once here, so that creating the solvers later cannot fail
+            solver.external_solver()?;
+            let solver = solver.clone();
+            SolverFactory::Configurable(Box::new(move || {
+                Box::new(solver.external_solver().expect("External solver checked"))
+            }))
+        }
+        SolverId::Backtrack | SolverId::LocalSearch => {
+            return Err(SolverError::new(UNSUPPORTED_BY_NATIVE_SOLVER))
+        }
+    };
+    Ok(factory)
+}
+
+/// Creates a portfolio of all the available solvers, with two LogicNG solvers seeded differently.
+fn portfolio() -> PortfolioSolverBuilder {
+    let mut portfolio = PortfolioSolverBuilder::new();
+    #[cfg(feature = "solver-cadical")]
+    {
+        portfolio = portfolio.with_member(SolverFactory::Configurable(Box::new(|| {
+            Box::new(CadicalSolver::new())
+        })));
+    }
+    for _ in 0..2 {
+        portfolio = portfolio.with_member(SolverFactory::Builder(Box::new(|| {
+            Box::new(LogicngSolverBuilder::new())
+        })));
+    }
+    #[cfg(feature = "solver-splr")]
+    {
+        portfolio = portfolio.with_member(SolverFactory::Configurable(Box::new(|| {
+            Box::new(SplrSolver::new())
+        })));
+    }
+    #[cfg(feature = "solver-varisat")]
+    {
+        portfolio = portfolio.with_member(SolverFactory::Configurable(Box::new(|| {
+            Box::new(VarisatSolver::new())
+        })));
+    }
+    portfolio
+}
+
+/// Explains why the grid has no solution with the solver, and prints the explanation.
+fn explain_and_print(crossword: Crossword, solver: &SolverChoice) {
+    let explanation = match solver.id {
+        #[cfg(feature = "solver-cadical")]
+        SolverId::Cadical => crossword.explain_with(Box::new(CadicalSolver::new())),
+        SolverId::Logicng => {
+            let solver_builder = Box::new(LogicngSolverBuilder::new());
+            crossword.explain_with_solver_built_by(solver_builder)
+        }
+        #[cfg(feature = "solver-splr")]
+        SolverId::Splr => crossword.explain_with(Box::new(SplrSolver::new())),
+        #[cfg(feature = "solver-varisat")]
+        SolverId::Varisat => crossword.explain_with(Box::new(VarisatSolver::new())),
+        SolverId::Portfolio => crossword.explain_with_solver_built_by(Box::new(portfolio())),
+        SolverId::External => solver
+            .external_solver()
+            .and_then(|external_solver| crossword.explain_with(Box::new(external_solver))),
+        SolverId::Backtrack | SolverId::LocalSearch => {
+            Err(SolverError::new(UNSUPPORTED_BY_NATIVE_SOLVER))
+        }
+    };
+    match explanation {
+        Ok(None) => println!("Nothing to explain, a solution exists."),
+        Ok(Some(explanation)) => println!("{explanation}."),
+        Err(error) => println!("Cannot explain: {error}."),
+    }
+}
+
+/// Solves the grid by cube-and-conquer with the solver, and prints as many solutions as given `count` and as possible,
+/// one per satisfiable cube.
+fn solve_by_cubes_and_print(
+    crossword: Crossword,
+    solver: &SolverChoice,
+    count: usize,
+    options: &CubeAndConquer,
+) {
+    let solutions =
+        factory(solver).and_then(|factory| crossword.solve_all_cubes_with(factory, options, count));
+    match solutions {
+        Err(error) => println!("Cannot solve by cubes: {error}."),
+        Ok(solutions) if solutions.is_empty() => println!("No solution found."),
+        Ok(solutions) => println!("{}", solutions.join("\n\n")),
+    }
+}
+
+/// Exports the problem in DIMACS CNF format to the file at given path, and the meaning of its variables in JSON next
+/// to it, then prints where they are.
+fn export_and_print(crossword: Crossword, cnf_path: PathBuf) {
+    let json_path = cnf_path.with_extension("json");
+    let files = File::create(&cnf_path).and_then(|cnf| Ok((cnf, File::create(&json_path)?)));
+    let exported = match files {
+        Ok((cnf, json)) => crossword.export_cnf(BufWriter::new(cnf), &mut BufWriter::new(json)),
+        Err(error) => Err(SolverError::new(error.to_string())),
+    };
+    match exported {
+        Ok(_) => println!(
+            "Exported to {} and {}.",
+            cnf_path.display(),
+            json_path.display()
+        ),
+        Err(error) => println!("Cannot export: {error}."),
+    }
+}
+
+/// Iterates on given [CrosswordSolutions] and prints as many solutions as given `count` and as possible, followed by
+/// the statistics of the solver if `stats` is set.
+fn iterate_and_print(count: usize, solutions: &mut CrosswordSolutions, stats: bool) {
     for number in 1..=count {
         let solution = solutions.next();
         match solution {
             None => {
-                if number == 1 {
+                if let Some(error) = solutions.error() {
+                    println!("Search failed: {error}.")
+                } else if solutions.is_interrupted() {
+                    println!("Search interrupted: Timeout reached.")
+                } else if number == 1 {
                     println!("No solution found.")
                 } else {
                     println!("No more solution.")
                 }
+                print_stats_if(stats, solutions);
                 break;
             }
             Some(grid) => {
@@ -106,7 +500,15 @@ fn iterate_and_print(count: usize, solutions: &mut CrosswordSolutions) {
                     println!();
                 }
                 println!("{}", grid);
+                print_stats_if(stats, solutions);
             }
         }
     }
 }
+
+/// Prints the statistics of the solver of given [CrosswordSolutions], if `stats` is set.
+fn print_stats_if(stats: bool, solutions: &CrosswordSolutions) {
+    if stats {
+        println!("Statistics: {}.", solutions.stats());
+    }
+}
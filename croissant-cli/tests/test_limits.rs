@@ -0,0 +1,30 @@
+use std::process::{Command, Output};
+
+#[test]
+fn timeout_unsupported() {
+    let output = croissant(&["--solver", "logicng", "--timeout", "1", "..."]);
+
+    assert_eq!(Some(2), output.status.code());
+    let error = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        error.contains("the logicng solver does not support --timeout"),
+        "Unexpected error: {error}"
+    );
+}
+
+#[test]
+fn timeout_supported() {
+    let output = croissant(&["--solver", "backtrack", "--timeout", "10", "..."]);
+
+    assert!(output.status.success());
+    let solution = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(3, solution.trim().len(), "Unexpected solution: {solution}");
+}
+
+/// Runs the command line with the given arguments.
+fn croissant(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_croissant-cli"))
+        .args(args)
+        .output()
+        .expect("Command line should run")
+}
@@ -0,0 +1,265 @@
+use croissant_solver::{Lit, Model, SolveOutcome, Solver, SolverError};
+
+use crate::alphabet;
+use crate::grid::{self, Grid};
+use crate::slot::Slot;
+use crate::variables::Variables;
+
+/// The letters which may appear in each cell of a grid.
+///
+/// A cell whose only candidate is a single letter is *forced*: It contains this letter in every fill of the grid. Such
+/// cells form the backbone of the problem. Prefilled cells are trivially forced. Blocks have no candidate.
+#[derive(Debug, PartialEq)]
+pub struct CandidateLetters {
+    /// The number of columns of the grid
+    column_count: usize,
+    /// The candidate letters, by cell, row after row
+    letters: Vec<Vec<char>>,
+}
+
+impl CandidateLetters {
+    /// Returns the candidate letters of the cell at the given position, in alphabetical order.
+    pub fn at(&self, row: usize, column: usize) -> &[char] {
+        &self.letters[row * self.column_count + column]
+    }
+
+    /// Returns `true` iff the cell at the given position is forced, i.e. has exactly one candidate letter.
+    pub fn is_forced(&self, row: usize, column: usize) -> bool {
+        self.at(row, column).len() == 1
+    }
+}
+
+/// Computes the exact candidate letters of the cells of the given grid, using the given solver.
+///
+/// The solver must contain the problem encoded with cell variables as defined by [Variables], and support solving
+/// under assumptions. Each model found reveals a candidate letter for every cell; Then, each (cell,letter) pair not
+/// yet seen in a model is checked by assuming it. All calls are made on the same solver, which thus keeps its learnt
+/// clauses from a call to the other.
+///
+/// Returns an error if the solver does not support solving under assumptions, or if a search is interrupted.
+pub fn compute(
+    grid: &Grid,
+    solver: &mut dyn Solver<Item = Model>,
+) -> Result<CandidateLetters, SolverError> {
+    let variables = Variables::new(grid.clone(), 0);
+    let cell_count = grid.row_count() * grid.column_count();
+    let mut possible = vec![[false; alphabet::letter_count()]; cell_count];
+    if !solve_and_record(grid, &variables, &[], &mut possible, solver)? {
+        // No fill at all, hence no candidate
+        return Ok(CandidateLetters::from(grid, &possible));
+    }
+    for row in 0..grid.row_count() {
+        for column in 0..grid.column_count() {
+            if grid.letter_at(row, column) != grid::EMPTY {
+                continue;
+            }
+            for letter_index in 0..alphabet::letter_count() {
+                if possible[row * grid.column_count() + column][letter_index] {
+                    continue;
+                }
+                let assumption = variables.representing_cell(row, column, letter_index);
+                solve_and_record(
+                    grid,
+                    &variables,
+                    &[assumption.into()],
+                    &mut possible,
+                    solver,
+                )?;
+            }
+        }
+    }
+    Ok(CandidateLetters::from(grid, &possible))
+}
+
+/// Solves the problem under the given assumptions and records the letters of the model found, if any, as possible.
+///
+/// Returns whether a model was found, or an error if the solver does not support solving under assumptions.
+fn solve_and_record(
+    grid: &Grid,
+    variables: &Variables,
+    assumptions: &[Lit],
+    possible: &mut [[bool; alphabet::letter_count()]],
+    solver: &mut dyn Solver<Item = Model>,
+) -> Result<bool, SolverError> {
+    let model = match solver.solve_with_assumptions(assumptions) {
+        SolveOutcome::Sat(model) => model,
+        SolveOutcome::Unsat => return Ok(false),
+        SolveOutcome::Interrupted => return Err(SolverError::new("Search interrupted")),
+        SolveOutcome::Unsupported => {
+            return Err(SolverError::new(
+                "Solver does not support solving under assumptions",
+            ))
+        }
+        SolveOutcome::Failed(error) => return Err(error),
+    };
+    for row in 0..grid.row_count() {
+        for column in 0..grid.column_count() {
+            let cell_letters = &mut possible[row * grid.column_count() + column];
+            for (letter_index, possible_letter) in cell_letters.iter_mut().enumerate() {
+                let variable = variables.representing_cell(row, column, letter_index);
+                if model.is_true(variable) {
+                    *possible_letter = true;
+                }
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Approximates the candidate letters of the cells of the given grid, by propagating the given word list: The words of
+/// each slot which are not compatible with the candidate letters of its cells are discarded, then the candidate
+/// letters of each cell are restricted to the letters of the remaining words of its slots, until nothing changes.
+///
+/// No solver is involved, which makes it much cheaper than [compute]. It is an over-approximation though: A letter
+/// may be a candidate without appearing in any fill, and a cell may have candidates although the grid has no fill.
+pub fn approximate(grid: &Grid, words: &[String]) -> CandidateLetters {
+    let column_count = grid.column_count();
+    let mut domains: Vec<u32> = (0..grid.row_count())
+        .flat_map(|row| (0..column_count).map(move |column| (row, column)))
+        .map(|(row, column)| match grid.letter_at(row, column) {
+            grid::EMPTY => (1 << alphabet::letter_count()) - 1,
+            grid::BLOCK => 0,
+            letter => 1 << alphabet::index_of(letter).unwrap(),
+        })
+        .collect();
+    let slots: Vec<Slot> = grid.slots();
+    let mut remaining_words: Vec<Vec<&[u8]>> = slots
+        .iter()
+        .map(|slot| {
+            words
+                .iter()
+                .filter(|word| word.len() == slot.len())
+                .map(|word| word.as_bytes())
+                .collect()
+        })
+        .collect();
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (slot, slot_words) in slots.iter().zip(remaining_words.iter_mut()) {
+            let cells: Vec<usize> = slot
+                .positions()
+                .iter()
+                .map(|pos| pos.row() * column_count + pos.column())
+                .collect();
+            slot_words.retain(|word| {
+                word.iter()
+                    .zip(&cells)
+                    .all(|(&letter, &cell)| domains[cell] & letter_bit(letter) != 0)
+            });
+            for (index, &cell) in cells.iter().enumerate() {
+                let supported = slot_words
+                    .iter()
+                    .fold(0, |letters, word| letters | letter_bit(word[index]));
+                if domains[cell] & supported != domains[cell] {
+                    domains[cell] &= supported;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    let possible: Vec<[bool; alphabet::letter_count()]> = domains
+        .iter()
+        .map(|&domain| {
+            let mut letters = [false; alphabet::letter_count()];
+            for (letter_index, possible) in letters.iter_mut().enumerate() {
+                *possible = domain & (1 << letter_index) != 0;
+            }
+            letters
+        })
+        .collect();
+    CandidateLetters::from(grid, &possible)
+}
+
+/// Returns the bit representing the given letter in a cell domain.
+///
+/// Panics if the letter is not in the [alphabet].
+fn letter_bit(letter: u8) -> u32 {
+    let letter = letter as char;
+    let letter_index =
+        alphabet::index_of(letter).unwrap_or_else(|| panic!("Unsupported character {letter}"));
+    1 << letter_index
+}
+
+impl CandidateLetters {
+    /// Creates an instance from the given possible letters, by cell, row after row. Blocks have no candidate.
+    fn from(grid: &Grid, possible: &[[bool; alphabet::letter_count()]]) -> Self {
+        let column_count = grid.column_count();
+        let letters = possible
+            .iter()
+            .enumerate()
+            .map(|(cell, possible_letters)| {
+                match grid.letter_at(cell / column_count, cell % column_count) {
+                    grid::BLOCK => Vec::new(),
+                    _ => (0..alphabet::letter_count())
+                        .filter(|&letter_index| possible_letters[letter_index])
+                        .map(alphabet::letter_at)
+                        .collect(),
+                }
+            })
+            .collect();
+        CandidateLetters {
+            column_count,
+            letters,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|&word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn approximate_propagates() {
+        let grid = Grid::try_from("A.\n..").unwrap();
+        let words = words(&["AB", "BA", "BB", "CA"]);
+
+        let candidates = approximate(&grid, &words);
+
+        assert_eq!(['A'], candidates.at(0, 0));
+        assert_eq!(['B'], candidates.at(0, 1));
+        assert_eq!(['B'], candidates.at(1, 0));
+        assert_eq!(['A', 'B'], candidates.at(1, 1));
+        assert!(candidates.is_forced(0, 1), "Cell should be forced");
+        assert!(!candidates.is_forced(1, 1), "Cell should not be forced");
+    }
+
+    #[test]
+    fn approximate_blocks_and_isolated_cells() {
+        let grid = Grid::try_from("#.\n..").unwrap();
+        let words = words(&["AB"]);
+
+        let candidates = approximate(&grid, &words);
+
+        assert!(
+            candidates.at(0, 0).is_empty(),
+            "Block should have no candidate"
+        );
+        assert_eq!(['A'], candidates.at(1, 0));
+        assert_eq!(['B'], candidates.at(1, 1));
+        assert_eq!(['A'], candidates.at(0, 1));
+    }
+
+    #[test]
+    fn approximate_no_word() {
+        let grid = Grid::try_from("..\n..").unwrap();
+        let words = words(&["ABC"]);
+
+        let candidates = approximate(&grid, &words);
+
+        assert!(
+            candidates.at(0, 0).is_empty(),
+            "Cell should have no candidate"
+        );
+        assert!(
+            candidates.at(1, 1).is_empty(),
+            "Cell should have no candidate"
+        );
+    }
+}
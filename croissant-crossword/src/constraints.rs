@@ -1,6 +1,9 @@
-use croissant_solver::SolverConfigurator;
+use std::collections::HashMap;
+
+use croissant_solver::{Lit, SolverConfigurator, Var};
 
 use crate::grid::Grid;
+use crate::mdd::Mdd;
 use crate::slot::Slot;
 use crate::variables::{Variables, BLOCK_INDEX, CELL_VALUE_COUNT};
 use crate::{alphabet, grid};
@@ -16,7 +19,9 @@ use crate::{alphabet, grid};
 ///   part, as there must be a correspondence between cell variables and slot variables. Basically,
 ///   each slot variable - i.e. a representation of a (slot,word) pair - is equivalent to a
 ///   conjunction (= and) of cell variables - i.e. (cell,letter) pairs. See
-///   [add_one_word_per_slot_clauses_to]
+///   [add_one_word_per_slot_clauses_to]. Alternatively, the words of the slot can be described
+///   by a multi-valued decision diagram, in which case no slot variable is needed. See
+///   [add_one_word_per_slot_clauses_using_mdds_to].
 /// - Prefilled cells must be kept as is. See [add_input_grid_constraints_are_satisfied_clauses_to].
 ///
 /// Implementation note: Functions here add rules to the solver passed as parameter. Although having
@@ -47,17 +52,16 @@ impl<'wordlist> Constraints<'wordlist> {
     /// Adds the clauses ensuring that each cell must contain exactly one letter from the alphabet -
     /// or a block - to the given solver.
     pub fn add_one_letter_or_block_per_cell_clauses_to(&self, solver: &mut dyn SolverConfigurator) {
-        let mut literals_buffer: Vec<i32> = Vec::with_capacity(CELL_VALUE_COUNT);
+        let mut literals_buffer: Vec<Lit> = Vec::with_capacity(CELL_VALUE_COUNT);
         for row in 0..self.grid.row_count() {
             for column in 0..self.grid.column_count() {
                 for letter_index in 0..alphabet::letter_count() {
                     let letter_variable =
-                        self.variables.representing_cell(row, column, letter_index) as i32;
-                    literals_buffer.push(letter_variable)
+                        self.variables.representing_cell(row, column, letter_index);
+                    literals_buffer.push(letter_variable.into())
                 }
-                let block_variable =
-                    self.variables.representing_cell(row, column, BLOCK_INDEX) as i32;
-                literals_buffer.push(block_variable);
+                let block_variable = self.variables.representing_cell(row, column, BLOCK_INDEX);
+                literals_buffer.push(block_variable.into());
                 solver.add_exactly_one(&literals_buffer);
                 literals_buffer.clear();
             }
@@ -73,7 +77,7 @@ impl<'wordlist> Constraints<'wordlist> {
             for (word_index, word) in self.words.iter().enumerate() {
                 if word.len() == slot.len() {
                     let slot_literal =
-                        self.variables.representing_slot(slot_index, word_index) as i32;
+                        Lit::from(self.variables.representing_slot(slot_index, word_index));
                     slot_literals_buffer.push(slot_literal);
 
                     self.fill_cell_literals_conjunction(&mut cell_literals_buffer, slot, word);
@@ -86,13 +90,128 @@ impl<'wordlist> Constraints<'wordlist> {
         }
     }
 
+    /// Returns the multi-valued decision diagrams of the words, one per slot length.
+    pub fn mdds(&self) -> HashMap<usize, Mdd> {
+        let mut mdds = HashMap::new();
+        for slot in self.grid.slots() {
+            mdds.entry(slot.len())
+                .or_insert_with(|| Mdd::new(self.words, slot.len()));
+        }
+        mdds
+    }
+
+    /// Returns the number of state variables needed by
+    /// [add_one_word_per_slot_clauses_using_mdds_to](Self::add_one_word_per_slot_clauses_using_mdds_to).
+    pub fn mdd_variables_count(&self, mdds: &HashMap<usize, Mdd>) -> usize {
+        self.grid
+            .slots()
+            .iter()
+            .map(|slot| mdds[&slot.len()].inner_node_count())
+            .sum()
+    }
+
+    /// Adds the clauses ensuring that each slot must contain exactly one word from the word list to
+    /// the given solver, using the given multi-valued decision diagrams - one per slot length, see
+    /// [mdds](Self::mdds).
+    ///
+    /// No slot variable is used. Instead, for each slot, a state variable is associated to each
+    /// inner node of the diagram. State variables are placed after the cell variables. A state
+    /// variable is true if the letters of the slot read so far lead to the corresponding node. The
+    /// clauses are:
+    ///
+    /// - The state of the root node is true.
+    /// - If the state of a node is true, then the next cell of the slot contains one of the letters
+    ///   labelling the outgoing edges of the node.
+    /// - If the state of a node is true and the next cell contains the letter labelling one of its
+    ///   outgoing edges, then the state of the target node is true. The terminal node has no state:
+    ///   Reaching it just means that the slot contains a word.
+    pub fn add_one_word_per_slot_clauses_using_mdds_to(
+        &self,
+        mdds: &HashMap<usize, Mdd>,
+        solver: &mut dyn SolverConfigurator,
+    ) {
+        let mut first_state_variable = self.variables.representing_cell_count() + 1;
+        for slot in self.grid.slots() {
+            first_state_variable = self.add_one_word_in_slot_clauses_using_mdd_to(
+                &slot,
+                &mdds[&slot.len()],
+                first_state_variable,
+                None,
+                solver,
+            );
+        }
+    }
+
+    /// Adds the clauses ensuring that the given slot contains one of the words of the given
+    /// multi-valued decision diagram to the given solver. See
+    /// [add_one_word_per_slot_clauses_using_mdds_to](Self::add_one_word_per_slot_clauses_using_mdds_to)
+    /// for the clauses.
+    ///
+    /// State variables are numbered from the given first state variable. If an activation literal
+    /// is given, the constraint only holds when this literal is true: Only the clause making the
+    /// state of the root node true depends on it, since other clauses are trivially satisfied when
+    /// all states are false.
+    ///
+    /// Returns the variable following the last state variable.
+    pub fn add_one_word_in_slot_clauses_using_mdd_to(
+        &self,
+        slot: &Slot,
+        mdd: &Mdd,
+        first_state_variable: usize,
+        activation_literal: Option<Lit>,
+        solver: &mut dyn SolverConfigurator,
+    ) -> usize {
+        let mut clause_buffer = Vec::with_capacity(alphabet::letter_count() + 1);
+        clause_buffer.extend(activation_literal.map(|literal| -literal));
+        if mdd.is_empty() {
+            // No word fits this slot
+            solver.add_clause(&clause_buffer);
+            return first_state_variable;
+        }
+        let mut next_state_variable = first_state_variable;
+        let mut first_state_variable_of_level = Vec::with_capacity(slot.len());
+        for level in 0..slot.len() {
+            first_state_variable_of_level.push(next_state_variable);
+            next_state_variable += mdd.nodes_at(level).len();
+        }
+        clause_buffer.push(Var::new(first_state_variable_of_level[0]).into());
+        solver.add_clause(&clause_buffer);
+        clause_buffer.clear();
+        for (level, pos) in slot.positions().iter().enumerate() {
+            for (node_index, node) in mdd.nodes_at(level).iter().enumerate() {
+                let state = Lit::from(Var::new(first_state_variable_of_level[level] + node_index));
+                clause_buffer.push(-state);
+                for &(letter_index, _) in node {
+                    let cell_var =
+                        self.variables
+                            .representing_cell(pos.row(), pos.column(), letter_index);
+                    clause_buffer.push(cell_var.into());
+                }
+                solver.add_clause(&clause_buffer);
+                clause_buffer.clear();
+
+                if level + 1 == slot.len() {
+                    continue; // Target is the terminal node
+                }
+                for &(letter_index, target) in node {
+                    let cell_var =
+                        self.variables
+                            .representing_cell(pos.row(), pos.column(), letter_index);
+                    let target_state = Var::new(first_state_variable_of_level[level + 1] + target);
+                    solver.add_clause(&[-state, -Lit::from(cell_var), target_state.into()]);
+                }
+            }
+        }
+        next_state_variable
+    }
+
     /// Fills the given vector with the cell literals whose conjunction (= and) is equivalent to the
     /// slot variable of the given slot and word.
     ///
     /// Panics if the given word contains a letter which is not in the [alphabet].
-    fn fill_cell_literals_conjunction(
+    pub fn fill_cell_literals_conjunction(
         &self,
-        cell_literals: &mut Vec<i32>,
+        cell_literals: &mut Vec<Lit>,
         slot: &Slot,
         word: &str,
     ) {
@@ -103,7 +222,7 @@ impl<'wordlist> Constraints<'wordlist> {
             let cell_var =
                 self.variables
                     .representing_cell(slot_pos.row(), slot_pos.column(), letter_index);
-            cell_literals.push(cell_var as i32)
+            cell_literals.push(cell_var.into())
         }
     }
 
@@ -119,14 +238,19 @@ impl<'wordlist> Constraints<'wordlist> {
                 let literal = match prefilled_letter {
                     grid::EMPTY => {
                         // Disallow solver to create a block
-                        -(self.variables.representing_cell(row, column, BLOCK_INDEX) as i32)
-                    }
-                    grid::BLOCK => {
-                        self.variables.representing_cell(row, column, BLOCK_INDEX) as i32
+                        self.variables
+                            .representing_cell(row, column, BLOCK_INDEX)
+                            .literal(false)
                     }
+                    grid::BLOCK => self
+                        .variables
+                        .representing_cell(row, column, BLOCK_INDEX)
+                        .into(),
                     _ => {
                         let letter_index = alphabet::index_of(prefilled_letter).unwrap();
-                        self.variables.representing_cell(row, column, letter_index) as i32
+                        self.variables
+                            .representing_cell(row, column, letter_index)
+                            .into()
                     }
                 };
                 solver.add_clause(&[literal]);
@@ -158,22 +282,25 @@ mod test {
     }
 
     impl SolverConfigurator for TestSolverConfigurator {
-        fn add_clause(&mut self, literals: &[i32]) {
-            let literals_copy = literals.to_vec();
-            self.clauses.push(literals_copy)
+        fn add_clause(&mut self, literals: &[Lit]) {
+            self.clauses.push(raw(literals))
         }
 
-        fn add_exactly_one(&mut self, literals: &[i32]) {
-            let literals_copy = literals.to_vec();
-            self.exactly_one_clauses.push(literals_copy)
+        fn add_exactly_one(&mut self, literals: &[Lit]) {
+            self.exactly_one_clauses.push(raw(literals))
         }
 
-        fn add_and(&mut self, literal: i32, conjunction: &[i32]) {
-            let conjunction_copy = conjunction.to_vec();
-            self.and_clauses.insert(literal, conjunction_copy);
+        fn add_and(&mut self, literal: Lit, conjunction: &[Lit]) {
+            self.and_clauses
+                .insert(i32::from(literal), raw(conjunction));
         }
     }
 
+    /// Returns the raw representations of the given literals.
+    fn raw(literals: &[Lit]) -> Vec<i32> {
+        literals.iter().map(|&literal| i32::from(literal)).collect()
+    }
+
     #[test]
     fn constraints_add_one_letter_or_block_per_cell_clauses_to() {
         let mut test_solver = TestSolverConfigurator::new();
@@ -261,6 +388,70 @@ mod test {
         );
     }
 
+    #[test]
+    fn add_one_word_per_slot_clauses_using_mdds_to() {
+        let mut test_solver = TestSolverConfigurator::new();
+        let grid = Grid::try_from("..").unwrap();
+        let words: Vec<String> = ["AB", "BA", "ABC"]
+            .iter()
+            .map(|&word| word.to_string())
+            .collect();
+        let variables = Variables::new(grid.clone(), words.len());
+        let constraints = Constraints::new(grid, variables, &words);
+        let mdds = constraints.mdds();
+
+        constraints.add_one_word_per_slot_clauses_using_mdds_to(&mdds, &mut test_solver);
+
+        assert_eq!(3, constraints.mdd_variables_count(&mdds));
+        assert_eq!(
+            vec![
+                vec![55],          // root state
+                vec![-55, 1, 2],   // root state => 'A' or 'B' at (0,0)
+                vec![-55, -1, 56], // root state and 'A' at (0,0) => state after 'A'
+                vec![-55, -2, 57], // root state and 'B' at (0,0) => state after 'B'
+                vec![-56, 29],     // state after 'A' => 'B' at (1,0)
+                vec![-57, 28],     // state after 'B' => 'A' at (1,0)
+            ],
+            test_solver.clauses
+        );
+        assert!(
+            test_solver.exactly_one_clauses.is_empty(),
+            "Unexpected clauses"
+        );
+        assert!(test_solver.and_clauses.is_empty(), "Unexpected clauses");
+    }
+
+    #[test]
+    fn add_one_word_in_slot_clauses_using_mdd_to_with_activation_literal() {
+        let mut test_solver = TestSolverConfigurator::new();
+        let grid = Grid::try_from("..").unwrap();
+        let words: Vec<String> = ["AB", "BA"].iter().map(|&word| word.to_string()).collect();
+        let variables = Variables::new(grid.clone(), words.len());
+        let constraints = Constraints::new(grid, variables, &words);
+        let mdd = Mdd::new(&words, 2);
+
+        let next_variable = constraints.add_one_word_in_slot_clauses_using_mdd_to(
+            &Slot::across(0, 2, 0),
+            &mdd,
+            100,
+            Some(Lit::from_raw(99)),
+            &mut test_solver,
+        );
+
+        assert_eq!(103, next_variable);
+        assert_eq!(
+            vec![
+                vec![-99, 100],      // activation literal => root state
+                vec![-100, 1, 2],    // root state => 'A' or 'B' at (0,0)
+                vec![-100, -1, 101], // root state and 'A' at (0,0) => state after 'A'
+                vec![-100, -2, 102], // root state and 'B' at (0,0) => state after 'B'
+                vec![-101, 29],      // state after 'A' => 'B' at (1,0)
+                vec![-102, 28],      // state after 'B' => 'A' at (1,0)
+            ],
+            test_solver.clauses
+        );
+    }
+
     #[test]
     fn add_input_grid_constraints_are_satisfied_clauses_to() {
         let mut test_solver = TestSolverConfigurator::new();
@@ -0,0 +1,57 @@
+use croissant_solver::{Lit, Model, SolveOutcome, Solver, SolverError};
+
+use crate::alphabet;
+use crate::grid::{self, Grid};
+use crate::pos::Pos;
+use crate::variables::Variables;
+
+/// Counts exactly the fills of the given grid, i.e. the distinct assignments of the letters of its empty cells, using
+/// the given solver. Auxiliary variables are projected out: Fills differing only by auxiliary variables count as one.
+///
+/// The solver must contain the problem encoded with cell variables as defined by [Variables], and support solving
+/// under assumptions. The count is made by branching on the letters of the empty cells, one cell after the other,
+/// each branch being checked by solving under assumptions; Unsatisfiable branches are pruned. All calls are made on
+/// the same solver, which thus keeps its learnt clauses from a call to the other. The number of calls grows with the
+/// number of fills: This is meant for small grids.
+///
+/// Returns an error if the solver does not support solving under assumptions, or if a search is interrupted.
+pub fn count(grid: &Grid, solver: &mut dyn Solver<Item = Model>) -> Result<u64, SolverError> {
+    let variables = Variables::new(grid.clone(), 0);
+    let empty_cells: Vec<Pos> = (0..grid.row_count())
+        .flat_map(|row| (0..grid.column_count()).map(move |column| Pos::new(column, row)))
+        .filter(|pos| grid.letter_at(pos.row(), pos.column()) == grid::EMPTY)
+        .collect();
+    count_under(&variables, &empty_cells, &mut Vec::new(), solver)
+}
+
+/// Counts the fills compatible with the given assumptions, which assign the first empty cells.
+fn count_under(
+    variables: &Variables,
+    empty_cells: &[Pos],
+    assumptions: &mut Vec<Lit>,
+    solver: &mut dyn Solver<Item = Model>,
+) -> Result<u64, SolverError> {
+    match solver.solve_with_assumptions(assumptions) {
+        SolveOutcome::Sat(_) => {}
+        SolveOutcome::Unsat => return Ok(0),
+        SolveOutcome::Interrupted => return Err(SolverError::new("Search interrupted")),
+        SolveOutcome::Unsupported => {
+            return Err(SolverError::new(
+                "Solver does not support solving under assumptions",
+            ))
+        }
+        SolveOutcome::Failed(error) => return Err(error),
+    }
+    let Some(cell) = empty_cells.get(assumptions.len()) else {
+        // All empty cells are assigned
+        return Ok(1);
+    };
+    let mut count = 0;
+    for letter_index in 0..alphabet::letter_count() {
+        let variable = variables.representing_cell(cell.row(), cell.column(), letter_index);
+        assumptions.push(variable.into());
+        count += count_under(variables, empty_cells, assumptions, solver)?;
+        assumptions.pop();
+    }
+    Ok(count)
+}
@@ -18,6 +18,11 @@
 //!    conjunction (= and) of cell variables - i.e. (cell,letter) pairs.
 //! 3. Prefilled cells must be kept as is.
 //!
+//! ## Alternative encodings
+//!
+//! This is the default encoding, [DirectEncoding]. Other encodings can be chosen using
+//! [Crossword::with_encoding]. See [encoding](crate::encoding).
+//!
 //! ## See Also
 //!
 //! - [Martin Hořeňovský's introduction to SAT solvers](https://codingnest.com/modern-sat-solvers-fast-neat-underused-part-1-of-n/). It very clearly explains the basics with the
@@ -27,19 +32,48 @@
 //! - [Croiseur's crossword solver backed by Sat4j](https://gitlab.com/super7ramp/croiseur/-/tree/master/croiseur-solver/croiseur-solver-sat),
 //!   which is the original implementation in Java of this program.
 
+use std::borrow::Cow;
+use std::io::{self, Seek, Write};
 use std::ops::DerefMut;
+use std::time::{Duration, Instant};
 
 use croissant_solver::SolverBuilder;
-use croissant_solver::{ConfigurableSolver, Solver, SolverConfigurator};
+use croissant_solver::{
+    ConfigurableSolver, DimacsWriter, Model, ProgressCallback, SearchLimits, SolveOutcome, Solver,
+    SolverConfigurator, SolverError, SolverFactory, SolverStats,
+};
 
-use crate::constraints::Constraints;
+use crate::candidates;
+pub use crate::candidates::CandidateLetters;
+use crate::counting;
+use crate::cubes;
+pub use crate::cubes::{CubeAndConquer, Splitting};
+use crate::diversity;
+pub use crate::diversity::Diversity;
+use crate::encoding::{Decoder, DirectEncoding, Encoding, MddEncoding};
+use crate::explanation;
+pub use crate::explanation::Explanation;
+use crate::export;
 use crate::grid::Grid;
+pub use crate::native::NativeSolverBuilder;
+use crate::random;
+use crate::slot::Slot;
+use crate::trivial;
+use crate::variable_counter::VariableCounter;
 use crate::variables::Variables;
+use crate::viable;
+pub use crate::viable::ViableWords;
 
-/// The crossword structure, holding variables and constraints information.
+/// The crossword structure, holding the problem definition and the way to encode it.
 pub struct Crossword<'wordlist> {
-    variables: Variables,
-    constraints: Constraints<'wordlist>,
+    grid: Grid,
+    words: &'wordlist [String],
+    scores: &'wordlist [i32],
+    encoding: Box<dyn Encoding>,
+    seed: Option<u64>,
+    score_bias: f64,
+    limits: SearchLimits,
+    progress: Option<ProgressCallback>,
 }
 
 impl<'wordlist> Crossword<'wordlist> {
@@ -64,96 +98,797 @@ impl<'wordlist> Crossword<'wordlist> {
     /// ```
     pub fn try_from(input_grid: &str, words: &'wordlist [String]) -> Result<Self, String> {
         let grid = Grid::try_from(input_grid)?;
-        let variables = Variables::new(grid.clone(), words.len());
-        let constraints = Constraints::new(grid, variables.clone(), words);
         Ok(Crossword {
-            variables,
-            constraints,
+            grid,
+            words,
+            scores: &[],
+            encoding: Box::new(DirectEncoding),
+            seed: None,
+            score_bias: 0.0,
+            limits: SearchLimits::default(),
+            progress: None,
         })
     }
 
+    /// Sets the [Encoding] of the problem. Default is [DirectEncoding]. Solutions are the same
+    /// whatever the encoding; Only the size of the problem and the time to solve it differ.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use croissant_crossword::crossword::Crossword;
+    /// use croissant_crossword::encoding::MddEncoding;
+    ///
+    /// let words = ["AAA".to_string()];
+    /// let crossword = Crossword::try_from("A..\n.#.\n...", &words)
+    ///     .unwrap()
+    ///     .with_encoding(Box::new(MddEncoding));
+    /// ```
+    pub fn with_encoding(mut self, encoding: Box<dyn Encoding>) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Sets the scores of the words: The score of a word is the one at the same index. Words without score have a
+    /// score of 0. Scores rank the words, the higher the better. By default, all words have the same score.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use croissant_crossword::crossword::Crossword;
+    ///
+    /// let words = ["AAA".to_string(), "BBB".to_string()];
+    /// let scores = [10, 50];
+    /// let crossword = Crossword::try_from("A..\n.#.\n...", &words)
+    ///     .unwrap()
+    ///     .with_word_scores(&scores);
+    /// ```
+    pub fn with_word_scores(mut self, scores: &'wordlist [i32]) -> Self {
+        self.scores = scores;
+        self
+    }
+
+    /// Sets the seed randomizing the solutions. By default, there is no randomness: Solving the same problem always
+    /// gives the same solutions. With a seed, solving the same problem with the same seed gives the same solutions,
+    /// and different seeds usually give different solutions.
+    ///
+    /// The seed shuffles the words before encoding the problem and is given to the solver, which may use it to
+    /// randomize its search, see [SolverConfigurator::set_seed].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use croissant_crossword::crossword::Crossword;
+    ///
+    /// let words = ["AAA".to_string(), "BBB".to_string()];
+    /// let crossword = Crossword::try_from("A..\n.#.\n...", &words)
+    ///     .unwrap()
+    ///     .with_seed(42);
+    /// ```
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets how much the randomness favors the words with the highest scores, see
+    /// [with_word_scores](Self::with_word_scores). Default is 0, i.e. no favor at all; The higher the bias, the more
+    /// high-scored words come first in the shuffled words. Only relevant with a seed, see [with_seed](Self::with_seed).
+    pub fn with_score_bias(mut self, score_bias: f64) -> Self {
+        self.score_bias = score_bias;
+        self
+    }
+
+    /// Sets the limits of each search of the solver: A time budget, a conflict budget and/or a cancellation token, see
+    /// [SolverConfigurator::set_limits]. By default, there is no limit. When a limit is reached, the search is
+    /// interrupted, see [CrosswordSolutions::is_interrupted].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use croissant_crossword::crossword::Crossword;
+    /// use croissant_solver::SearchLimits;
+    /// use std::time::Duration;
+    ///
+    /// let words = ["AAA".to_string()];
+    /// let limits = SearchLimits {
+    ///     timeout: Some(Duration::from_secs(10)),
+    ///     ..SearchLimits::default()
+    /// };
+    /// let crossword = Crossword::try_from("A..\n.#.\n...", &words)
+    ///     .unwrap()
+    ///     .with_limits(limits);
+    /// ```
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the function to call with the statistics of the solver while it searches solutions, in order to report
+    /// progress, see [SolverConfigurator::set_progress_callback]. Statistics are those known by the solver: See also
+    /// [CrosswordSolutions::stats].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use croissant_crossword::crossword::Crossword;
+    ///
+    /// let words = ["AAA".to_string()];
+    /// let crossword = Crossword::try_from("A..\n.#.\n...", &words)
+    ///     .unwrap()
+    ///     .with_progress(Box::new(|stats| eprintln!("{stats}")));
+    /// ```
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress = Some(callback);
+        self
+    }
+
     /// Solves this problem with the solver built using given [SolverBuilder]. Note that solution may not be actually
     /// computed when this function returns: It may be created as late as when calling the created
     /// [CrosswordSolutions::next].
+    ///
+    /// Trivial problems are solved without any solver: A grid without slot and without empty cell, e.g. an empty grid,
+    /// has a single solution, itself; A grid with slots but an empty word list has no solution. The same goes for the
+    /// other `solve_*` functions.
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the [CrosswordSolutions], or a [SolverError] if the solver cannot be built.
     pub fn solve_with_solver_built_by(
-        self,
+        mut self,
         mut solver_builder: Box<dyn SolverBuilder>,
-    ) -> CrosswordSolutions {
-        self.add_clauses_to(solver_builder.deref_mut());
-        let solver = solver_builder.build();
-        CrosswordSolutions::new(self.variables, solver)
+    ) -> Result<CrosswordSolutions, SolverError> {
+        if let Some(solutions) = self.trivial_solutions() {
+            return Ok(solutions);
+        }
+        let started = Instant::now();
+        let (decoder, mut encoding_stats, next_variable) =
+            self.add_clauses_to(solver_builder.deref_mut());
+        let solver = solver_builder.build()?;
+        encoding_stats.encoding_time = started.elapsed();
+        Ok(CrosswordSolutions::new(
+            self.grid,
+            decoder,
+            solver,
+            encoding_stats,
+            next_variable,
+        ))
     }
 
     /// Solves this problem with given [ConfigurableSolver]. Note that solution may not be actually computed when this
     /// function returns: It may be created as late as when calling the created [CrosswordSolutions::next].
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the [CrosswordSolutions], or a [SolverError] if the solver cannot be configured.
     pub fn solve_with(
+        mut self,
+        mut solver: Box<dyn ConfigurableSolver<Item = Model>>,
+    ) -> Result<CrosswordSolutions, SolverError> {
+        if let Some(solutions) = self.trivial_solutions() {
+            return Ok(solutions);
+        }
+        let started = Instant::now();
+        let (decoder, mut encoding_stats, next_variable) = self.add_clauses_to(solver.deref_mut());
+        encoding_stats.encoding_time = started.elapsed();
+        Ok(CrosswordSolutions::new(
+            self.grid,
+            decoder,
+            solver,
+            encoding_stats,
+            next_variable,
+        ))
+    }
+
+    /// Solves this problem with the solver built using given [NativeSolverBuilder], i.e. without clauses. The chosen
+    /// [Encoding] is ignored; The seed and the scores of the words, the limits and the progress callback are given to
+    /// the builder. Solutions are the same as with any other solver, up to their order.
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the [CrosswordSolutions], or a [SolverError] if the solver cannot be built.
+    pub fn solve_natively_with(
+        mut self,
+        solver_builder: Box<dyn NativeSolverBuilder>,
+    ) -> Result<CrosswordSolutions, SolverError> {
+        if let Some(solutions) = self.trivial_solutions() {
+            return Ok(solutions);
+        }
+        let started = Instant::now();
+        let decoder = Box::new(Variables::new(self.grid.clone(), 0));
+        let solver = solver_builder.build(
+            &self.grid,
+            &self.encoded_words(),
+            decoder.as_ref(),
+            self.limits.clone(),
+            self.progress.take(),
+        )?;
+        let encoding_stats = SolverStats {
+            variables: decoder.representing_cell_count(),
+            encoding_time: started.elapsed(),
+            ..SolverStats::default()
+        };
+        let next_variable = encoding_stats.variables + 1;
+        Ok(CrosswordSolutions::new(
+            self.grid,
+            decoder,
+            solver,
+            encoding_stats,
+            next_variable,
+        ))
+    }
+
+    /// Explains why this problem has no solution, using the given [ConfigurableSolver]. The solver must support
+    /// solving under assumptions, see [Solver::solve_with_assumptions].
+    ///
+    /// The prefilled letters and the slot constraints are put behind selector literals; Then, an unsatisfiable core of
+    /// these selectors is extracted and minimized. The chosen [Encoding] is ignored: Slots are always encoded with
+    /// decision diagrams, see [MddEncoding](crate::encoding::MddEncoding).
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the [Explanation], or [None] if the problem actually has a solution, or a [SolverError]
+    /// containing the error details.
+    pub fn explain_with(
+        self,
+        mut solver: Box<dyn ConfigurableSolver<Item = Model>>,
+    ) -> Result<Option<Explanation>, SolverError> {
+        solver.set_limits(self.limits.clone());
+        let selectors =
+            explanation::add_clauses_with_selectors_to(&self.grid, self.words, solver.deref_mut());
+        explanation::explain(&self.grid, selectors, solver.deref_mut())
+    }
+
+    /// Explains why this problem has no solution, using the solver built by the given [SolverBuilder]. See
+    /// [explain_with](Self::explain_with).
+    pub fn explain_with_solver_built_by(
+        self,
+        mut solver_builder: Box<dyn SolverBuilder>,
+    ) -> Result<Option<Explanation>, SolverError> {
+        solver_builder.set_limits(self.limits.clone());
+        let selectors = explanation::add_clauses_with_selectors_to(
+            &self.grid,
+            self.words,
+            solver_builder.deref_mut(),
+        );
+        let mut solver = solver_builder.build()?;
+        explanation::explain(&self.grid, selectors, solver.deref_mut())
+    }
+
+    /// Computes the letters which may appear in each cell of this problem, using the given [ConfigurableSolver]. The
+    /// solver must support solving under assumptions, see [Solver::solve_with_assumptions].
+    ///
+    /// The result is exact: A letter is a candidate for a cell iff it appears in this cell in at least one solution.
+    /// Cells with a single candidate are forced. If the problem has no solution, no cell has any candidate. The chosen
+    /// [Encoding] is ignored: Slots are always encoded with decision diagrams, see [MddEncoding].
+    ///
+    /// See [approximate_candidate_letters](Self::approximate_candidate_letters) for a cheaper alternative.
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the [CandidateLetters], or a [SolverError] containing the error details.
+    pub fn candidate_letters_with(
+        self,
+        mut solver: Box<dyn ConfigurableSolver<Item = Model>>,
+    ) -> Result<CandidateLetters, SolverError> {
+        self.encode_with_cell_variables(solver.deref_mut());
+        candidates::compute(&self.grid, solver.deref_mut())
+    }
+
+    /// Computes the letters which may appear in each cell of this problem, using the solver built by the given
+    /// [SolverBuilder]. See [candidate_letters_with](Self::candidate_letters_with).
+    pub fn candidate_letters_with_solver_built_by(
+        self,
+        mut solver_builder: Box<dyn SolverBuilder>,
+    ) -> Result<CandidateLetters, SolverError> {
+        self.encode_with_cell_variables(solver_builder.deref_mut());
+        let mut solver = solver_builder.build()?;
+        candidates::compute(&self.grid, solver.deref_mut())
+    }
+
+    /// Approximates the letters which may appear in each cell of this problem, without any solver, by propagating the
+    /// word list through the slots until a fixed point is reached.
+    ///
+    /// The result is an over-approximation of [candidate_letters_with](Self::candidate_letters_with): Every actual
+    /// candidate is present, but some letters may not appear in any solution.
+    pub fn approximate_candidate_letters(&self) -> CandidateLetters {
+        candidates::approximate(&self.grid, self.words)
+    }
+
+    /// Returns the words which can go in the given slot, i.e. which have at least one full completion of the grid, by
+    /// descending score, see [with_word_scores](Self::with_word_scores). The given [ConfigurableSolver] must support
+    /// solving under assumptions, see [Solver::solve_with_assumptions].
+    ///
+    /// Words are checked lazily, when iterating: The first words come fast, without waiting for the whole word list
+    /// to be checked. The chosen [Encoding] is ignored: Slots are always encoded with decision diagrams, see
+    /// [MddEncoding].
+    ///
+    /// See [viable_words_by_crossings](Self::viable_words_by_crossings) for a cheaper alternative.
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the [ViableWords], or a [SolverError] containing the error details, e.g. if the slot is not a
+    /// slot of the grid.
+    pub fn viable_words_with(
+        self,
+        slot: &Slot,
+        mut solver: Box<dyn ConfigurableSolver<Item = Model>>,
+    ) -> Result<ViableWords<'wordlist>, SolverError> {
+        viable::validate(&self.grid, slot).map_err(SolverError::new)?;
+        self.encode_with_cell_variables(solver.deref_mut());
+        ViableWords::new(&self.grid, slot, self.words, self.scores, solver)
+    }
+
+    /// Returns the words which can go in the given slot, using the solver built by the given [SolverBuilder]. See
+    /// [viable_words_with](Self::viable_words_with).
+    pub fn viable_words_with_solver_built_by(
+        self,
+        slot: &Slot,
+        mut solver_builder: Box<dyn SolverBuilder>,
+    ) -> Result<ViableWords<'wordlist>, SolverError> {
+        viable::validate(&self.grid, slot).map_err(SolverError::new)?;
+        self.encode_with_cell_variables(solver_builder.deref_mut());
+        let solver = solver_builder.build()?;
+        ViableWords::new(&self.grid, slot, self.words, self.scores, solver)
+    }
+
+    /// Returns the words which fit the given slot and its crossing slots, by descending score, without any solver.
+    ///
+    /// Only the crossing slots are checked, independently of each other: The result contains all the words returned
+    /// by [viable_words_with](Self::viable_words_with), but some of them may have no full completion.
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the words, or a String containing the error details, e.g. if the slot is not a slot of the grid.
+    pub fn viable_words_by_crossings(&self, slot: &Slot) -> Result<Vec<String>, String> {
+        viable::validate(&self.grid, slot)?;
+        Ok(viable::check_crossings(
+            &self.grid,
+            slot,
+            self.words,
+            self.scores,
+        ))
+    }
+
+    /// Counts exactly the solutions of this problem, using the given [ConfigurableSolver]. The solver must support
+    /// solving under assumptions, see [Solver::solve_with_assumptions].
+    ///
+    /// Contrary to [CrosswordSolutions::count_solutions], the count is made by branching on the cells rather than by
+    /// enumerating the solutions one by one. It is still exponential though: Use it on small grids. The chosen
+    /// [Encoding] is ignored: Slots are always encoded with decision diagrams, see [MddEncoding].
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the number of solutions, or a [SolverError] containing the error details.
+    pub fn count_solutions_exactly_with(
+        self,
+        mut solver: Box<dyn ConfigurableSolver<Item = Model>>,
+    ) -> Result<u64, SolverError> {
+        self.encode_with_cell_variables(solver.deref_mut());
+        counting::count(&self.grid, solver.deref_mut())
+    }
+
+    /// Counts exactly the solutions of this problem, using the solver built by the given [SolverBuilder]. See
+    /// [count_solutions_exactly_with](Self::count_solutions_exactly_with).
+    pub fn count_solutions_exactly_with_solver_built_by(
+        self,
+        mut solver_builder: Box<dyn SolverBuilder>,
+    ) -> Result<u64, SolverError> {
+        self.encode_with_cell_variables(solver_builder.deref_mut());
+        let mut solver = solver_builder.build()?;
+        counting::count(&self.grid, solver.deref_mut())
+    }
+
+    /// Exports this problem in [DIMACS CNF](DimacsWriter) format to the given writer, as it would be given to a solver,
+    /// i.e. with the chosen [Encoding] and seed. What the variables stand for is written in JSON to the given sidecar
+    /// writer, see [Encoding::meanings]:
+    ///
+    /// ```json
+    /// {
+    ///   "variables": [
+    ///     {"variable": 1, "cell": {"row": 0, "column": 0}, "value": "A"},
+    ///     {"variable": 55, "slot": {"row": 0, "column": 0, "direction": "across", "length": 2}, "word": "AB"}
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the CNF writer, positioned after the last clause, or a [SolverError] containing the error
+    /// details, e.g. if a writer fails.
+    pub fn export_cnf<W: Write + Seek>(
+        mut self,
+        cnf: W,
+        sidecar: &mut dyn Write,
+    ) -> Result<W, SolverError> {
+        let to_error = |error: io::Error| SolverError::new(format!("Cannot write CNF: {error}"));
+        let mut dimacs_writer = DimacsWriter::new(cnf).map_err(to_error)?;
+        self.add_clauses_to(&mut dimacs_writer);
+        let cnf = dimacs_writer.finish().map_err(to_error)?;
+        let meanings = self.encoding.meanings(&self.grid, &self.encoded_words());
+        export::write_meanings(&meanings, sidecar)
+            .map_err(|error| SolverError::new(format!("Cannot write variables: {error}")))?;
+        Ok(cnf)
+    }
+
+    /// Solves this problem by cube-and-conquer, using the solvers created by the given [SolverFactory]: The problem is
+    /// split into many sub-problems, the cubes, which are solved concurrently by as many solvers as threads, see
+    /// [CubeAndConquer]. Solvers must support solving under assumptions, see [Solver::solve_with_assumptions].
+    ///
+    /// Search stops at the first satisfiable cube: When several threads find a solution at the same time, which one is
+    /// returned is not specified. The chosen [Encoding], seed and progress callback are ignored: Slots are always
+    /// encoded with decision diagrams, see [MddEncoding]. The [SearchLimits] apply to each cube.
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the solution, or [None] if the problem has no solution, or a [SolverError] containing the error
+    /// details.
+    pub fn solve_by_cubes_with(
+        self,
+        factory: SolverFactory,
+        options: &CubeAndConquer,
+    ) -> Result<Option<String>, SolverError> {
+        let solutions = self.conquer(&factory, options, 1)?;
+        Ok(solutions.into_iter().next())
+    }
+
+    /// Solves the cubes of this problem, using the solvers created by the given [SolverFactory], until the given
+    /// number of them are found satisfiable. Use [usize::MAX] to solve all the cubes. See
+    /// [solve_by_cubes_with](Self::solve_by_cubes_with).
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with one solution per satisfiable cube, by cube order, at most `limit` of them, or a [SolverError]
+    /// containing the error details. Cubes being disjoint, the solutions are all different.
+    pub fn solve_all_cubes_with(
         self,
-        mut solver: Box<dyn ConfigurableSolver<Item = Vec<i32>>>,
-    ) -> CrosswordSolutions {
-        self.add_clauses_to(solver.deref_mut());
-        CrosswordSolutions::new(self.variables, solver)
-    }
-
-    /// Adds clauses to the given solver configurator.
-    fn add_clauses_to(&self, solver_configurator: &mut dyn SolverConfigurator) {
-        solver_configurator.allocate_variables(self.variables.count());
-        solver_configurator.set_relevant_variables(self.variables.representing_cells());
-        self.constraints
-            .add_one_letter_or_block_per_cell_clauses_to(solver_configurator);
-        self.constraints
-            .add_one_word_per_slot_clauses_to(solver_configurator);
-        self.constraints
-            .add_input_grid_constraints_are_satisfied_clauses_to(solver_configurator);
+        factory: SolverFactory,
+        options: &CubeAndConquer,
+        limit: usize,
+    ) -> Result<Vec<String>, SolverError> {
+        self.conquer(&factory, options, limit)
+    }
+
+    /// Splits this problem into cubes then solves them, until the given number of them are found satisfiable.
+    fn conquer(
+        &self,
+        factory: &SolverFactory,
+        options: &CubeAndConquer,
+        limit: usize,
+    ) -> Result<Vec<String>, SolverError> {
+        let cubes = cubes::split(&self.grid, self.words, options);
+        cubes::conquer(
+            &self.grid,
+            self.words,
+            &cubes,
+            factory,
+            options.thread_count(),
+            &self.limits,
+            limit,
+        )
+    }
+
+    /// Returns the solutions of this problem if they are known without searching, see [trivial::solver].
+    fn trivial_solutions(&self) -> Option<CrosswordSolutions> {
+        let solver = trivial::solver(&self.grid, self.words)?;
+        let decoder = Box::new(Variables::new(self.grid.clone(), 0));
+        let encoding_stats = SolverStats {
+            variables: decoder.representing_cell_count(),
+            ..SolverStats::default()
+        };
+        let next_variable = encoding_stats.variables + 1;
+        Some(CrosswordSolutions::new(
+            self.grid.clone(),
+            decoder,
+            Box::new(solver),
+            encoding_stats,
+            next_variable,
+        ))
+    }
+
+    /// Returns the words in the order given to the [Encoding], i.e. shuffled if a seed is set.
+    fn encoded_words(&self) -> Cow<'wordlist, [String]> {
+        match self.seed {
+            None => Cow::Borrowed(self.words),
+            Some(seed) => Cow::Owned(random::shuffle(
+                self.words,
+                self.scores,
+                seed,
+                self.score_bias,
+            )),
+        }
+    }
+
+    /// Adds clauses with cell variables to the given solver configurator, ignoring the chosen [Encoding].
+    fn encode_with_cell_variables(&self, solver_configurator: &mut dyn SolverConfigurator) {
+        solver_configurator.set_limits(self.limits.clone());
+        MddEncoding.encode(&self.grid, self.words, solver_configurator);
+    }
+
+    /// Adds clauses to the given solver configurator. Returns the decoder of the solver models, the numbers of variables
+    /// and clauses, and the first variable not used by the clauses.
+    fn add_clauses_to(
+        &mut self,
+        solver_configurator: &mut dyn SolverConfigurator,
+    ) -> (Box<dyn Decoder>, SolverStats, usize) {
+        let mut variable_counter = VariableCounter::new(solver_configurator);
+        variable_counter.set_limits(self.limits.clone());
+        if let Some(progress) = self.progress.take() {
+            variable_counter.set_progress_callback(progress);
+        }
+        if let Some(seed) = self.seed {
+            variable_counter.set_seed(seed);
+        }
+        let decoder =
+            self.encoding
+                .encode(&self.grid, &self.encoded_words(), &mut variable_counter);
+        let encoding_stats = SolverStats {
+            variables: variable_counter.next_variable() - 1,
+            clauses: variable_counter.clause_count(),
+            ..SolverStats::default()
+        };
+        (decoder, encoding_stats, variable_counter.next_variable())
     }
 }
 
 /// An iterator over crossword solutions.
 pub struct CrosswordSolutions {
-    variables: Variables,
-    solver: Box<dyn Solver<Item = Vec<i32>>>,
+    grid: Grid,
+    decoder: Box<dyn Decoder>,
+    solver: Box<dyn Solver<Item = Model>>,
+    diversity: Option<Diversity>,
+    next_variable: usize,
+    interrupted: bool,
+    error: Option<SolverError>,
+    encoding_stats: SolverStats,
+    solving_time: Duration,
 }
 
 impl CrosswordSolutions {
-    fn new(variables: Variables, solver: Box<dyn Solver<Item = Vec<i32>>>) -> Self {
-        CrosswordSolutions { variables, solver }
+    fn new(
+        grid: Grid,
+        decoder: Box<dyn Decoder>,
+        solver: Box<dyn Solver<Item = Model>>,
+        encoding_stats: SolverStats,
+        next_variable: usize,
+    ) -> Self {
+        CrosswordSolutions {
+            grid,
+            decoder,
+            solver,
+            diversity: None,
+            next_variable,
+            interrupted: false,
+            error: None,
+            encoding_stats,
+            solving_time: Duration::ZERO,
+        }
+    }
+
+    /// Requires each next solution to differ from all the previous ones by at least the given [Diversity], i.e. by a
+    /// number of cells or slots. Without diversity, solutions may differ by as little as a single cell.
+    ///
+    /// After each solution, a cardinality constraint over the cell variables of this solution is added to the solver,
+    /// see [Solver::as_configurator].
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with these solutions, or a [SolverError] containing the error details, e.g. if the solver does not
+    /// support adding clauses between two solutions or if the [Encoding] has no cell variable.
+    pub fn with_diversity(mut self, diversity: Diversity) -> Result<Self, SolverError> {
+        if self.solver.as_configurator().is_none() {
+            return Err(SolverError::new(
+                "Solver does not support adding clauses between solutions",
+            ));
+        }
+        if !self.decoder.has_cell_literals() {
+            return Err(SolverError::new(
+                "Encoding does not support diversity: It has no cell variable",
+            ));
+        }
+        self.diversity = Some(diversity);
+        Ok(self)
+    }
+
+    /// Constrains the next solutions to differ enough from the given one, if a diversity is required.
+    fn diversify(&mut self, model: &Model) {
+        let Some(diversity) = self.diversity else {
+            return;
+        };
+        let decoder = &self.decoder;
+        let true_cell_literals =
+            diversity::true_cell_literals(&self.grid, model, |row, column, value| {
+                decoder.cell_literal(row, column, value).unwrap()
+            });
+        let configurator = self.solver.as_configurator().unwrap();
+        self.next_variable = diversity::add_clauses_to(
+            diversity,
+            &self.grid,
+            &true_cell_literals,
+            self.next_variable,
+            configurator,
+        );
+    }
+
+    /// Returns `true` iff the last search has been interrupted, i.e. if the last call to [next](Self::next) returned
+    /// [None] because a limit was reached rather than because there is no solution left, see
+    /// [Crossword::with_limits].
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted
+    }
+
+    /// Returns the error of the solver if the last search has failed, i.e. if the last call to [next](Self::next)
+    /// returned [None] because of this error.
+    pub fn error(&self) -> Option<&SolverError> {
+        self.error.as_ref()
+    }
+
+    /// Returns the statistics of the encoding and of the searches so far, e.g. after each call to
+    /// [next](Self::next). Times are measured here; Numbers of variables and clauses are those of the solver, or
+    /// those of the encoding if the solver is unable to tell; Other counters are given by the solver, see
+    /// [Solver::stats].
+    pub fn stats(&self) -> SolverStats {
+        let solver_stats = self.solver.stats();
+        let known_or_encoded =
+            |known: usize, encoded: usize| if known > 0 { known } else { encoded };
+        SolverStats {
+            variables: known_or_encoded(solver_stats.variables, self.encoding_stats.variables),
+            clauses: known_or_encoded(solver_stats.clauses, self.encoding_stats.clauses),
+            encoding_time: self.encoding_stats.encoding_time,
+            solving_time: self.solving_time,
+            ..solver_stats
+        }
+    }
+
+    /// Searches the next model, recording whether the search has been interrupted or has failed.
+    fn next_model(&mut self) -> Option<Model> {
+        let started = Instant::now();
+        let outcome = self.solver.solve_next();
+        self.solving_time += started.elapsed();
+        self.interrupted = outcome == SolveOutcome::Interrupted;
+        self.error = None;
+        match outcome {
+            SolveOutcome::Sat(model) => {
+                self.diversify(&model);
+                Some(model)
+            }
+            SolveOutcome::Failed(error) => {
+                self.error = Some(error);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Counts the remaining solutions, up to the given limit. Solutions are not decoded, which makes it cheaper than
+    /// counting the items of this iterator. Returns an error if a search is interrupted or fails, the count being then
+    /// partial.
+    pub fn count_solutions(mut self, limit: usize) -> Result<usize, SolverError> {
+        let mut count = 0;
+        while count < limit && self.next_model().is_some() {
+            count += 1;
+        }
+        if self.interrupted {
+            return Err(SolverError::new("Search interrupted"));
+        }
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        Ok(count)
+    }
+
+    /// Returns `true` iff there is exactly one remaining solution. At most two solutions are searched. Returns an error
+    /// if a search is interrupted or fails.
+    pub fn is_unique(self) -> Result<bool, SolverError> {
+        Ok(self.count_solutions(2)? == 1)
     }
 }
 
 impl Iterator for CrosswordSolutions {
     type Item = String;
     fn next(&mut self) -> Option<Self::Item> {
-        self.solver
-            .next()
-            .map(move |solution| self.variables.back_to_domain(&solution))
+        let model = self.next_model()?;
+        Some(self.decoder.back_to_domain(&model))
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use croissant_solver::{Lit, Var};
 
     struct StubSolverBuilder {}
     impl SolverConfigurator for StubSolverBuilder {
-        fn add_clause(&mut self, _literals: &[i32]) { /* Do nothing */
+        fn add_clause(&mut self, _literals: &[Lit]) { /* Do nothing */
         }
     }
     impl SolverBuilder for StubSolverBuilder {
-        fn build(&self) -> Box<dyn Solver<Item = Vec<i32>>> {
-            Box::new(StubSolver {})
+        fn build(&self) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+            Ok(Box::new(StubSolver {}))
         }
     }
 
     struct StubSolver {}
     impl Solver for StubSolver {}
     impl Iterator for StubSolver {
-        type Item = Vec<i32>;
+        type Item = Model;
         fn next(&mut self) -> Option<Self::Item> {
             None
         }
     }
     impl ConfigurableSolver for StubSolver {}
     impl SolverConfigurator for StubSolver {
-        fn add_clause(&mut self, _literals: &[i32]) { /* Do nothing. */
+        fn add_clause(&mut self, _literals: &[Lit]) { /* Do nothing. */
+        }
+    }
+
+    /// A native solver builder whose solver fills each cell with the first letter of the first word, once.
+    struct FirstLetterSolverBuilder {}
+    impl NativeSolverBuilder for FirstLetterSolverBuilder {
+        fn build(
+            self: Box<Self>,
+            grid: &Grid,
+            words: &[String],
+            decoder: &dyn Decoder,
+            _limits: SearchLimits,
+            _progress: Option<ProgressCallback>,
+        ) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+            let letter = words[0].as_bytes()[0] - b'A';
+            let cells = grid.row_count() * grid.column_count();
+            let mut model: Model = (1..=cells * 27)
+                .map(|variable| Var::new(variable).literal(false))
+                .collect();
+            for row in 0..grid.row_count() {
+                for column in 0..grid.column_count() {
+                    let literal = decoder.cell_literal(row, column, letter as usize).unwrap();
+                    model.set(literal);
+                }
+            }
+            Ok(Box::new(ModelsSolver(vec![model].into_iter())))
+        }
+    }
+
+    /// A solver returning the given models.
+    struct ModelsSolver(std::vec::IntoIter<Model>);
+    impl Solver for ModelsSolver {}
+    impl Iterator for ModelsSolver {
+        type Item = Model;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    struct InterruptedSolverBuilder {}
+    impl SolverConfigurator for InterruptedSolverBuilder {
+        fn add_clause(&mut self, _literals: &[Lit]) { /* Do nothing */
+        }
+    }
+    impl SolverBuilder for InterruptedSolverBuilder {
+        fn build(&self) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+            Ok(Box::new(InterruptedSolver {}))
+        }
+    }
+
+    struct FailingSolverBuilder {}
+    impl SolverConfigurator for FailingSolverBuilder {
+        fn add_clause(&mut self, _literals: &[Lit]) { /* Do nothing */
+        }
+    }
+    impl SolverBuilder for FailingSolverBuilder {
+        fn build(&self) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+            Err(SolverError::new("Cannot build solver"))
+        }
+    }
+
+    struct InterruptedSolver {}
+    impl Solver for InterruptedSolver {
+        fn solve_next(&mut self) -> SolveOutcome {
+            SolveOutcome::Interrupted
+        }
+    }
+    impl Iterator for InterruptedSolver {
+        type Item = Model;
+        fn next(&mut self) -> Option<Self::Item> {
+            None
         }
     }
 
@@ -190,10 +925,121 @@ mod test {
         let crossword = Crossword::try_from("...\n...", &words).unwrap();
         let stub_solver = Box::new(StubSolver {});
 
-        let mut solutions = crossword.solve_with(stub_solver);
+        let mut solutions = crossword.solve_with(stub_solver).unwrap();
         assert_eq!(None, solutions.next())
     }
 
+    #[test]
+    fn solve_natively_with() {
+        let words: Vec<String> = ["BB", "AA"].iter().map(|&word| word.to_string()).collect();
+        let crossword = Crossword::try_from("..\n..", &words).unwrap();
+
+        let mut solutions = crossword
+            .solve_natively_with(Box::new(FirstLetterSolverBuilder {}))
+            .unwrap();
+
+        assert_eq!(Some("BB\nBB".to_string()), solutions.next());
+        assert_eq!(None, solutions.next());
+        assert_eq!(2 * 2 * 27, solutions.stats().variables);
+    }
+
+    #[test]
+    fn count_solutions_none() {
+        let words: Vec<String> = ["ABC", "DEF", "AA", "BB", "CC"]
+            .iter()
+            .map(|&word| word.to_string())
+            .collect();
+        let crossword = Crossword::try_from("...\n...", &words).unwrap();
+        let stub_solver_builder = Box::new(StubSolverBuilder {});
+
+        let solutions = crossword
+            .solve_with_solver_built_by(stub_solver_builder)
+            .unwrap();
+        assert_eq!(Ok(0), solutions.count_solutions(10))
+    }
+
+    #[test]
+    fn interrupted() {
+        let words: Vec<String> = ["ABC", "DEF", "AA", "BB", "CC"]
+            .iter()
+            .map(|&word| word.to_string())
+            .collect();
+        let crossword = Crossword::try_from("...\n...", &words).unwrap();
+        let stub_solver_builder = Box::new(InterruptedSolverBuilder {});
+
+        let mut solutions = crossword
+            .solve_with_solver_built_by(stub_solver_builder)
+            .unwrap();
+        assert!(!solutions.is_interrupted(), "No search yet");
+        assert_eq!(None, solutions.next());
+        assert!(solutions.is_interrupted(), "Search should be interrupted");
+    }
+
+    #[test]
+    fn count_solutions_interrupted() {
+        let words: Vec<String> = ["ABC", "DEF", "AA", "BB", "CC"]
+            .iter()
+            .map(|&word| word.to_string())
+            .collect();
+        let crossword = Crossword::try_from("...\n...", &words).unwrap();
+        let stub_solver_builder = Box::new(InterruptedSolverBuilder {});
+
+        let solutions = crossword
+            .solve_with_solver_built_by(stub_solver_builder)
+            .unwrap();
+        assert_eq!(
+            Err(SolverError::new("Search interrupted")),
+            solutions.count_solutions(10)
+        );
+    }
+
+    #[test]
+    fn stats_from_encoding() {
+        let words: Vec<String> = ["ABC", "DEF", "AA", "BB", "CC"]
+            .iter()
+            .map(|&word| word.to_string())
+            .collect();
+        let crossword = Crossword::try_from("...\n...", &words).unwrap();
+        let stub_solver_builder = Box::new(StubSolverBuilder {});
+
+        let mut solutions = crossword
+            .solve_with_solver_built_by(stub_solver_builder)
+            .unwrap();
+        solutions.next();
+
+        let stats = solutions.stats();
+        // 6 cells × 27 values + 5 slots × 5 words
+        assert_eq!(6 * 27 + 5 * 5, stats.variables);
+        assert!(stats.clauses > 0, "Clauses should be counted");
+        assert_eq!(None, stats.conflicts);
+    }
+
+    #[test]
+    fn export_cnf() {
+        let words: Vec<String> = ["ABC", "DEF", "AA", "BB", "CC"]
+            .iter()
+            .map(|&word| word.to_string())
+            .collect();
+        let crossword = Crossword::try_from("...\n...", &words).unwrap();
+        let mut json = Vec::new();
+
+        let cnf = crossword
+            .export_cnf(std::io::Cursor::new(Vec::new()), &mut json)
+            .unwrap();
+
+        let cnf = String::from_utf8(cnf.into_inner()).unwrap();
+        let header = cnf.lines().next().unwrap().trim_end();
+        let clause_count = cnf.lines().filter(|line| line.ends_with(" 0")).count()
+            - 1 /* relevant variables */;
+        assert_eq!(format!("p cnf {} {clause_count}", 6 * 27 + 5 * 5), header);
+        let json = String::from_utf8(json).unwrap();
+        // 6 cells × 27 values + 2 across slots × 2 words + 3 down slots × 3 words
+        assert_eq!(6 * 27 + 2 * 2 + 3 * 3, json.matches("\"variable\"").count());
+        assert!(json.contains(
+            "{\"variable\": 163, \"slot\": {\"row\": 0, \"column\": 0, \"direction\": \"across\", \"length\": 3}, \"word\": \"ABC\"}"
+        ));
+    }
+
     #[test]
     fn solve_with_builder() {
         let words: Vec<String> = ["ABC", "DEF", "AA", "BB", "CC"]
@@ -203,7 +1049,46 @@ mod test {
         let crossword = Crossword::try_from("...\n...", &words).unwrap();
         let stub_solver_builder = Box::new(StubSolverBuilder {});
 
-        let mut solutions = crossword.solve_with_solver_built_by(stub_solver_builder);
+        let mut solutions = crossword
+            .solve_with_solver_built_by(stub_solver_builder)
+            .unwrap();
         assert_eq!(None, solutions.next())
     }
+
+    #[test]
+    fn solve_with_failing_builder() {
+        let words: Vec<String> = ["ABC", "DEF", "AA", "BB", "CC"]
+            .iter()
+            .map(|&word| word.to_string())
+            .collect();
+        let crossword = Crossword::try_from("...\n...", &words).unwrap();
+
+        let solutions = crossword.solve_with_solver_built_by(Box::new(FailingSolverBuilder {}));
+
+        assert_eq!(
+            Some(SolverError::new("Cannot build solver")),
+            solutions.err()
+        );
+    }
+
+    #[test]
+    fn solve_empty_grid_without_solver() {
+        let crossword = Crossword::try_from("", &[]).unwrap();
+
+        let solutions = crossword.solve_with_solver_built_by(Box::new(FailingSolverBuilder {}));
+
+        assert_eq!(vec![String::new()], solutions.unwrap().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn solve_without_words_without_solver() {
+        let crossword = Crossword::try_from("...\n...", &[]).unwrap();
+
+        let mut solutions = crossword
+            .solve_with_solver_built_by(Box::new(FailingSolverBuilder {}))
+            .unwrap();
+
+        assert_eq!(None, solutions.next());
+        assert!(!solutions.is_interrupted(), "Search should be complete");
+    }
 }
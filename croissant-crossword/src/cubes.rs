@@ -0,0 +1,442 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use croissant_solver::{
+    CancellationToken, Lit, SearchLimits, SolveOutcome, SolverError, SolverFactory,
+};
+
+use crate::alphabet;
+use crate::candidates;
+use crate::encoding::{Encoding, MddEncoding};
+use crate::grid::{self, Grid};
+use crate::slot::Slot;
+use crate::variables::Variables;
+use crate::viable;
+
+/// The interval at which the cancellation of the search by the caller is checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The default number of cubes per thread: Cubes are far from equally hard, having many more cubes than threads keeps
+/// all threads busy until the end.
+const CUBES_PER_THREAD: usize = 16;
+
+/// How to split a problem into cubes, i.e. into sub-problems assigning some cells.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Splitting {
+    /// On the candidate words of the most constrained slots, i.e. of the slots with the fewest candidate words.
+    #[default]
+    Words,
+    /// On the candidate letters of the crossing cells of highest degree, i.e. of the cells at the crossing of the
+    /// longest slots.
+    Letters,
+}
+
+/// The options of cube-and-conquer solving, see
+/// [solve_by_cubes_with](crate::crossword::Crossword::solve_by_cubes_with).
+#[derive(Clone, Debug)]
+pub struct CubeAndConquer {
+    /// How to split the problem.
+    splitting: Splitting,
+    /// The minimal number of cubes to split the problem into, if the problem is big enough.
+    cube_count: usize,
+    /// The number of threads solving the cubes.
+    thread_count: usize,
+}
+
+impl Default for CubeAndConquer {
+    /// Splits on words, using all the cores.
+    fn default() -> Self {
+        let thread_count = thread::available_parallelism().map_or(1, |count| count.get());
+        CubeAndConquer {
+            splitting: Splitting::default(),
+            cube_count: CUBES_PER_THREAD * thread_count,
+            thread_count,
+        }
+    }
+}
+
+impl CubeAndConquer {
+    /// Sets how to split the problem. Default is [Splitting::Words].
+    pub fn with_splitting(mut self, splitting: Splitting) -> Self {
+        self.splitting = splitting;
+        self
+    }
+
+    /// Sets the minimal number of cubes to split the problem into. Problem is split further until there are at least as
+    /// many cubes or until there is nothing left to split. Default is 16 cubes per thread.
+    pub fn with_cube_count(mut self, cube_count: usize) -> Self {
+        self.cube_count = cube_count.max(1);
+        self
+    }
+
+    /// Sets the number of threads solving the cubes. Default is the number of cores. The number of cubes is adjusted
+    /// to the new number of threads.
+    pub fn with_thread_count(mut self, thread_count: usize) -> Self {
+        self.thread_count = thread_count.max(1);
+        self.cube_count = CUBES_PER_THREAD * self.thread_count;
+        self
+    }
+
+    /// Returns how to split the problem.
+    pub fn splitting(&self) -> Splitting {
+        self.splitting
+    }
+
+    /// Returns the minimal number of cubes to split the problem into.
+    pub fn cube_count(&self) -> usize {
+        self.cube_count
+    }
+
+    /// Returns the number of threads solving the cubes.
+    pub fn thread_count(&self) -> usize {
+        self.thread_count
+    }
+}
+
+/// A partial fill of the grid: The values of the cells, row by row, [grid::EMPTY] for the unassigned ones.
+type Fill = Vec<char>;
+
+/// Splits the problem defined by the given grid and word list into cubes, as specified by the given options. Cubes are
+/// disjoint and cover all the fills of the grid.
+///
+/// Returns the cubes as cell literals, see [Variables::representing_cell]. No cube means no solution; A single empty
+/// cube means nothing to split.
+pub fn split(grid: &Grid, words: &[String], options: &CubeAndConquer) -> Vec<Vec<Lit>> {
+    let fills = match options.splitting {
+        Splitting::Words => split_on_words(grid, words, options.cube_count),
+        Splitting::Letters => split_on_letters(grid, words, options.cube_count),
+    };
+    let variables = Variables::new(grid.clone(), 0);
+    let column_count = grid.column_count();
+    fills
+        .iter()
+        .map(|fill| {
+            fill.iter()
+                .enumerate()
+                .filter(|&(cell, &value)| {
+                    value != grid::EMPTY
+                        && grid.letter_at(cell / column_count, cell % column_count) == grid::EMPTY
+                })
+                .map(|(cell, &value)| {
+                    let letter_index = alphabet::index_of(value).unwrap();
+                    Lit::from(variables.representing_cell(
+                        cell / column_count,
+                        cell % column_count,
+                        letter_index,
+                    ))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the initial fill, i.e. the prefilled cells of the given grid.
+fn initial_fill(grid: &Grid) -> Fill {
+    (0..grid.row_count())
+        .flat_map(|row| (0..grid.column_count()).map(move |column| grid.letter_at(row, column)))
+        .collect()
+}
+
+/// Returns the indices of the cells of the given slot in a [Fill].
+fn cells_of(slot: &Slot, column_count: usize) -> Vec<usize> {
+    slot.positions()
+        .iter()
+        .map(|pos| pos.row() * column_count + pos.column())
+        .collect()
+}
+
+/// Splits on the candidate words of the slots with the fewest candidates, one slot after the other.
+fn split_on_words(grid: &Grid, words: &[String], cube_count: usize) -> Vec<Fill> {
+    let column_count = grid.column_count();
+    let mut slots: Vec<(Vec<usize>, Vec<usize>)> = grid
+        .slots()
+        .iter()
+        .filter(|slot| {
+            slot.positions()
+                .iter()
+                .any(|pos| grid.letter_at(pos.row(), pos.column()) == grid::EMPTY)
+        })
+        .map(|slot| {
+            let candidates = viable::ranked_candidates(grid, slot, words, &[]);
+            (cells_of(slot, column_count), candidates)
+        })
+        .collect();
+    slots.sort_by_key(|(_, candidates)| candidates.len());
+
+    let mut fills = vec![initial_fill(grid)];
+    for (cells, candidates) in slots {
+        if fills.len() >= cube_count || fills.is_empty() {
+            break;
+        }
+        fills = fills
+            .iter()
+            .flat_map(|fill| {
+                candidates
+                    .iter()
+                    .map(|&word_index| words[word_index].as_bytes())
+                    .filter(|word| {
+                        cells.iter().zip(word.iter()).all(|(&cell, &letter)| {
+                            fill[cell] == grid::EMPTY || fill[cell] == letter as char
+                        })
+                    })
+                    .map(|word| {
+                        let mut fill = fill.clone();
+                        for (&cell, &letter) in cells.iter().zip(word.iter()) {
+                            fill[cell] = letter as char;
+                        }
+                        fill
+                    })
+            })
+            .collect();
+    }
+    fills
+}
+
+/// Splits on the candidate letters of the crossing cells, by descending degree: The degree of a cell is the total
+/// length of its slots.
+fn split_on_letters(grid: &Grid, words: &[String], cube_count: usize) -> Vec<Fill> {
+    let column_count = grid.column_count();
+    let mut degrees = vec![(0, 0); grid.row_count() * column_count];
+    for slot in grid.slots() {
+        for cell in cells_of(&slot, column_count) {
+            let (slot_count, length) = degrees[cell];
+            degrees[cell] = (slot_count + 1, length + slot.len());
+        }
+    }
+    let mut cells: Vec<usize> = (0..degrees.len())
+        .filter(|&cell| {
+            let (slot_count, _) = degrees[cell];
+            slot_count > 1
+                && grid.letter_at(cell / column_count, cell % column_count) == grid::EMPTY
+        })
+        .collect();
+    cells.sort_by_key(|&cell| std::cmp::Reverse(degrees[cell].1));
+
+    let candidates = candidates::approximate(grid, words);
+    let mut fills = vec![initial_fill(grid)];
+    for cell in cells {
+        if fills.len() >= cube_count || fills.is_empty() {
+            break;
+        }
+        let letters = candidates.at(cell / column_count, cell % column_count);
+        fills = fills
+            .iter()
+            .flat_map(|fill| {
+                letters.iter().map(|&letter| {
+                    let mut fill = fill.clone();
+                    fill[cell] = letter;
+                    fill
+                })
+            })
+            .collect();
+    }
+    fills
+}
+
+/// Solves the given cubes of the problem defined by the given grid and word list concurrently, on the given number of
+/// threads, with the solvers created by the given factory. Each thread creates its own solver, then solves the cubes
+/// one after the other under assumptions, until there is no cube left. Slots are encoded with decision diagrams, see
+/// [MddEncoding].
+///
+/// Search stops as soon as `limit` cubes are found satisfiable, or when all the cubes are solved.
+///
+/// Returns one solution per satisfiable cube, by cube order, at most `limit` of them, or an error if the search is
+/// interrupted or if the solver does not support solving under assumptions.
+pub fn conquer(
+    grid: &Grid,
+    words: &[String],
+    cubes: &[Vec<Lit>],
+    factory: &SolverFactory,
+    thread_count: usize,
+    limits: &SearchLimits,
+    limit: usize,
+) -> Result<Vec<String>, SolverError> {
+    let next_cube = AtomicUsize::new(0);
+    let stop = CancellationToken::new();
+    let solutions: Mutex<Vec<(usize, String)>> = Mutex::new(Vec::new());
+    let error: Mutex<Option<SolverError>> = Mutex::new(None);
+    let fail = |failure: SolverError| {
+        error.lock().unwrap().get_or_insert(failure);
+        stop.cancel();
+    };
+    let thread_count = thread_count.clamp(1, cubes.len().max(1));
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..thread_count)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut decoder = None;
+                    let solver = factory.create(|configurator| {
+                        configurator.set_limits(SearchLimits {
+                            cancellation: Some(stop.clone()),
+                            ..limits.clone()
+                        });
+                        decoder = Some(MddEncoding.encode(grid, words, configurator));
+                    });
+                    let mut solver = match solver {
+                        Ok(solver) => solver,
+                        Err(error) => return fail(error),
+                    };
+                    let decoder = decoder.expect("Factory should have configured the solver");
+                    while !stop.is_cancelled() {
+                        let cube_index = next_cube.fetch_add(1, Ordering::Relaxed);
+                        let Some(cube) = cubes.get(cube_index) else {
+                            break;
+                        };
+                        match solver.solve_with_assumptions(cube) {
+                            SolveOutcome::Sat(model) => {
+                                let solution = decoder.back_to_domain(&model);
+                                let mut solutions = solutions.lock().unwrap();
+                                solutions.push((cube_index, solution));
+                                if solutions.len() >= limit {
+                                    stop.cancel();
+                                }
+                            }
+                            SolveOutcome::Unsat => {}
+                            SolveOutcome::Interrupted => {
+                                // Not an error if the other threads already found enough solutions
+                                if solutions.lock().unwrap().len() < limit {
+                                    fail(SolverError::new("Search interrupted"));
+                                }
+                            }
+                            SolveOutcome::Unsupported => fail(SolverError::new(
+                                "Solver does not support solving under assumptions",
+                            )),
+                            SolveOutcome::Failed(error) => fail(error),
+                        }
+                    }
+                })
+            })
+            .collect();
+        let cancellation = limits.cancellation.as_ref();
+        while !workers.iter().all(|worker| worker.is_finished()) {
+            if cancellation.is_some_and(CancellationToken::is_cancelled) {
+                fail(SolverError::new("Search interrupted"));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+    let mut solutions = solutions.into_inner().unwrap();
+    if let Some(error) = error.into_inner().unwrap() {
+        if solutions.len() < limit {
+            return Err(error);
+        }
+    }
+    solutions.sort_by_key(|&(cube_index, _)| cube_index);
+    Ok(solutions
+        .into_iter()
+        .take(limit)
+        .map(|(_, solution)| solution)
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn split_on_words_most_constrained_slot_first() {
+        let grid = Grid::try_from("...\n.##").unwrap();
+        let words = words(&["ABC", "ABD", "AB"]);
+        let options = CubeAndConquer::default().with_cube_count(2);
+
+        let cubes = split(&grid, &words, &options);
+
+        // 1-Down has a single candidate, then 1-Across has two
+        let variables = Variables::new(grid.clone(), 0);
+        let cell = |row, column, letter| {
+            let letter_index = alphabet::index_of(letter).unwrap();
+            Lit::from(variables.representing_cell(row, column, letter_index))
+        };
+        let first_cube = vec![
+            cell(0, 0, 'A'),
+            cell(0, 1, 'B'),
+            cell(0, 2, 'C'),
+            cell(1, 0, 'B'),
+        ];
+        let second_cube = vec![
+            cell(0, 0, 'A'),
+            cell(0, 1, 'B'),
+            cell(0, 2, 'D'),
+            cell(1, 0, 'B'),
+        ];
+        assert_eq!(vec![first_cube, second_cube], cubes);
+    }
+
+    #[test]
+    fn split_on_words_until_cube_count() {
+        let grid = Grid::try_from("..\n..").unwrap();
+        let words = words(&["AB", "BA", "AA", "BB"]);
+        let options = CubeAndConquer::default().with_cube_count(5);
+
+        let cubes = split(&grid, &words, &options);
+
+        // First slot gives 4 cubes, second one gives 4 times 4 cubes, all consistent
+        assert_eq!(16, cubes.len());
+        assert!(cubes.iter().all(|cube| cube.len() == 4));
+    }
+
+    #[test]
+    fn split_on_words_inconsistent() {
+        let grid = Grid::try_from("..\n..").unwrap();
+        let words = words(&["AB", "CD"]);
+        let options = CubeAndConquer::default().with_cube_count(100);
+
+        let cubes = split(&grid, &words, &options);
+
+        // Rows and columns cannot be both "AB" or "CD"
+        assert!(cubes.is_empty());
+    }
+
+    #[test]
+    fn split_on_words_nothing_to_split() {
+        let grid = Grid::try_from("AB").unwrap();
+        let words = words(&["AB"]);
+        let options = CubeAndConquer::default();
+
+        let cubes = split(&grid, &words, &options);
+
+        assert_eq!(vec![Vec::<Lit>::new()], cubes);
+    }
+
+    #[test]
+    fn split_on_letters_highest_degree_first() {
+        let grid = Grid::try_from("...\n.#.\n...").unwrap();
+        let words = words(&["ABC", "CBA"]);
+        let options = CubeAndConquer::default()
+            .with_splitting(Splitting::Letters)
+            .with_cube_count(2);
+
+        let cubes = split(&grid, &words, &options);
+
+        // Corners are the only crossing cells, all of the same degree: The first one is split on 'A' and 'C'
+        let variables = Variables::new(grid.clone(), 0);
+        let a = Lit::from(variables.representing_cell(0, 0, 0));
+        let c = Lit::from(variables.representing_cell(0, 0, 2));
+        assert_eq!(vec![vec![a], vec![c]], cubes);
+    }
+
+    #[test]
+    fn split_on_letters_no_candidate() {
+        let grid = Grid::try_from("..\n..").unwrap();
+        let words = words(&["ABC"]);
+        let options = CubeAndConquer::default().with_splitting(Splitting::Letters);
+
+        let cubes = split(&grid, &words, &options);
+
+        assert!(cubes.is_empty());
+    }
+
+    #[test]
+    fn thread_count_adjusts_cube_count() {
+        let options = CubeAndConquer::default().with_thread_count(3);
+
+        assert_eq!(3, options.thread_count());
+        assert_eq!(48, options.cube_count());
+    }
+}
@@ -0,0 +1,174 @@
+use croissant_solver::{Lit, Model, SolverConfigurator, Var};
+
+use crate::grid::{self, Grid};
+use crate::variables::CELL_VALUE_COUNT;
+
+/// How much a solution must differ from all the previous ones, see
+/// [CrosswordSolutions::with_diversity](crate::crossword::CrosswordSolutions::with_diversity).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Diversity {
+    /// The minimal number of initially empty cells containing a different letter
+    Cells(usize),
+    /// The minimal number of slots containing a different word
+    Slots(usize),
+}
+
+/// Returns the literals which are true in the given model and stand for the values of the cells, by cell, row after
+/// row. Uses the given function to get the literal of a (cell,value) pair.
+pub fn true_cell_literals(
+    grid: &Grid,
+    model: &Model,
+    cell_literal: impl Fn(usize, usize, usize) -> Lit,
+) -> Vec<Lit> {
+    (0..grid.row_count())
+        .flat_map(|row| (0..grid.column_count()).map(move |column| (row, column)))
+        .map(|(row, column)| {
+            (0..CELL_VALUE_COUNT)
+                .map(|value| cell_literal(row, column, value))
+                .find(|&literal| model.satisfies(literal))
+                .expect("Cell has no value in model")
+        })
+        .collect()
+}
+
+/// Adds the constraints forcing the next solutions to differ from the solution given by its true cell literals, as
+/// returned by [true_cell_literals]: An *at-least-k* constraint over the differences, see
+/// [SolverConfigurator::add_at_least_k]. The differences of the slots are auxiliary variables, numbered from the given
+/// free variable, or from the [variables count](SolverConfigurator::variables_count) of the configurator if bigger.
+///
+/// Returns the first free variable after the auxiliary variables used, including the ones of the configurator.
+pub fn add_clauses_to(
+    diversity: Diversity,
+    grid: &Grid,
+    true_cell_literals: &[Lit],
+    next_variable: usize,
+    configurator: &mut dyn SolverConfigurator,
+) -> usize {
+    let column_count = grid.column_count();
+    let next_variable = free_variable(configurator, next_variable);
+    match diversity {
+        Diversity::Cells(k) => {
+            let differences: Vec<Lit> = true_cell_literals
+                .iter()
+                .enumerate()
+                .filter(|&(cell, _)| {
+                    grid.letter_at(cell / column_count, cell % column_count) == grid::EMPTY
+                })
+                .map(|(_, &literal)| -literal)
+                .collect();
+            configurator.add_at_least_k(&differences, k);
+            free_variable(configurator, next_variable)
+        }
+        Diversity::Slots(k) => {
+            let slots = grid.slots();
+            let mut differences = Vec::with_capacity(slots.len());
+            let mut next_variable = next_variable;
+            for slot in slots {
+                // difference ⇒ at least one cell of the slot differs
+                let difference = Lit::from(Var::new(next_variable));
+                next_variable += 1;
+                let mut clause: Vec<Lit> = slot
+                    .positions()
+                    .iter()
+                    .map(|pos| -true_cell_literals[pos.row() * column_count + pos.column()])
+                    .collect();
+                clause.push(-difference);
+                configurator.add_clause(&clause);
+                differences.push(difference);
+            }
+            configurator.allocate_variables(next_variable - 1);
+            configurator.add_at_least_k(&differences, k);
+            free_variable(configurator, next_variable)
+        }
+    }
+}
+
+/// Returns the first free variable of the given configurator, the variables before the given one being used.
+fn free_variable(configurator: &dyn SolverConfigurator, next_variable: usize) -> usize {
+    configurator
+        .variables_count()
+        .map_or(next_variable, |variables_count| {
+            next_variable.max(variables_count + 1)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct TestSolverConfigurator {
+        variables_count: Option<usize>,
+        clauses: Vec<Vec<i32>>,
+        at_least_k: Vec<(Vec<i32>, usize)>,
+    }
+
+    impl SolverConfigurator for TestSolverConfigurator {
+        fn allocate_variables(&mut self, variables_count: usize) {
+            self.variables_count = self.variables_count.max(Some(variables_count));
+        }
+        fn variables_count(&self) -> Option<usize> {
+            self.variables_count
+        }
+        fn add_clause(&mut self, literals: &[Lit]) {
+            self.clauses.push(raw(literals));
+        }
+        fn add_at_least_k(&mut self, literals: &[Lit], k: usize) {
+            self.at_least_k.push((raw(literals), k));
+            // Uses one auxiliary variable
+            self.allocate_variables(self.variables_count.unwrap() + 1);
+        }
+    }
+
+    /// Returns the raw representations of the given literals.
+    fn raw(literals: &[Lit]) -> Vec<i32> {
+        literals.iter().map(|&literal| i32::from(literal)).collect()
+    }
+
+    /// Returns the positive literals of the variables 1 to 4.
+    fn true_cell_literals() -> Vec<Lit> {
+        (1..=4).map(|index| Var::new(index).into()).collect()
+    }
+
+    #[test]
+    fn cells() {
+        let grid = Grid::try_from("A.\n#.").unwrap();
+        let mut configurator = TestSolverConfigurator {
+            variables_count: Some(9),
+            ..TestSolverConfigurator::default()
+        };
+
+        let next_variable = add_clauses_to(
+            Diversity::Cells(1),
+            &grid,
+            &true_cell_literals(),
+            10,
+            &mut configurator,
+        );
+
+        assert!(configurator.clauses.is_empty(), "No clause expected");
+        assert_eq!(vec![(vec![-2, -4], 1)], configurator.at_least_k);
+        assert_eq!(11, next_variable);
+    }
+
+    #[test]
+    fn slots() {
+        let grid = Grid::try_from("..\n#.").unwrap();
+        let mut configurator = TestSolverConfigurator::default();
+
+        let next_variable = add_clauses_to(
+            Diversity::Slots(2),
+            &grid,
+            &true_cell_literals(),
+            10,
+            &mut configurator,
+        );
+
+        assert_eq!(
+            vec![vec![-1, -2, -10], vec![-2, -4, -11]],
+            configurator.clauses
+        );
+        assert_eq!(vec![(vec![10, 11], 2)], configurator.at_least_k);
+        assert_eq!(13, next_variable);
+    }
+}
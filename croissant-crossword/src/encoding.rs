@@ -0,0 +1,373 @@
+//! # Encodings of a crossword problem into a boolean satisfiability problem
+//!
+//! There is more than one way to express a crossword problem with boolean variables and clauses. They all lead to
+//! the same solutions but differ in size and in the time needed by the solvers to find these solutions. The
+//! [Encoding] trait allows choosing one at runtime, see [Crossword::with_encoding].
+//!
+//! Available encodings are:
+//!
+//! - [DirectEncoding]: The default. One variable per (cell,letter) pair and one variable per (slot,word) pair. See
+//!   [crossword](crate::crossword) for details.
+//! - [MddEncoding]: One variable per (cell,letter) pair and, for each slot, a multi-valued decision diagram of the
+//!   words fitting the slot. Much more compact than [DirectEncoding] on big word lists.
+//! - [WordLevelEncoding]: One variable per (slot,word) pair and no cell variable at all. Crossing slots are linked by
+//!   compatibility clauses. This is how a human constructor reasons about a fill, and it may be much faster on sparse
+//!   grids.
+//!
+//! [Crossword::with_encoding]: crate::crossword::Crossword::with_encoding
+
+use croissant_solver::{Lit, Model, SolverConfigurator, Var};
+
+use crate::alphabet;
+use crate::constraints::Constraints;
+use crate::grid::{self, Grid};
+use crate::pos::Pos;
+use crate::slot::Slot;
+use crate::variables::{Variables, BLOCK_INDEX, CELL_VALUE_COUNT};
+use crate::word_level::{WordConstraints, WordVariables};
+
+/// Definition of an encoding, i.e. a way to translate a crossword problem into clauses.
+pub trait Encoding {
+    /// Adds the clauses describing the crossword problem defined by the given grid and word list to the given solver
+    /// configurator.
+    ///
+    /// Implementations are also responsible for giving the variables hints - see
+    /// [SolverConfigurator::allocate_variables] and [SolverConfigurator::set_relevant_variables] - to the solver
+    /// configurator.
+    ///
+    /// Returns the [Decoder] able to translate the models found by the solver back to crossword grids.
+    fn encode(
+        &self,
+        grid: &Grid,
+        words: &[String],
+        solver_configurator: &mut dyn SolverConfigurator,
+    ) -> Box<dyn Decoder>;
+
+    /// Returns what the variables of the encoding of the problem defined by the given grid and word list stand for,
+    /// by ascending variable, e.g. to interpret the problem once exported. Auxiliary variables are omitted.
+    ///
+    /// Default implementation returns nothing.
+    fn meanings(&self, _grid: &Grid, _words: &[String]) -> Vec<(Var, Meaning)> {
+        Vec::new()
+    }
+}
+
+/// What a variable of an encoding stands for, see [Encoding::meanings].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Meaning {
+    /// The cell at the given position contains the given value, i.e. a letter or a [block](grid::BLOCK).
+    Cell(Pos, char),
+    /// The given slot contains the given word.
+    Slot(Slot, String),
+}
+
+/// Definition of a decoder, i.e. the translator of solver models back to crossword grids.
+pub trait Decoder {
+    /// Translates a model back to a crossword grid.
+    fn back_to_domain(&self, model: &Model) -> String;
+
+    /// Returns the literal which is true iff the cell at the given position contains the given value, i.e. the index
+    /// of a letter in the alphabet or the index following the last letter for a block. Returns [None] if the encoding
+    /// has no such literal.
+    ///
+    /// Default implementation returns [None].
+    fn cell_literal(&self, _row: usize, _column: usize, _value: usize) -> Option<Lit> {
+        None
+    }
+
+    /// Returns `true` iff the encoding has a literal for each value of each cell, see [cell_literal](Self::cell_literal).
+    ///
+    /// Default implementation returns `false`.
+    fn has_cell_literals(&self) -> bool {
+        false
+    }
+}
+
+/// The direct encoding: One variable per (cell,letter) pair and one variable per (slot,word) pair. Each slot variable
+/// is equivalent to the conjunction of the cell variables spelling the word in the slot.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DirectEncoding;
+
+impl Encoding for DirectEncoding {
+    fn encode(
+        &self,
+        grid: &Grid,
+        words: &[String],
+        solver_configurator: &mut dyn SolverConfigurator,
+    ) -> Box<dyn Decoder> {
+        let variables = Variables::new(grid.clone(), words.len());
+        let constraints = Constraints::new(grid.clone(), variables.clone(), words);
+        solver_configurator.allocate_variables(variables.count());
+        solver_configurator.set_relevant_variables(variables.representing_cells());
+        constraints.add_one_letter_or_block_per_cell_clauses_to(solver_configurator);
+        constraints.add_one_word_per_slot_clauses_to(solver_configurator);
+        constraints.add_input_grid_constraints_are_satisfied_clauses_to(solver_configurator);
+        Box::new(variables)
+    }
+
+    fn meanings(&self, grid: &Grid, words: &[String]) -> Vec<(Var, Meaning)> {
+        let variables = Variables::new(grid.clone(), words.len());
+        let mut meanings = cell_meanings(grid, &variables);
+        for (slot_index, slot) in grid.slots().into_iter().enumerate() {
+            for (word_index, word) in words.iter().enumerate() {
+                if word.len() == slot.len() {
+                    let variable = variables.representing_slot(slot_index, word_index);
+                    meanings.push((variable, Meaning::Slot(slot.clone(), word.clone())));
+                }
+            }
+        }
+        meanings
+    }
+}
+
+/// The encoding based on multi-valued decision diagrams (MDD): One variable per (cell,letter) pair and, instead of
+/// slot variables, for each slot the states of a minimized prefix tree of the words fitting the slot.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MddEncoding;
+
+impl Encoding for MddEncoding {
+    fn encode(
+        &self,
+        grid: &Grid,
+        words: &[String],
+        solver_configurator: &mut dyn SolverConfigurator,
+    ) -> Box<dyn Decoder> {
+        let variables = Variables::new(grid.clone(), words.len());
+        let constraints = Constraints::new(grid.clone(), variables.clone(), words);
+        let mdds = constraints.mdds();
+        solver_configurator.allocate_variables(
+            variables.representing_cell_count() + constraints.mdd_variables_count(&mdds),
+        );
+        solver_configurator.set_relevant_variables(variables.representing_cells());
+        constraints.add_one_letter_or_block_per_cell_clauses_to(solver_configurator);
+        constraints.add_one_word_per_slot_clauses_using_mdds_to(&mdds, solver_configurator);
+        constraints.add_input_grid_constraints_are_satisfied_clauses_to(solver_configurator);
+        Box::new(variables)
+    }
+
+    /// Returns the meanings of the cell variables only: The states of the decision diagrams are auxiliary variables.
+    fn meanings(&self, grid: &Grid, words: &[String]) -> Vec<(Var, Meaning)> {
+        let variables = Variables::new(grid.clone(), words.len());
+        cell_meanings(grid, &variables)
+    }
+}
+
+/// The word-level encoding: Only one variable per (slot,word) pair, for the words fitting the slot and compatible with
+/// its prefilled letters. For each crossing between two slots, the word in the first slot implies that the word in the
+/// second slot has the same letter at the crossing.
+///
+/// Cells are reconstructed from the chosen words. Cells which belong to no slot are left as is.
+///
+/// Note that the size of the problem mainly depends on the *at-most-one* constraints over the candidates of each slot,
+/// see [SolverConfigurator::add_at_most_one].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WordLevelEncoding;
+
+impl Encoding for WordLevelEncoding {
+    fn encode(
+        &self,
+        grid: &Grid,
+        words: &[String],
+        solver_configurator: &mut dyn SolverConfigurator,
+    ) -> Box<dyn Decoder> {
+        let variables = WordVariables::new(grid.clone(), words);
+        let constraints = WordConstraints::new(&variables);
+        solver_configurator.allocate_variables(variables.count());
+        solver_configurator.set_relevant_variables((1..=variables.count()).map(Var::new).collect());
+        constraints.add_one_word_per_slot_clauses_to(solver_configurator);
+        constraints.add_crossing_clauses_to(solver_configurator);
+        Box::new(variables)
+    }
+
+    fn meanings(&self, grid: &Grid, words: &[String]) -> Vec<(Var, Meaning)> {
+        let variables = WordVariables::new(grid.clone(), words);
+        let mut meanings = Vec::with_capacity(variables.count());
+        for (slot_index, slot) in variables.slots().iter().enumerate() {
+            for (candidate_index, word) in variables.candidates(slot_index).iter().enumerate() {
+                let variable = variables.representing_slot(slot_index, candidate_index);
+                meanings.push((variable, Meaning::Slot(slot.clone(), word.clone())));
+            }
+        }
+        meanings
+    }
+}
+
+/// Returns the meanings of the cell variables of the given grid.
+fn cell_meanings(grid: &Grid, variables: &Variables) -> Vec<(Var, Meaning)> {
+    let mut meanings = Vec::with_capacity(variables.representing_cell_count());
+    for row in 0..grid.row_count() {
+        for column in 0..grid.column_count() {
+            for value in 0..CELL_VALUE_COUNT {
+                let variable = variables.representing_cell(row, column, value);
+                let value = match value {
+                    BLOCK_INDEX => grid::BLOCK,
+                    letter_index => alphabet::letter_at(letter_index),
+                };
+                meanings.push((variable, Meaning::Cell(Pos::new(column, row), value)));
+            }
+        }
+    }
+    meanings
+}
+
+impl Decoder for Variables {
+    fn back_to_domain(&self, model: &Model) -> String {
+        Variables::back_to_domain(self, model)
+    }
+
+    fn cell_literal(&self, row: usize, column: usize, value: usize) -> Option<Lit> {
+        Some(self.representing_cell(row, column, value).into())
+    }
+    fn has_cell_literals(&self) -> bool {
+        true
+    }
+}
+
+impl Decoder for WordVariables {
+    fn back_to_domain(&self, model: &Model) -> String {
+        WordVariables::back_to_domain(self, model)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct TestSolverConfigurator {
+        variables_count: usize,
+        relevant_variables: Vec<Var>,
+        clauses_count: usize,
+    }
+
+    impl SolverConfigurator for TestSolverConfigurator {
+        fn allocate_variables(&mut self, variables_count: usize) {
+            self.variables_count = variables_count;
+        }
+
+        fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
+            self.relevant_variables = relevant_variables;
+        }
+
+        fn add_clause(&mut self, _literals: &[Lit]) {
+            self.clauses_count += 1;
+        }
+    }
+
+    fn words() -> Vec<String> {
+        ["AB", "BA", "ABC"]
+            .iter()
+            .map(|&word| word.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn direct_encoding() {
+        let mut test_solver = TestSolverConfigurator::default();
+        let grid = Grid::try_from("..").unwrap();
+
+        DirectEncoding.encode(&grid, &words(), &mut test_solver);
+
+        assert_eq!(57, test_solver.variables_count); // 2 cells * 27 values + 1 slot * 3 words
+        assert_eq!(
+            (1..=54).map(Var::new).collect::<Vec<_>>(),
+            test_solver.relevant_variables
+        );
+        assert_eq!(
+            2 * (1 + 351) // exactly one value per cell
+            + 2 * 3 + 1 + 1 // one word among two for the slot
+            + 2, // input grid
+            test_solver.clauses_count
+        );
+    }
+
+    #[test]
+    fn mdd_encoding() {
+        let mut test_solver = TestSolverConfigurator::default();
+        let grid = Grid::try_from("..").unwrap();
+
+        MddEncoding.encode(&grid, &words(), &mut test_solver);
+
+        assert_eq!(57, test_solver.variables_count); // 2 cells * 27 values + 3 MDD states
+        assert_eq!(
+            (1..=54).map(Var::new).collect::<Vec<_>>(),
+            test_solver.relevant_variables
+        );
+        assert_eq!(
+            2 * (1 + 351) // exactly one value per cell
+            + 6 // one word among two for the slot, see constraints tests
+            + 2, // input grid
+            test_solver.clauses_count
+        );
+    }
+
+    #[test]
+    fn direct_encoding_meanings() {
+        let grid = Grid::try_from("..").unwrap();
+
+        let meanings = DirectEncoding.meanings(&grid, &words());
+
+        assert_eq!(56, meanings.len()); // 2 cells * 27 values + 1 slot * 2 words
+        assert_eq!(
+            (Var::new(1), Meaning::Cell(Pos::new(0, 0), 'A')),
+            meanings[0]
+        );
+        assert_eq!(
+            (Var::new(54), Meaning::Cell(Pos::new(1, 0), '#')),
+            meanings[53]
+        );
+        let slot = Slot::across(0, 2, 0);
+        assert_eq!(
+            (Var::new(55), Meaning::Slot(slot.clone(), "AB".to_string())),
+            meanings[54]
+        );
+        assert_eq!(
+            (Var::new(56), Meaning::Slot(slot, "BA".to_string())),
+            meanings[55]
+        );
+    }
+
+    #[test]
+    fn mdd_encoding_meanings() {
+        let grid = Grid::try_from("..").unwrap();
+
+        let meanings = MddEncoding.meanings(&grid, &words());
+
+        assert_eq!(54, meanings.len()); // 2 cells * 27 values, no MDD state
+        assert_eq!(
+            (Var::new(28), Meaning::Cell(Pos::new(1, 0), 'A')),
+            meanings[27]
+        );
+    }
+
+    #[test]
+    fn word_level_encoding_meanings() {
+        let grid = Grid::try_from("..").unwrap();
+
+        let meanings = WordLevelEncoding.meanings(&grid, &words());
+
+        let slot = Slot::across(0, 2, 0);
+        assert_eq!(
+            vec![
+                (Var::new(1), Meaning::Slot(slot.clone(), "AB".to_string())),
+                (Var::new(2), Meaning::Slot(slot, "BA".to_string()))
+            ],
+            meanings
+        );
+    }
+
+    #[test]
+    fn word_level_encoding() {
+        let mut test_solver = TestSolverConfigurator::default();
+        let grid = Grid::try_from("..").unwrap();
+
+        WordLevelEncoding.encode(&grid, &words(), &mut test_solver);
+
+        assert_eq!(2, test_solver.variables_count); // 1 slot * 2 candidates
+        assert_eq!(
+            vec![Var::new(1), Var::new(2)],
+            test_solver.relevant_variables
+        );
+        assert_eq!(2, test_solver.clauses_count); // one word among two for the slot, no crossing
+    }
+}
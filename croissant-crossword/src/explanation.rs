@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use croissant_solver::{Lit, Model, SolveOutcome, Solver, SolverConfigurator, SolverError, Var};
+
+use crate::alphabet;
+use crate::constraints::Constraints;
+use crate::grid::{self, Grid};
+use crate::mdd::Mdd;
+use crate::pos::Pos;
+use crate::slot::Slot;
+use crate::variables::{Variables, BLOCK_INDEX};
+
+/// The explanation of why a grid cannot be filled: A minimal set of prefilled cells and slots which cannot be
+/// satisfied together. Removing any of them from the set makes the remaining ones satisfiable.
+#[derive(Debug, PartialEq)]
+pub struct Explanation {
+    /// The conflicting prefilled cells
+    cells: Vec<Pos>,
+    /// The conflicting slots
+    slots: Vec<Slot>,
+    /// The names of the conflicting slots, e.g. "5-Down"
+    slot_names: Vec<String>,
+}
+
+impl Explanation {
+    /// Returns the conflicting prefilled cells.
+    pub fn cells(&self) -> &[Pos] {
+        &self.cells
+    }
+
+    /// Returns the conflicting slots.
+    pub fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+}
+
+impl Display for Explanation {
+    /// Formats the explanation as a sentence, e.g. "Prefilled letters at (2,3),(2,4) plus slot 5-Down have no
+    /// compatible words". Cells are given as (row,column), starting at 0. Slots are numbered as usual in crosswords.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::with_capacity(2);
+        if !self.cells.is_empty() {
+            let cells: Vec<String> = self
+                .cells
+                .iter()
+                .map(|pos| format!("({},{})", pos.row(), pos.column()))
+                .collect();
+            let noun = if cells.len() == 1 {
+                "letter"
+            } else {
+                "letters"
+            };
+            parts.push(format!("prefilled {noun} at {}", cells.join(",")));
+        }
+        if !self.slot_names.is_empty() {
+            let noun = if self.slot_names.len() == 1 {
+                "slot"
+            } else {
+                "slots"
+            };
+            parts.push(format!("{noun} {}", self.slot_names.join(",")));
+        }
+        if parts.is_empty() {
+            return write!(f, "The grid cannot be filled");
+        }
+        let culprits = parts.join(" plus ");
+        let verb = if self.cells.len() + self.slots.len() == 1 {
+            "has"
+        } else {
+            "have"
+        };
+        let mut first_letter = culprits.chars();
+        let capitalized: String = first_letter
+            .next()
+            .map(|letter| letter.to_ascii_uppercase())
+            .into_iter()
+            .chain(first_letter)
+            .collect();
+        write!(f, "{capitalized} {verb} no compatible words")
+    }
+}
+
+/// What a selector literal stands for.
+#[derive(Clone, Debug)]
+pub enum Culprit {
+    /// The prefilled letter of a cell
+    Cell(Pos),
+    /// The "exactly one word" constraint of a slot
+    Slot(Slot),
+}
+
+/// Adds the clauses of the problem to the given solver, with the prefilled letters and the slot constraints behind
+/// selector literals, so that they can be disabled by not assuming these literals. Slots are encoded using
+/// multi-valued decision diagrams, see [MddEncoding](crate::encoding::MddEncoding). Blocks and empty cells are not
+/// selectable: They define the slots.
+///
+/// Returns the selector literals and what they stand for.
+pub fn add_clauses_with_selectors_to(
+    grid: &Grid,
+    words: &[String],
+    solver: &mut dyn SolverConfigurator,
+) -> Vec<(Lit, Culprit)> {
+    let variables = Variables::new(grid.clone(), 0);
+    let constraints = Constraints::new(grid.clone(), variables.clone(), words);
+    let slots = grid.slots();
+    let mut mdds = HashMap::new();
+    for slot in &slots {
+        mdds.entry(slot.len())
+            .or_insert_with(|| Mdd::new(words, slot.len()));
+    }
+    let slot_variables_count: usize = slots
+        .iter()
+        .map(|slot| 1 + mdds[&slot.len()].inner_node_count())
+        .sum();
+    solver.allocate_variables(variables.representing_cell_count() + slot_variables_count);
+    solver.set_relevant_variables(variables.representing_cells());
+    constraints.add_one_letter_or_block_per_cell_clauses_to(solver);
+
+    let mut selectors = Vec::new();
+    for row in 0..grid.row_count() {
+        for column in 0..grid.column_count() {
+            match grid.letter_at(row, column) {
+                grid::EMPTY => {
+                    let block = variables.representing_cell(row, column, BLOCK_INDEX);
+                    solver.add_clause(&[block.literal(false)]);
+                }
+                grid::BLOCK => {
+                    let block = variables.representing_cell(row, column, BLOCK_INDEX);
+                    solver.add_clause(&[block.into()]);
+                }
+                letter => {
+                    let letter_index = alphabet::index_of(letter).unwrap();
+                    let selector = variables.representing_cell(row, column, letter_index);
+                    selectors.push((selector.into(), Culprit::Cell(Pos::new(column, row))));
+                }
+            }
+        }
+    }
+
+    let mut next_variable = variables.representing_cell_count() + 1;
+    for slot in slots {
+        let selector = Lit::from(Var::new(next_variable));
+        next_variable = constraints.add_one_word_in_slot_clauses_using_mdd_to(
+            &slot,
+            &mdds[&slot.len()],
+            next_variable + 1,
+            Some(selector),
+            solver,
+        );
+        selectors.push((selector, Culprit::Slot(slot)));
+    }
+    selectors
+}
+
+/// Explains why the given grid cannot be filled, using the given solver containing the clauses added by
+/// [add_clauses_with_selectors_to] and the returned selectors.
+///
+/// The unsatisfiable core is first given by the solver, if it is able to tell the failed assumptions, then it is
+/// minimized by removing the selectors one by one.
+///
+/// Returns [None] if the grid can actually be filled, or an error if the solver does not support solving under
+/// assumptions.
+pub fn explain(
+    grid: &Grid,
+    selectors: Vec<(Lit, Culprit)>,
+    solver: &mut dyn Solver<Item = Model>,
+) -> Result<Option<Explanation>, SolverError> {
+    let mut core: Vec<Lit> = selectors.iter().map(|&(selector, _)| selector).collect();
+    if !is_unsat(&mut core, solver)? {
+        return Ok(None);
+    }
+    let mut index = 0;
+    while index < core.len() {
+        let mut candidate_core = core.clone();
+        candidate_core.remove(index);
+        if is_unsat(&mut candidate_core, solver)? {
+            core = candidate_core;
+        } else {
+            index += 1;
+        }
+    }
+    let culprits: HashMap<Lit, Culprit> = selectors.into_iter().collect();
+    let mut explanation = Explanation {
+        cells: Vec::new(),
+        slots: Vec::new(),
+        slot_names: Vec::new(),
+    };
+    for selector in core {
+        match &culprits[&selector] {
+            Culprit::Cell(pos) => explanation.cells.push(*pos),
+            Culprit::Slot(slot) => {
+                explanation.slot_names.push(slot_name(grid, slot));
+                explanation.slots.push(slot.clone());
+            }
+        }
+    }
+    Ok(Some(explanation))
+}
+
+/// Solves the problem assuming the given selectors. If unsatisfiable, shrinks the selectors to the failed ones, if
+/// the solver is able to tell them.
+///
+/// Returns whether the problem is unsatisfiable, or an error if the solver does not support solving under assumptions.
+fn is_unsat(
+    selectors: &mut Vec<Lit>,
+    solver: &mut dyn Solver<Item = Model>,
+) -> Result<bool, SolverError> {
+    match solver.solve_with_assumptions(selectors) {
+        SolveOutcome::Sat(_) => Ok(false),
+        SolveOutcome::Unsat => {
+            if let Some(failed_assumptions) = solver.failed_assumptions() {
+                selectors.retain(|selector| failed_assumptions.contains(selector));
+            }
+            Ok(true)
+        }
+        SolveOutcome::Interrupted => Err(SolverError::new("Search interrupted")),
+        SolveOutcome::Unsupported => Err(SolverError::new(
+            "Solver does not support solving under assumptions",
+        )),
+        SolveOutcome::Failed(error) => Err(error),
+    }
+}
+
+/// Returns the conventional name of the given slot in the given grid, e.g. "5-Down". Slot starts are numbered from 1,
+/// row by row.
+fn slot_name(grid: &Grid, slot: &Slot) -> String {
+    let starts: Vec<Pos> = grid
+        .slots()
+        .iter()
+        .map(|slot| slot.positions()[0])
+        .collect();
+    let start = slot.positions()[0];
+    let mut number = 0;
+    'rows: for row in 0..grid.row_count() {
+        for column in 0..grid.column_count() {
+            if starts.contains(&Pos::new(column, row)) {
+                number += 1;
+            }
+            if start == Pos::new(column, row) {
+                break 'rows;
+            }
+        }
+    }
+    let direction = if slot.is_down() { "Down" } else { "Across" };
+    format!("{number}-{direction}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn slot_name() {
+        let grid = Grid::try_from("...#\n.#..\n....").unwrap();
+
+        let names: Vec<String> = grid
+            .slots()
+            .iter()
+            .map(|slot| super::slot_name(&grid, slot))
+            .collect();
+
+        assert_eq!(
+            vec!["1-Across", "3-Across", "5-Across", "1-Down", "2-Down", "4-Down"],
+            names
+        );
+    }
+
+    #[test]
+    fn display() {
+        let explanation = Explanation {
+            cells: vec![Pos::new(3, 2), Pos::new(4, 2)],
+            slots: vec![Slot::down(0, 3, 1)],
+            slot_names: vec!["5-Down".to_string()],
+        };
+
+        assert_eq!(
+            "Prefilled letters at (2,3),(2,4) plus slot 5-Down have no compatible words",
+            explanation.to_string()
+        );
+    }
+
+    #[test]
+    fn display_single_slot() {
+        let explanation = Explanation {
+            cells: vec![],
+            slots: vec![Slot::across(0, 3, 0)],
+            slot_names: vec!["1-Across".to_string()],
+        };
+
+        assert_eq!(
+            "Slot 1-Across has no compatible words",
+            explanation.to_string()
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::io::{self, Write};
+
+use croissant_solver::Var;
+
+use crate::encoding::Meaning;
+
+/// Writes the given meanings of variables in JSON to the given writer, one variable per line:
+///
+/// - A cell variable gives the position of the cell and its value, i.e. a letter or a block.
+/// - A slot variable gives the position of the first cell of the slot, its direction, its length and its word.
+///
+/// Values and words are made of letters from 'A' to 'Z' or of blocks, hence need no escaping.
+pub fn write_meanings(meanings: &[(Var, Meaning)], writer: &mut dyn Write) -> io::Result<()> {
+    writeln!(writer, "{{")?;
+    writeln!(writer, "  \"variables\": [")?;
+    for (index, (variable, meaning)) in meanings.iter().enumerate() {
+        let separator = if index + 1 < meanings.len() { "," } else { "" };
+        match meaning {
+            Meaning::Cell(pos, value) => writeln!(
+                writer,
+                "    {{\"variable\": {variable}, \"cell\": {{\"row\": {}, \"column\": {}}}, \"value\": \"{value}\"}}{separator}",
+                pos.row(),
+                pos.column()
+            )?,
+            Meaning::Slot(slot, word) => {
+                let start = slot.positions()[0];
+                let direction = if slot.is_down() { "down" } else { "across" };
+                writeln!(
+                    writer,
+                    "    {{\"variable\": {variable}, \"slot\": {{\"row\": {}, \"column\": {}, \"direction\": \"{direction}\", \"length\": {}}}, \"word\": \"{word}\"}}{separator}",
+                    start.row(),
+                    start.column(),
+                    slot.len()
+                )?
+            }
+        }
+    }
+    writeln!(writer, "  ]")?;
+    writeln!(writer, "}}")?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pos::Pos;
+    use crate::slot::Slot;
+
+    #[test]
+    fn write_meanings() {
+        let meanings = [
+            (Var::new(1), Meaning::Cell(Pos::new(1, 0), 'A')),
+            (Var::new(2), Meaning::Cell(Pos::new(1, 0), '#')),
+            (
+                Var::new(3),
+                Meaning::Slot(Slot::down(0, 2, 1), "AB".to_string()),
+            ),
+        ];
+        let mut json = Vec::new();
+
+        super::write_meanings(&meanings, &mut json).unwrap();
+
+        let expected = r##"{
+  "variables": [
+    {"variable": 1, "cell": {"row": 0, "column": 1}, "value": "A"},
+    {"variable": 2, "cell": {"row": 0, "column": 1}, "value": "#"},
+    {"variable": 3, "slot": {"row": 0, "column": 1, "direction": "down", "length": 2}, "word": "AB"}
+  ]
+}
+"##;
+        assert_eq!(expected, String::from_utf8(json).unwrap());
+    }
+
+    #[test]
+    fn write_no_meaning() {
+        let mut json = Vec::new();
+
+        super::write_meanings(&[], &mut json).unwrap();
+
+        assert_eq!(
+            "{\n  \"variables\": [\n  ]\n}\n",
+            String::from_utf8(json).unwrap()
+        );
+    }
+}
@@ -57,6 +57,21 @@ impl Grid {
         self.rows[row].chars().nth(column).unwrap()
     }
 
+    /// Sets the value at given position. Value is either a letter, [BLOCK] or [EMPTY].
+    ///
+    /// Returns an error if the position is outside the grid or if the value is not valid.
+    pub fn set_letter_at(&mut self, row: usize, column: usize, value: char) -> Result<(), String> {
+        if row >= self.row_count() || column >= self.column_count() {
+            return Err(format!("Invalid position: Row #{row}, column #{column}"));
+        }
+        if value != EMPTY && value != BLOCK && !alphabet::contains(value) {
+            return Err(format!("Invalid value at row #{row}: {value}"));
+        }
+        let mut buffer = [0; 4];
+        self.rows[row].replace_range(column..column + 1, value.encode_utf8(&mut buffer));
+        Ok(())
+    }
+
     /// Returns the slots of this grid.
     pub fn slots(&self) -> Vec<Slot> {
         let mut slots = vec![];
@@ -149,6 +164,32 @@ mod tests {
         assert_eq!(expected_err, grid_creation);
     }
 
+    #[test]
+    fn grid_set_letter_at() {
+        let mut grid = Grid::try_from("ABC\n...").unwrap();
+
+        grid.set_letter_at(1, 2, BLOCK).unwrap();
+        grid.set_letter_at(0, 0, EMPTY).unwrap();
+        grid.set_letter_at(1, 0, 'Z').unwrap();
+
+        assert_eq!(Grid::try_from(".BC\nZ.#").unwrap(), grid);
+    }
+
+    #[test]
+    fn grid_set_letter_at_invalid() {
+        let mut grid = Grid::try_from("ABC\n...").unwrap();
+
+        assert_eq!(
+            Err(String::from("Invalid position: Row #2, column #0")),
+            grid.set_letter_at(2, 0, 'A')
+        );
+        assert_eq!(
+            Err(String::from("Invalid value at row #1: @")),
+            grid.set_letter_at(1, 0, '@')
+        );
+        assert_eq!(Grid::try_from("ABC\n...").unwrap(), grid);
+    }
+
     #[test]
     fn grid_row_count() {
         let grid = Grid::try_from("A\nB").unwrap();
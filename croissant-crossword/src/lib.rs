@@ -18,7 +18,7 @@
 //!
 //! // Solving using logicng solver (not included)
 //! let solver_builder = Box::new(LogicngSolverBuilder::new());
-//! let solutions = crossword.solve_with_solver_built_by(solver_builder);
+//! let solutions = crossword.solve_with_solver_built_by(solver_builder).unwrap();
 //! for solution in solutions {
 //!     println!("{solution}")
 //! }
@@ -31,11 +31,26 @@
 
 // API
 pub mod crossword;
+pub mod encoding;
+pub mod grid;
+pub mod pos;
+pub mod session;
+pub mod slot;
 
 // Implementation
 mod alphabet;
+mod candidates;
 mod constraints;
-mod grid;
-mod pos;
-mod slot;
+mod counting;
+mod cubes;
+mod diversity;
+mod explanation;
+mod export;
+mod mdd;
+mod native;
+mod random;
+mod trivial;
+mod variable_counter;
 mod variables;
+mod viable;
+mod word_level;
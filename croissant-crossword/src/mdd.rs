@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use crate::alphabet;
+
+/// A node of a [Mdd]: The list of its outgoing edges, each edge being a pair (letter index, index of the target node
+/// in the next level).
+pub type Node = Vec<(usize, usize)>;
+
+/// A multi-valued decision diagram (MDD) representing a set of words of the same length.
+///
+/// It is a minimized deterministic finite automaton, organized in levels: Level *i* contains the nodes reached after
+/// reading the *i* first letters of a word. Level 0 contains a single node, the root. The last level contains a single
+/// node, the terminal node, which has no outgoing edge. Every path from the root to the terminal node spells a word
+/// of the set, and every word of the set is spelled by exactly one path.
+///
+/// Minimization means that nodes having the same outgoing edges are merged, i.e. the common suffixes of the words are
+/// shared. This is what makes the diagram much smaller than a plain prefix trie.
+#[derive(Debug, PartialEq)]
+pub struct Mdd {
+    /// The nodes, by level.
+    levels: Vec<Vec<Node>>,
+}
+
+impl Mdd {
+    /// Creates the diagram of the given words of the given length. Words with a different length are ignored.
+    ///
+    /// Panics if a word contains a letter which is not in the [alphabet].
+    pub fn new(words: &[String], length: usize) -> Self {
+        let mut words: Vec<&[u8]> = words
+            .iter()
+            .filter(|word| word.len() == length)
+            .map(|word| word.as_bytes())
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+
+        let mut mdd = Mdd {
+            levels: vec![Vec::new(); length + 1],
+        };
+        if !words.is_empty() {
+            let mut unique_nodes = vec![HashMap::new(); length + 1];
+            mdd.build(&words, 0, &mut unique_nodes);
+        }
+        mdd
+    }
+
+    /// Builds the sub-diagram of the given sorted words, starting at the given depth. Returns the index of the root
+    /// of the sub-diagram in the level corresponding to the given depth.
+    fn build(
+        &mut self,
+        words: &[&[u8]],
+        depth: usize,
+        unique_nodes: &mut Vec<HashMap<Node, usize>>,
+    ) -> usize {
+        let mut node = Node::new();
+        if depth < self.length() {
+            let mut group_start = 0;
+            while group_start < words.len() {
+                let letter = words[group_start][depth];
+                let group_end = group_start
+                    + words[group_start..]
+                        .iter()
+                        .take_while(|word| word[depth] == letter)
+                        .count();
+                let child = self.build(&words[group_start..group_end], depth + 1, unique_nodes);
+                let letter_index = alphabet::index_of(letter as char)
+                    .unwrap_or_else(|| panic!("Unsupported character {}", letter as char));
+                node.push((letter_index, child));
+                group_start = group_end;
+            }
+        }
+        let level = &mut self.levels[depth];
+        *unique_nodes[depth].entry(node).or_insert_with_key(|node| {
+            level.push(node.clone());
+            level.len() - 1
+        })
+    }
+
+    /// Returns the length of the words represented by this diagram.
+    pub fn length(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Returns `true` iff this diagram represents no word at all.
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// Returns the nodes at the given level.
+    pub fn nodes_at(&self, level: usize) -> &[Node] {
+        &self.levels[level]
+    }
+
+    /// Returns the number of non-terminal nodes, i.e. the number of nodes of all levels but the last one.
+    pub fn inner_node_count(&self) -> usize {
+        self.levels[..self.length()].iter().map(Vec::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|&word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn mdd_new() {
+        let words = words(&["ABC", "ABD", "BBC", "BBD", "CA", "ABC"]);
+
+        let mdd = Mdd::new(&words, 3);
+
+        assert_eq!(3, mdd.length());
+        assert_eq!(vec![vec![(0, 0), (1, 0)]], mdd.nodes_at(0)); // 'A' or 'B', same suffixes
+        assert_eq!(vec![vec![(1, 0)]], mdd.nodes_at(1)); // 'B'
+        assert_eq!(vec![vec![(2, 0), (3, 0)]], mdd.nodes_at(2)); // 'C' or 'D'
+        assert_eq!(vec![Node::new()], mdd.nodes_at(3)); // terminal
+        assert_eq!(3, mdd.inner_node_count());
+    }
+
+    #[test]
+    fn mdd_new_no_common_suffix() {
+        let words = words(&["AB", "BA"]);
+
+        let mdd = Mdd::new(&words, 2);
+
+        assert_eq!(vec![vec![(0, 0), (1, 1)]], mdd.nodes_at(0));
+        assert_eq!(vec![vec![(1, 0)], vec![(0, 0)]], mdd.nodes_at(1));
+        assert_eq!(3, mdd.inner_node_count());
+    }
+
+    #[test]
+    fn mdd_new_empty() {
+        let words = words(&["AB", "BA"]);
+
+        let mdd = Mdd::new(&words, 3);
+
+        assert!(mdd.is_empty());
+        assert_eq!(0, mdd.inner_node_count());
+    }
+}
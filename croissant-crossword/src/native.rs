@@ -0,0 +1,24 @@
+use croissant_solver::{Model, ProgressCallback, SearchLimits, Solver, SolverError};
+
+use crate::encoding::Decoder;
+use crate::grid::Grid;
+
+/// The builder of a native solver, i.e. of a solver working directly on the grid and the words rather than on clauses,
+/// e.g. a backtracking filler. See [Crossword::solve_natively_with](crate::crossword::Crossword::solve_natively_with).
+pub trait NativeSolverBuilder {
+    /// Builds the solver of the problem defined by the given grid and words. Words are given by order of preference.
+    ///
+    /// The models of the built solver must assign each cell variable, i.e. the variable of each literal returned by
+    /// [Decoder::cell_literal] of the given decoder. Each model must be a distinct solution of the problem, and the
+    /// solver must honor the [Solver] contract, including the given limits, as far as possible.
+    ///
+    /// Returns a [SolverError] if the solver cannot be created.
+    fn build(
+        self: Box<Self>,
+        grid: &Grid,
+        words: &[String],
+        decoder: &dyn Decoder,
+        limits: SearchLimits,
+        progress: Option<ProgressCallback>,
+    ) -> Result<Box<dyn Solver<Item = Model>>, SolverError>;
+}
@@ -1,5 +1,5 @@
 /// A cell position in the grid.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Pos {
     column: usize,
     row: usize,
@@ -0,0 +1,98 @@
+/// A small, seedable, pseudo-random number generator: SplitMix64. Good enough to shuffle words; Not suitable for
+/// anything related to security.
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Creates a new generator from the given seed.
+    pub fn new(seed: u64) -> Self {
+        Random { state: seed }
+    }
+
+    /// Returns the next pseudo-random integer.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns the next pseudo-random number in the open interval (0,1).
+    pub fn next_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+}
+
+/// Returns the given words in a pseudo-random order, determined by the given seed.
+///
+/// The order can be biased towards the words with the highest scores: A word is picked before another with a
+/// probability depending on the difference of their scores multiplied by the given bias. A bias of 0 gives a uniform
+/// shuffle, ignoring scores; A huge bias sorts words by descending score. Words without score have a score of 0.
+pub fn shuffle(words: &[String], scores: &[i32], seed: u64, score_bias: f64) -> Vec<String> {
+    let mut random = Random::new(seed);
+    // Gumbel-max trick: Sorting by biased score + Gumbel noise draws words with weights exp(bias * score)
+    let mut keyed_words: Vec<(f64, &String)> = words
+        .iter()
+        .enumerate()
+        .map(|(word_index, word)| {
+            let score = scores.get(word_index).copied().unwrap_or(0) as f64;
+            let gumbel_noise = -(-random.next_f64().ln()).ln();
+            (score_bias * score + gumbel_noise, word)
+        })
+        .collect();
+    keyed_words.sort_by(|(first_key, _), (second_key, _)| second_key.total_cmp(first_key));
+    keyed_words
+        .into_iter()
+        .map(|(_, word)| word.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(count: usize) -> Vec<String> {
+        (0..count).map(|index| format!("W{index}")).collect()
+    }
+
+    #[test]
+    fn next_f64() {
+        let mut random = Random::new(42);
+        for _ in 0..1000 {
+            let number = random.next_f64();
+            assert!(number > 0.0 && number < 1.0, "{number} is out of (0,1)");
+        }
+    }
+
+    #[test]
+    fn shuffle_is_reproducible() {
+        let words = words(20);
+
+        assert_eq!(shuffle(&words, &[], 42, 0.0), shuffle(&words, &[], 42, 0.0));
+        assert_ne!(shuffle(&words, &[], 42, 0.0), shuffle(&words, &[], 43, 0.0));
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation() {
+        let words = words(20);
+
+        let mut shuffled_words = shuffle(&words, &[], 42, 0.0);
+        shuffled_words.sort();
+        let mut sorted_words = words.clone();
+        sorted_words.sort();
+
+        assert_eq!(sorted_words, shuffled_words);
+    }
+
+    #[test]
+    fn shuffle_with_huge_bias() {
+        let words = words(5);
+        let scores = [3, 1, 5, 2, 4];
+
+        let shuffled_words = shuffle(&words, &scores, 42, 1000.0);
+
+        assert_eq!(vec!["W2", "W4", "W0", "W3", "W1"], shuffled_words);
+    }
+}
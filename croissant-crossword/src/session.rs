@@ -0,0 +1,452 @@
+//! # An editable crossword, solved incrementally
+//!
+//! [Crossword](crate::crossword::Crossword) encodes the whole problem each time it is solved. This is fine for a
+//! one-shot fill, but wasteful for an editor which solves the grid again after each modification: Most of the problem
+//! is unchanged.
+//!
+//! A [CrosswordSession] keeps the encoded problem in a [ConfigurableSolver] supporting assumptions - see
+//! [Solver::solve_with_assumptions](croissant_solver::Solver::solve_with_assumptions) - and only translates the
+//! modifications:
+//!
+//! - Cell contents are not clauses but *assumptions*, given to the solver at each call to [CrosswordSession::solve].
+//!   Changing a letter is thus free.
+//! - Each slot is guarded by an *activation literal*, assumed true as long as the slot exists in the grid. Adding or
+//!   removing a block deactivates the slots which do not exist anymore and encodes the new ones, if they were not
+//!   encoded before.
+//! - Excluded words are forbidden by clauses guarded by the activation literal of each slot.
+//!
+//! Slots are encoded using the multi-valued decision diagrams of [MddEncoding](crate::encoding::MddEncoding), built
+//! once per slot length.
+
+use std::collections::HashMap;
+use std::iter;
+
+use croissant_solver::{ConfigurableSolver, Lit, Model, SolveOutcome, SolverError, Var};
+
+use crate::alphabet;
+use crate::constraints::Constraints;
+use crate::grid::{self, Grid};
+use crate::mdd::Mdd;
+use crate::slot::Slot;
+use crate::variables::{Variables, BLOCK_INDEX};
+
+/// A crossword being edited, whose encoding is kept between two solves.
+pub struct CrosswordSession<'wordlist> {
+    /// The current grid
+    grid: Grid,
+    /// The word list
+    words: &'wordlist [String],
+    /// The cell variables, which do not change since the grid dimensions do not change
+    variables: Variables,
+    /// The decision diagrams of the words, by length, built as needed
+    mdds: HashMap<usize, Mdd>,
+    /// The words excluded from the solutions
+    excluded_words: Vec<String>,
+    /// The activation literals of the slots encoded so far, whether they still exist or not
+    activation_literals: HashMap<Slot, Lit>,
+    /// The next free variable
+    next_variable: usize,
+    /// The solver, holding the encoded problem
+    solver: Box<dyn ConfigurableSolver<Item = Model>>,
+}
+
+impl<'wordlist> CrosswordSession<'wordlist> {
+    /// Creates a new session from given grid, word list and solver.
+    ///
+    /// ## Arguments
+    ///
+    /// - `input_grid`: A string representing the grid rows. '.' indicates a blank cell, '#' indicates a block.
+    /// - `words`: The word list. Must contain words with only letters from 'A' to 'Z'.
+    /// - `solver`: The solver. It must be empty: The session adds all the clauses it needs. It must support solving
+    ///   under assumptions, otherwise [solve](Self::solve) fails.
+    ///
+    /// ## Returns
+    ///
+    /// A [Result] with the created session, or a String containing the error details.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use croissant_crossword::session::CrosswordSession;
+    /// use croissant_solver::{ConfigurableSolver, Model, SolverError};
+    ///
+    /// fn fill(solver: Box<dyn ConfigurableSolver<Item = Model>>) -> Result<Option<String>, SolverError> {
+    ///     let words = ["AAA".to_string()];
+    ///     let mut session = CrosswordSession::try_from("...\n...\n...", &words, solver).unwrap();
+    ///     session.set_cell(0, 0, 'A').unwrap();
+    ///     session.set_block(1, 1).unwrap();
+    ///     session.solve()
+    /// }
+    /// ```
+    pub fn try_from(
+        input_grid: &str,
+        words: &'wordlist [String],
+        mut solver: Box<dyn ConfigurableSolver<Item = Model>>,
+    ) -> Result<Self, String> {
+        let grid = Grid::try_from(input_grid)?;
+        let variables = Variables::new(grid.clone(), 0);
+        let constraints = Constraints::new(grid.clone(), variables.clone(), words);
+        solver.allocate_variables(variables.representing_cell_count());
+        solver.set_relevant_variables(variables.representing_cells());
+        constraints.add_one_letter_or_block_per_cell_clauses_to(solver.as_mut());
+        Ok(CrosswordSession {
+            grid,
+            words,
+            next_variable: variables.representing_cell_count() + 1,
+            variables,
+            mdds: HashMap::new(),
+            excluded_words: Vec::new(),
+            activation_literals: HashMap::new(),
+            solver,
+        })
+    }
+
+    /// Returns the current grid, i.e. the input grid with all the modifications applied.
+    pub fn grid(&self) -> String {
+        (0..self.grid.row_count())
+            .map(|row| {
+                (0..self.grid.column_count())
+                    .map(|column| self.grid.letter_at(row, column))
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Puts the given letter in the cell at the given position. The cell may previously contain a block.
+    ///
+    /// Returns an error if the position is outside the grid or if the letter is not in the alphabet.
+    pub fn set_cell(&mut self, row: usize, column: usize, letter: char) -> Result<(), String> {
+        if !alphabet::contains(letter) {
+            return Err(format!("Invalid letter: {letter}"));
+        }
+        self.grid.set_letter_at(row, column, letter)
+    }
+
+    /// Empties the cell at the given position. The cell may previously contain a block.
+    ///
+    /// Returns an error if the position is outside the grid.
+    pub fn clear_cell(&mut self, row: usize, column: usize) -> Result<(), String> {
+        self.grid.set_letter_at(row, column, grid::EMPTY)
+    }
+
+    /// Puts a block in the cell at the given position.
+    ///
+    /// Returns an error if the position is outside the grid.
+    pub fn set_block(&mut self, row: usize, column: usize) -> Result<(), String> {
+        self.grid.set_letter_at(row, column, grid::BLOCK)
+    }
+
+    /// Excludes the given word from the solutions. Exclusion is definitive.
+    ///
+    /// Returns an error if the word contains a letter which is not in the alphabet.
+    pub fn exclude_word(&mut self, word: &str) -> Result<(), String> {
+        if let Some(letter) = word.chars().find(|&letter| !alphabet::contains(letter)) {
+            return Err(format!("Invalid letter in word {word}: {letter}"));
+        }
+        let constraints = Constraints::new(self.grid.clone(), self.variables.clone(), self.words);
+        for (slot, &activation_literal) in &self.activation_literals {
+            if slot.len() == word.len() {
+                add_excluded_word_clause_to(
+                    &constraints,
+                    slot,
+                    word,
+                    activation_literal,
+                    self.solver.as_mut(),
+                );
+            }
+        }
+        self.excluded_words.push(word.to_string());
+        Ok(())
+    }
+
+    /// Solves the current grid.
+    ///
+    /// Returns a solution, if any. Calling this function again without modifying the grid may return the same
+    /// solution. Returns an error if the solver does not support solving under assumptions, or if the search is
+    /// interrupted.
+    pub fn solve(&mut self) -> Result<Option<String>, SolverError> {
+        let slots = self.grid.slots();
+        self.encode_new_slots(&slots);
+        let mut assumptions = self.cell_assumptions();
+        assumptions.extend(slots.iter().map(|slot| self.activation_literals[slot]));
+        match self.solver.solve_with_assumptions(&assumptions) {
+            SolveOutcome::Sat(model) => Ok(Some(self.variables.back_to_domain(&model))),
+            SolveOutcome::Unsat => Ok(None),
+            SolveOutcome::Interrupted => Err(SolverError::new("Search interrupted")),
+            SolveOutcome::Unsupported => Err(SolverError::new(
+                "Solver does not support solving under assumptions",
+            )),
+            SolveOutcome::Failed(error) => Err(error),
+        }
+    }
+
+    /// Encodes the given slots which have not been encoded yet.
+    fn encode_new_slots(&mut self, slots: &[Slot]) {
+        let constraints = Constraints::new(self.grid.clone(), self.variables.clone(), self.words);
+        for slot in slots {
+            if self.activation_literals.contains_key(slot) {
+                continue;
+            }
+            let activation_literal = Lit::from(Var::new(self.next_variable));
+            let words = self.words;
+            let mdd = self
+                .mdds
+                .entry(slot.len())
+                .or_insert_with(|| Mdd::new(words, slot.len()));
+            self.next_variable = constraints.add_one_word_in_slot_clauses_using_mdd_to(
+                slot,
+                mdd,
+                self.next_variable + 1,
+                Some(activation_literal),
+                self.solver.as_mut(),
+            );
+            for word in &self.excluded_words {
+                if word.len() == slot.len() {
+                    add_excluded_word_clause_to(
+                        &constraints,
+                        slot,
+                        word,
+                        activation_literal,
+                        self.solver.as_mut(),
+                    );
+                }
+            }
+            self.activation_literals
+                .insert(slot.clone(), activation_literal);
+        }
+    }
+
+    /// Returns the assumptions corresponding to the current cell values: Prefilled letters and blocks must be kept as
+    /// is, and empty cells must not become blocks.
+    fn cell_assumptions(&self) -> Vec<Lit> {
+        let mut assumptions = Vec::with_capacity(self.grid.row_count() * self.grid.column_count());
+        for row in 0..self.grid.row_count() {
+            for column in 0..self.grid.column_count() {
+                let assumption = match self.grid.letter_at(row, column) {
+                    grid::EMPTY => self
+                        .variables
+                        .representing_cell(row, column, BLOCK_INDEX)
+                        .literal(false),
+                    grid::BLOCK => self
+                        .variables
+                        .representing_cell(row, column, BLOCK_INDEX)
+                        .into(),
+                    letter => {
+                        let letter_index = alphabet::index_of(letter).unwrap();
+                        self.variables
+                            .representing_cell(row, column, letter_index)
+                            .into()
+                    }
+                };
+                assumptions.push(assumption);
+            }
+        }
+        assumptions
+    }
+}
+
+/// Adds the clause forbidding the given word in the given slot when the given activation literal is true to the given
+/// solver.
+fn add_excluded_word_clause_to(
+    constraints: &Constraints,
+    slot: &Slot,
+    word: &str,
+    activation_literal: Lit,
+    solver: &mut dyn ConfigurableSolver<Item = Model>,
+) {
+    let mut cell_literals = Vec::with_capacity(word.len());
+    constraints.fill_cell_literals_conjunction(&mut cell_literals, slot, word);
+    let clause: Vec<Lit> = iter::once(-activation_literal)
+        .chain(cell_literals.iter().map(|&cell_literal| -cell_literal))
+        .collect();
+    solver.add_clause(&clause);
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use croissant_solver::{Solver, SolverConfigurator};
+
+    use super::*;
+
+    /// What the stub solver received.
+    #[derive(Default)]
+    struct Received {
+        clauses: Vec<Vec<i32>>,
+        assumptions: Vec<i32>,
+    }
+
+    /// A solver which always returns the same outcome and records what it receives, for inspection once moved into
+    /// the session.
+    struct StubSolver(Rc<RefCell<Received>>, SolveOutcome);
+
+    impl SolverConfigurator for StubSolver {
+        fn add_clause(&mut self, literals: &[Lit]) {
+            self.0.borrow_mut().clauses.push(raw(literals))
+        }
+
+        fn add_exactly_one(&mut self, _literals: &[Lit]) {
+            // Do nothing, cell clauses are not verified here.
+        }
+    }
+
+    impl Iterator for StubSolver {
+        type Item = Model;
+        fn next(&mut self) -> Option<Self::Item> {
+            None
+        }
+    }
+
+    impl Solver for StubSolver {
+        fn solve_with_assumptions(&mut self, assumptions: &[Lit]) -> SolveOutcome {
+            self.0.borrow_mut().assumptions = raw(assumptions);
+            self.1.clone()
+        }
+    }
+
+    impl ConfigurableSolver for StubSolver {}
+
+    /// Returns the raw representations of the given literals.
+    fn raw(literals: &[Lit]) -> Vec<i32> {
+        literals.iter().map(|&literal| i32::from(literal)).collect()
+    }
+
+    fn words() -> Vec<String> {
+        ["AB", "BA", "ABC"]
+            .iter()
+            .map(|&word| word.to_string())
+            .collect()
+    }
+
+    fn session_with_stub<'wordlist>(
+        grid: &str,
+        words: &'wordlist [String],
+    ) -> (CrosswordSession<'wordlist>, Rc<RefCell<Received>>) {
+        let received = Rc::new(RefCell::new(Received::default()));
+        let solver = Box::new(StubSolver(received.clone(), SolveOutcome::Unsat));
+        let session = CrosswordSession::try_from(grid, words, solver).unwrap();
+        (session, received)
+    }
+
+    #[test]
+    fn edit() {
+        let words = words();
+        let (mut session, _) = session_with_stub("...\n...", &words);
+
+        session.set_cell(0, 0, 'A').unwrap();
+        session.set_block(0, 2).unwrap();
+        session.set_block(1, 1).unwrap();
+        session.clear_cell(1, 1).unwrap();
+
+        assert_eq!("A.#\n...", session.grid());
+    }
+
+    #[test]
+    fn edit_invalid() {
+        let words = words();
+        let (mut session, _) = session_with_stub("...\n...", &words);
+
+        assert_eq!(
+            Err(String::from("Invalid letter: #")),
+            session.set_cell(0, 0, '#')
+        );
+        assert_eq!(
+            Err(String::from("Invalid position: Row #2, column #0")),
+            session.set_block(2, 0)
+        );
+        assert_eq!(
+            Err(String::from("Invalid letter in word A-B: -")),
+            session.exclude_word("A-B")
+        );
+        assert_eq!("...\n...", session.grid());
+    }
+
+    #[test]
+    fn solve_assumes_cells_and_slots() {
+        let words = words();
+        let (mut session, stub) = session_with_stub("A.", &words);
+
+        session.solve().unwrap();
+
+        // 2 cells * 27 values, then the activation literal of the single slot
+        assert_eq!(vec![1, -54, 55], stub.borrow().assumptions);
+        assert_eq!(
+            vec![
+                vec![-55, 56],     // activation literal => root state
+                vec![-56, 1, 2],   // root state => 'A' or 'B' at (0,0)
+                vec![-56, -1, 57], // root state and 'A' at (0,0) => state after 'A'
+                vec![-56, -2, 58], // root state and 'B' at (0,0) => state after 'B'
+                vec![-57, 29],     // state after 'A' => 'B' at (1,0)
+                vec![-58, 28],     // state after 'B' => 'A' at (1,0)
+            ],
+            stub.borrow().clauses
+        );
+    }
+
+    #[test]
+    fn solve_encodes_slots_once() {
+        let words = words();
+        let (mut session, stub) = session_with_stub("..\n..", &words);
+        session.solve().unwrap();
+        let clauses_count = stub.borrow().clauses.len();
+
+        session.set_cell(0, 0, 'B').unwrap();
+        session.solve().unwrap();
+        assert_eq!(clauses_count, stub.borrow().clauses.len());
+
+        session.set_block(1, 1).unwrap();
+        session.solve().unwrap();
+        assert_eq!(clauses_count, stub.borrow().clauses.len()); // Slots only disappeared
+        assert_eq!(
+            // Cells, then activation literals of the across and down slots at (0,0)
+            vec![2, -54, -81, 108, 109, 117],
+            stub.borrow().assumptions
+        );
+
+        session.clear_cell(1, 1).unwrap();
+        session.solve().unwrap();
+        assert_eq!(clauses_count, stub.borrow().clauses.len()); // Slots reappeared
+    }
+
+    #[test]
+    fn exclude_word() {
+        let words = words();
+        let (mut session, stub) = session_with_stub("..", &words);
+        session.solve().unwrap();
+        stub.borrow_mut().clauses.clear();
+
+        session.exclude_word("BA").unwrap();
+        session.exclude_word("ABC").unwrap();
+
+        // Not 'B' at (0,0) and 'A' at (1,0) when slot is active
+        assert_eq!(vec![vec![-55, -2, -28]], stub.borrow().clauses);
+    }
+
+    #[test]
+    fn exclude_word_before_slot_is_encoded() {
+        let words = words();
+        let (mut session, stub) = session_with_stub("..", &words);
+
+        session.exclude_word("BA").unwrap();
+        session.solve().unwrap();
+
+        assert_eq!(Some(&vec![-55, -2, -28]), stub.borrow().clauses.last());
+    }
+
+    #[test]
+    fn solve_unsupported() {
+        let words = words();
+        let solver = Box::new(StubSolver(Rc::default(), SolveOutcome::Unsupported));
+        let mut session = CrosswordSession::try_from("..", &words, solver).unwrap();
+
+        assert_eq!(
+            Err(SolverError::new(
+                "Solver does not support solving under assumptions"
+            )),
+            session.solve()
+        );
+    }
+}
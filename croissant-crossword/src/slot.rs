@@ -4,7 +4,7 @@ use crate::pos::Pos;
 pub const MIN_LEN: usize = 2;
 
 /// The definition of a group of contiguous cells.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Slot {
     /// The start of the varying coordinate.
     start: usize,
@@ -42,6 +42,16 @@ impl Slot {
         self.end - self.start
     }
 
+    /// Returns `true` iff this slot has no cell. Should not happen, see [MIN_LEN].
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` iff this is a down slot.
+    pub fn is_down(&self) -> bool {
+        self.is_down
+    }
+
     /// Returns the positions of the cells of this slot.
     pub fn positions(&self) -> Vec<Pos> {
         (self.start..self.end)
@@ -0,0 +1,109 @@
+use croissant_solver::{Lit, Model, Solver, SolverConfigurator, Var};
+
+use crate::alphabet;
+use crate::grid::{self, Grid};
+use crate::variables::{Variables, BLOCK_INDEX};
+
+/// Returns the solver of the given problem if its solutions are known without searching, or [None] otherwise:
+///
+/// - A grid without slot and without empty cell, e.g. an empty grid, has a single solution: Itself.
+/// - A grid with slots but without words has no solution.
+///
+/// Models of the returned solver give the state of the cell variables of [Variables::new].
+pub fn solver(grid: &Grid, words: &[String]) -> Option<TrivialSolver> {
+    let has_slots = grid.slot_count() > 0;
+    if has_slots && words.is_empty() {
+        return Some(TrivialSolver { model: None });
+    }
+    let cells: Vec<(usize, usize)> = (0..grid.row_count())
+        .flat_map(|row| (0..grid.column_count()).map(move |column| (row, column)))
+        .collect();
+    let has_empty_cells = cells
+        .iter()
+        .any(|&(row, column)| grid.letter_at(row, column) == grid::EMPTY);
+    if has_slots || has_empty_cells {
+        return None;
+    }
+    let variables = Variables::new(grid.clone(), 0);
+    let mut model: Model = (1..=variables.representing_cell_count())
+        .map(|variable| Var::new(variable).literal(false))
+        .collect();
+    for (row, column) in cells {
+        let value = match grid.letter_at(row, column) {
+            grid::BLOCK => BLOCK_INDEX,
+            letter => alphabet::index_of(letter).expect("Grid should only contain letters"),
+        };
+        model.set(variables.representing_cell(row, column, value).into());
+    }
+    Some(TrivialSolver { model: Some(model) })
+}
+
+/// A [Solver] of a problem whose solutions are known without searching: It has at most one model.
+///
+/// Clauses added between two searches are ignored: There is no other model to exclude.
+pub struct TrivialSolver {
+    /// The model not returned yet, if any.
+    model: Option<Model>,
+}
+
+impl Iterator for TrivialSolver {
+    type Item = Model;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.model.take()
+    }
+}
+
+impl Solver for TrivialSolver {
+    fn as_configurator(&mut self) -> Option<&mut dyn SolverConfigurator> {
+        Some(self)
+    }
+}
+
+impl SolverConfigurator for TrivialSolver {
+    fn add_clause(&mut self, _literals: &[Lit]) {
+        // Nothing to exclude
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|&word| word.to_string()).collect()
+    }
+
+    fn solutions(grid: &str, words: &[String]) -> Option<Vec<String>> {
+        let grid = Grid::try_from(grid).unwrap();
+        let solver = solver(&grid, words)?;
+        let variables = Variables::new(grid, 0);
+        Some(
+            solver
+                .map(|model| variables.back_to_domain(&model))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn empty_grid() {
+        assert_eq!(Some(vec![String::new()]), solutions("", &words(&[])));
+        assert_eq!(Some(vec![String::new()]), solutions("", &words(&["AB"])));
+    }
+
+    #[test]
+    fn no_slot() {
+        assert_eq!(
+            Some(vec!["A#\n#B".to_string()]),
+            solutions("A#\n#B", &words(&[]))
+        );
+        assert_eq!(None, solutions(".#\n#B", &words(&[])));
+    }
+
+    #[test]
+    fn no_word() {
+        assert_eq!(Some(vec![]), solutions("...", &words(&[])));
+        assert_eq!(Some(vec![]), solutions("ABC", &words(&[])));
+        assert_eq!(None, solutions("...", &words(&["ABC"])));
+    }
+}
@@ -0,0 +1,164 @@
+use croissant_solver::{Lit, ProgressCallback, SearchLimits, SolverConfigurator, Var};
+
+/// A [SolverConfigurator] proxy counting the variables used by the clauses it forwards, in order to know the first
+/// free variable afterwards, after the auxiliary variables the actual configurator may have used as well, see
+/// [SolverConfigurator::variables_count]. It counts the forwarded clauses as well, a cardinality constraint counting as
+/// one.
+pub struct VariableCounter<'a> {
+    /// The actual configurator
+    configurator: &'a mut dyn SolverConfigurator,
+    /// The greatest variable seen so far
+    max_variable: usize,
+    /// The number of clauses and constraints forwarded so far
+    clause_count: usize,
+}
+
+impl<'a> VariableCounter<'a> {
+    /// Creates a new proxy of the given configurator.
+    pub fn new(configurator: &'a mut dyn SolverConfigurator) -> Self {
+        VariableCounter {
+            configurator,
+            max_variable: 0,
+            clause_count: 0,
+        }
+    }
+
+    /// Returns the first variable not used so far.
+    pub fn next_variable(&self) -> usize {
+        let configurator_variables_count = self.configurator.variables_count().unwrap_or(0);
+        self.max_variable.max(configurator_variables_count) + 1
+    }
+
+    /// Returns the number of clauses and constraints forwarded so far.
+    pub fn clause_count(&self) -> usize {
+        self.clause_count
+    }
+
+    /// Records the variables of the given literals.
+    fn record(&mut self, literals: &[Lit]) {
+        let max_variable = literals.iter().map(|literal| literal.var().index()).max();
+        self.max_variable = self.max_variable.max(max_variable.unwrap_or(0));
+    }
+}
+
+impl SolverConfigurator for VariableCounter<'_> {
+    fn allocate_variables(&mut self, variables_count: usize) {
+        self.max_variable = self.max_variable.max(variables_count);
+        self.configurator.allocate_variables(variables_count);
+    }
+
+    fn variables_count(&self) -> Option<usize> {
+        Some(self.next_variable() - 1)
+    }
+
+    fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
+        self.configurator.set_relevant_variables(relevant_variables);
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.configurator.set_seed(seed);
+    }
+
+    fn set_limits(&mut self, limits: SearchLimits) {
+        self.configurator.set_limits(limits);
+    }
+
+    fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        self.configurator.supports_limits(limits)
+    }
+
+    fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.configurator.set_progress_callback(callback);
+    }
+
+    fn add_clause(&mut self, literals: &[Lit]) {
+        self.record(literals);
+        self.clause_count += 1;
+        self.configurator.add_clause(literals);
+    }
+
+    fn add_exactly_one(&mut self, literals: &[Lit]) {
+        self.record(literals);
+        self.clause_count += 1;
+        self.configurator.add_exactly_one(literals);
+    }
+
+    fn add_at_most_one(&mut self, literals: &[Lit]) {
+        self.record(literals);
+        self.clause_count += 1;
+        self.configurator.add_at_most_one(literals);
+    }
+
+    fn add_at_most_k(&mut self, literals: &[Lit], k: usize) {
+        self.record(literals);
+        self.clause_count += 1;
+        self.configurator.add_at_most_k(literals, k);
+    }
+
+    fn add_at_least_k(&mut self, literals: &[Lit], k: usize) {
+        self.record(literals);
+        self.clause_count += 1;
+        self.configurator.add_at_least_k(literals, k);
+    }
+
+    fn add_pb_le(&mut self, literals: &[Lit], weights: &[i64], bound: i64) {
+        self.record(literals);
+        self.clause_count += 1;
+        self.configurator.add_pb_le(literals, weights, bound);
+    }
+
+    fn add_and(&mut self, literal: Lit, conjunction: &[Lit]) {
+        self.record(&[literal]);
+        self.record(conjunction);
+        self.clause_count += 1;
+        self.configurator.add_and(literal, conjunction);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use croissant_solver::{AtMostOneEncoding, DimacsWriter};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct TestSolverConfigurator {
+        clauses: Vec<Vec<i32>>,
+    }
+
+    impl SolverConfigurator for TestSolverConfigurator {
+        fn add_clause(&mut self, literals: &[Lit]) {
+            self.clauses
+                .push(literals.iter().map(|&literal| i32::from(literal)).collect());
+        }
+    }
+
+    #[test]
+    fn variable_counter() {
+        let mut configurator = TestSolverConfigurator::default();
+        let mut counter = VariableCounter::new(&mut configurator);
+
+        counter.allocate_variables(3);
+        counter.add_clause(&[Lit::from_raw(1), Lit::from_raw(-7)]);
+        counter.add_and(Lit::from_raw(2), &[Lit::from_raw(3), Lit::from_raw(-5)]);
+
+        assert_eq!(8, counter.next_variable());
+        assert_eq!(2, counter.clause_count());
+    }
+
+    #[test]
+    fn variable_counter_after_auxiliary_variables() {
+        let mut dimacs_writer = DimacsWriter::new(Cursor::new(Vec::new())).unwrap();
+        let mut counter = VariableCounter::new(&mut dimacs_writer);
+        let literals: Vec<Lit> = (1..=40).map(|index| Var::new(index).into()).collect();
+
+        counter.allocate_variables(40);
+        counter.add_at_most_one(&literals);
+
+        let auxiliary_count = AtMostOneEncoding::auto(40).auxiliary_variable_count(40);
+        assert!(auxiliary_count > 0, "Auxiliary variables expected");
+        assert_eq!(41 + auxiliary_count, counter.next_variable());
+    }
+}
@@ -1,3 +1,5 @@
+use croissant_solver::{Model, Var};
+
 use crate::grid::Grid;
 use crate::{alphabet, grid};
 
@@ -70,37 +72,42 @@ impl Variables {
     ///     <th>etc.</th>
     ///   </tr>
     /// </table>
-    pub fn representing_cell(&self, row: usize, column: usize, value: usize) -> usize {
-        row * self.grid.column_count() * CELL_VALUE_COUNT + column * CELL_VALUE_COUNT + value + 1
-        // variable must be strictly positive
+    pub fn representing_cell(&self, row: usize, column: usize, value: usize) -> Var {
+        Var::new(
+            row * self.grid.column_count() * CELL_VALUE_COUNT
+                + column * CELL_VALUE_COUNT
+                + value
+                + 1,
+        )
     }
 
     /// Returns all the variables representing cells.
-    pub fn representing_cells(&self) -> Vec<usize> {
-        Vec::from_iter(1..(self.representing_cell_count() + 1))
+    pub fn representing_cells(&self) -> Vec<Var> {
+        (1..=self.representing_cell_count()).map(Var::new).collect()
     }
 
     /// Returns the variable associated to the given word at the given slot.
     ///
     /// Slot variable are put after cell variables, so first slot variable corresponds to the number
     /// of cell variables (plus 1 because variables start at 1).
-    pub fn representing_slot(&self, slot_index: usize, word_index: usize) -> usize {
-        self.representing_cell_count() // last cell variable
+    pub fn representing_slot(&self, slot_index: usize, word_index: usize) -> Var {
+        Var::new(
+            self.representing_cell_count() // last cell variable
             + slot_index * self.word_count
             + word_index
-            + 1
+            + 1,
+        )
     }
 
-    /// Translates a vector of the variables states back to a crossword grid.
-    pub fn back_to_domain(&self, model: &[i32]) -> String {
+    /// Translates a model back to a crossword grid.
+    pub fn back_to_domain(&self, model: &Model) -> String {
         let column_count = self.grid.column_count();
         let row_count = self.grid.row_count();
         let mut output_grid = String::with_capacity(row_count * (column_count + 1/* new line */));
         for row in 0..row_count {
             for column in 0..column_count {
                 for value in 0..CELL_VALUE_COUNT {
-                    let variable = self.representing_cell(row, column, value) - 1;
-                    if model[variable] > 0 {
+                    if model.is_true(self.representing_cell(row, column, value)) {
                         let character = match value {
                             BLOCK_INDEX => grid::BLOCK,
                             _ => alphabet::letter_at(value),
@@ -118,7 +125,7 @@ impl Variables {
     }
 
     /// Returns the number of variables representing cells.
-    fn representing_cell_count(&self) -> usize {
+    pub fn representing_cell_count(&self) -> usize {
         self.grid.column_count() * self.grid.row_count() * CELL_VALUE_COUNT
     }
 
@@ -142,15 +149,15 @@ mod test {
         let grid = Grid::try_from("...\n...\n...").unwrap();
         let variables = Variables::new(grid, 100_000 /* does not matter here */);
 
-        assert_eq!(1, variables.representing_cell(0, 0, 0));
-        assert_eq!(2, variables.representing_cell(0, 0, 1));
-        assert_eq!(27, variables.representing_cell(0, 0, 26));
+        assert_eq!(Var::new(1), variables.representing_cell(0, 0, 0));
+        assert_eq!(Var::new(2), variables.representing_cell(0, 0, 1));
+        assert_eq!(Var::new(27), variables.representing_cell(0, 0, 26));
 
-        assert_eq!(28, variables.representing_cell(0, 1, 0));
-        assert_eq!(29, variables.representing_cell(0, 1, 1));
-        assert_eq!(54, variables.representing_cell(0, 1, 26));
+        assert_eq!(Var::new(28), variables.representing_cell(0, 1, 0));
+        assert_eq!(Var::new(29), variables.representing_cell(0, 1, 1));
+        assert_eq!(Var::new(54), variables.representing_cell(0, 1, 26));
 
-        assert_eq!(243, variables.representing_cell(2, 2, 26))
+        assert_eq!(Var::new(243), variables.representing_cell(2, 2, 26))
     }
 
     #[test]
@@ -158,14 +165,14 @@ mod test {
         let grid = Grid::try_from("...\n...\n...").unwrap();
         let variables = Variables::new(grid, 100_000);
 
-        assert_eq!(244, variables.representing_slot(0, 0));
-        assert_eq!(245, variables.representing_slot(0, 1));
-        assert_eq!(100_243, variables.representing_slot(0, 99_999));
+        assert_eq!(Var::new(244), variables.representing_slot(0, 0));
+        assert_eq!(Var::new(245), variables.representing_slot(0, 1));
+        assert_eq!(Var::new(100_243), variables.representing_slot(0, 99_999));
 
-        assert_eq!(100_244, variables.representing_slot(1, 0));
-        assert_eq!(100_245, variables.representing_slot(1, 1));
+        assert_eq!(Var::new(100_244), variables.representing_slot(1, 0));
+        assert_eq!(Var::new(100_245), variables.representing_slot(1, 1));
 
-        assert_eq!(600_243, variables.representing_slot(5, 99_999));
+        assert_eq!(Var::new(600_243), variables.representing_slot(5, 99_999));
     }
 
     #[test]
@@ -223,6 +230,12 @@ mod test {
             }
         }
 
+        let model: Model = model
+            .iter()
+            .enumerate()
+            .map(|(index, &state)| Var::new(index + 1).literal(state > 0))
+            .collect();
+
         let solved_grid = variables.back_to_domain(&model);
 
         assert_eq!("AAA\nB#B\nCCC", solved_grid);
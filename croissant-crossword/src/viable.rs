@@ -0,0 +1,310 @@
+use std::collections::HashSet;
+
+use croissant_solver::{Lit, Model, SolveOutcome, Solver, SolverError};
+
+use crate::alphabet;
+use crate::grid::{self, Grid};
+use crate::pos::Pos;
+use crate::slot::Slot;
+use crate::variables::Variables;
+
+/// Returns an error if the given slot is not a slot of the given grid.
+pub fn validate(grid: &Grid, slot: &Slot) -> Result<(), String> {
+    if grid.slots().contains(slot) {
+        Ok(())
+    } else {
+        Err(format!("Unknown slot: {slot:?}"))
+    }
+}
+
+/// Returns the indices of the words which fit the given slot, i.e. which have the length of the slot and are
+/// compatible with its prefilled letters, by descending score. Words without score have a score of 0. Words with the
+/// same score keep their order in the word list.
+pub fn ranked_candidates(grid: &Grid, slot: &Slot, words: &[String], scores: &[i32]) -> Vec<usize> {
+    let positions = slot.positions();
+    let mut candidates: Vec<usize> = words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| fits(grid, &positions, word))
+        .map(|(word_index, _)| word_index)
+        .collect();
+    candidates.sort_by_key(|&word_index| -scores.get(word_index).copied().unwrap_or(0));
+    candidates
+}
+
+/// Returns `true` iff the given word fits the cells at the given positions.
+fn fits(grid: &Grid, positions: &[Pos], word: &str) -> bool {
+    word.len() == positions.len()
+        && positions.iter().zip(word.chars()).all(|(pos, letter)| {
+            let value = grid.letter_at(pos.row(), pos.column());
+            value == grid::EMPTY || value == letter
+        })
+}
+
+/// Returns the words fitting the given slot whose letters are compatible with the crossing slots, by descending
+/// score: For each cell of the slot, at least one word must fit the crossing slot with the same letter in this cell.
+///
+/// This is a cheap, solver-less, check: The crossing slots are checked independently of each other and of the rest of
+/// the grid. A returned word may thus have no full completion.
+pub fn check_crossings(grid: &Grid, slot: &Slot, words: &[String], scores: &[i32]) -> Vec<String> {
+    let slots = grid.slots();
+    let crossings: Vec<(usize, u32)> = slot
+        .positions()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, pos)| {
+            let crossing_slot = slots.iter().find(|other| {
+                other.is_down() != slot.is_down() && other.positions().contains(pos)
+            })?;
+            let index_in_crossing_slot = crossing_slot
+                .positions()
+                .iter()
+                .position(|other_pos| other_pos == pos)
+                .unwrap();
+            let supported_letters = ranked_candidates(grid, crossing_slot, words, &[])
+                .iter()
+                .map(|&word_index| words[word_index].as_bytes()[index_in_crossing_slot])
+                .fold(0, |letters, letter| letters | letter_bit(letter));
+            Some((index, supported_letters))
+        })
+        .collect();
+    ranked_candidates(grid, slot, words, scores)
+        .into_iter()
+        .map(|word_index| &words[word_index])
+        .filter(|word| {
+            let letters = word.as_bytes();
+            crossings.iter().all(|&(index, supported_letters)| {
+                supported_letters & letter_bit(letters[index]) != 0
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns the bit representing the given letter in a set of letters.
+fn letter_bit(letter: u8) -> u32 {
+    1 << alphabet::index_of(letter as char).unwrap()
+}
+
+/// An iterator over the words of a slot which have at least one full completion, by descending score.
+///
+/// Words are checked lazily, one by one, when calling [ViableWords::next], by solving the problem assuming the word
+/// in the slot. All checks are made on the same solver, which thus keeps its learnt clauses from a check to the other.
+/// Every model found also reveals a viable word for the slot, which is then not checked again. Iteration stops early if
+/// a check is interrupted, see [SolverConfigurator::set_limits](croissant_solver::SolverConfigurator::set_limits) and
+/// [ViableWords::is_interrupted], or if it fails, see [ViableWords::error].
+pub struct ViableWords<'wordlist> {
+    /// The words
+    words: &'wordlist [String],
+    /// The indices of the words fitting the slot, not checked yet
+    candidates: std::vec::IntoIter<usize>,
+    /// The positions of the cells of the slot
+    positions: Vec<Pos>,
+    /// The cell variables
+    variables: Variables,
+    /// The words found in the slot in the models found so far
+    known_viable_words: HashSet<String>,
+    /// The solver, containing the problem encoded with cell variables
+    solver: Box<dyn Solver<Item = Model>>,
+    /// Whether a check has been interrupted
+    interrupted: bool,
+    /// The error of the failed check, if any
+    error: Option<SolverError>,
+}
+
+impl<'wordlist> ViableWords<'wordlist> {
+    /// Creates a new iterator over the viable words of the given slot, using the given solver.
+    ///
+    /// The solver must contain the problem encoded with cell variables as defined by [Variables], and support solving
+    /// under assumptions. Returns an error if it does not, or if the initial search is interrupted.
+    pub fn new(
+        grid: &Grid,
+        slot: &Slot,
+        words: &'wordlist [String],
+        scores: &[i32],
+        solver: Box<dyn Solver<Item = Model>>,
+    ) -> Result<Self, SolverError> {
+        let mut viable_words = ViableWords {
+            words,
+            candidates: ranked_candidates(grid, slot, words, scores).into_iter(),
+            positions: slot.positions(),
+            variables: Variables::new(grid.clone(), 0),
+            known_viable_words: HashSet::new(),
+            solver,
+            interrupted: false,
+            error: None,
+        };
+        match viable_words.solver.solve_with_assumptions(&[]) {
+            SolveOutcome::Sat(model) => viable_words.record(&model),
+            // No fill at all, hence no viable word
+            SolveOutcome::Unsat => viable_words.candidates = Vec::new().into_iter(),
+            SolveOutcome::Interrupted => return Err(SolverError::new("Search interrupted")),
+            SolveOutcome::Unsupported => {
+                return Err(SolverError::new(
+                    "Solver does not support solving under assumptions",
+                ))
+            }
+            SolveOutcome::Failed(error) => return Err(error),
+        }
+        Ok(viable_words)
+    }
+
+    /// Returns `true` iff a check has been interrupted, i.e. if iteration stopped because a limit was reached rather
+    /// than because all the words have been checked. Words not returned so far may then still be viable.
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted
+    }
+
+    /// Returns the error of the solver if a check has failed, in which case iteration stopped. Words not returned so far
+    /// may then still be viable.
+    pub fn error(&self) -> Option<&SolverError> {
+        self.error.as_ref()
+    }
+
+    /// Records the word of the slot in the given model as viable.
+    fn record(&mut self, model: &Model) {
+        let word: String = self
+            .positions
+            .iter()
+            .filter_map(|pos| {
+                (0..alphabet::letter_count())
+                    .find(|&letter_index| {
+                        model.is_true(self.variables.representing_cell(
+                            pos.row(),
+                            pos.column(),
+                            letter_index,
+                        ))
+                    })
+                    .map(alphabet::letter_at)
+            })
+            .collect();
+        self.known_viable_words.insert(word);
+    }
+
+    /// Returns `true` iff the given word has at least one full completion.
+    fn is_viable(&mut self, word: &str) -> bool {
+        if self.known_viable_words.contains(word) {
+            return true;
+        }
+        let assumptions: Vec<Lit> = self
+            .positions
+            .iter()
+            .zip(word.chars())
+            .map(|(pos, letter)| {
+                let letter_index = alphabet::index_of(letter).unwrap();
+                self.variables
+                    .representing_cell(pos.row(), pos.column(), letter_index)
+                    .into()
+            })
+            .collect();
+        match self.solver.solve_with_assumptions(&assumptions) {
+            SolveOutcome::Sat(model) => {
+                self.record(&model);
+                true
+            }
+            SolveOutcome::Interrupted => {
+                // Stop iterating rather than miss viable words
+                self.candidates = Vec::new().into_iter();
+                self.interrupted = true;
+                false
+            }
+            SolveOutcome::Failed(error) => {
+                self.candidates = Vec::new().into_iter();
+                self.error = Some(error);
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Iterator for ViableWords<'_> {
+    type Item = String;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(word_index) = self.candidates.next() {
+            let word = &self.words[word_index];
+            if self.is_viable(word) {
+                return Some(word.clone());
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A solver finding an empty model without assumptions, and interrupted otherwise.
+    struct InterruptedSolver {}
+    impl Solver for InterruptedSolver {
+        fn solve_with_assumptions(&mut self, assumptions: &[Lit]) -> SolveOutcome {
+            if assumptions.is_empty() {
+                SolveOutcome::Sat(Model::new())
+            } else {
+                SolveOutcome::Interrupted
+            }
+        }
+    }
+    impl Iterator for InterruptedSolver {
+        type Item = Model;
+        fn next(&mut self) -> Option<Self::Item> {
+            None
+        }
+    }
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|&word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn ranked_candidates() {
+        let grid = Grid::try_from("A..\n...").unwrap();
+        let words = words(&["ABC", "BCD", "AB", "AAA", "ACE"]);
+        let scores = [1, 5, 9, 2];
+
+        let candidates = super::ranked_candidates(&grid, &Slot::across(0, 3, 0), &words, &scores);
+
+        assert_eq!(vec![3, 0, 4], candidates);
+    }
+
+    #[test]
+    fn check_crossings() {
+        let grid = Grid::try_from("...\n#..").unwrap();
+        let words = words(&["ABC", "ADE", "XYZ", "BA", "CB"]);
+
+        let viable_words = super::check_crossings(&grid, &Slot::across(0, 3, 0), &words, &[]);
+
+        assert_eq!(vec!["ABC".to_string()], viable_words);
+    }
+
+    #[test]
+    fn interrupted() {
+        let grid = Grid::try_from("...\n#..").unwrap();
+        let words = words(&["ABC", "ADE"]);
+
+        let mut viable_words = ViableWords::new(
+            &grid,
+            &Slot::across(0, 3, 0),
+            &words,
+            &[],
+            Box::new(InterruptedSolver {}),
+        )
+        .unwrap();
+
+        assert!(!viable_words.is_interrupted(), "No check yet");
+        assert_eq!(None, viable_words.next());
+        assert!(viable_words.is_interrupted(), "Check should be interrupted");
+    }
+
+    #[test]
+    fn validate() {
+        let grid = Grid::try_from("...\n#..").unwrap();
+
+        assert_eq!(Ok(()), super::validate(&grid, &Slot::down(0, 2, 1)));
+        assert_eq!(
+            Err("Unknown slot: Slot { start: 0, end: 2, offset: 0, is_down: true }".to_string()),
+            super::validate(&grid, &Slot::down(0, 2, 0))
+        );
+    }
+}
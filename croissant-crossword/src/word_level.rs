@@ -0,0 +1,359 @@
+use std::collections::HashSet;
+
+use croissant_solver::{Lit, Model, SolverConfigurator, Var};
+
+use crate::grid::Grid;
+use crate::slot::Slot;
+use crate::{alphabet, grid};
+
+/// Where translation of problem data from/to integer variables occurs, for the word-level encoding.
+///
+/// There is only one kind of variables: Slot variables. For each pair (slot,candidate) is associated a variable.
+/// Candidates of a slot are the words of the word list which have the length of the slot and which are compatible
+/// with the letters prefilled in the slot.
+pub struct WordVariables {
+    /// The crossword grid
+    grid: Grid,
+    /// The slots of the grid
+    slots: Vec<Slot>,
+    /// The candidate words, by slot
+    candidates: Vec<Vec<String>>,
+    /// The first variable of each slot
+    first_variables: Vec<usize>,
+}
+
+impl WordVariables {
+    /// Creates a new instance.
+    pub fn new(grid: Grid, words: &[String]) -> Self {
+        let slots = grid.slots();
+        let candidates: Vec<Vec<String>> = slots
+            .iter()
+            .map(|slot| Self::candidates_of(&grid, slot, words))
+            .collect();
+        let mut first_variables = Vec::with_capacity(slots.len());
+        let mut next_variable = 1; // variable must be strictly positive
+        for slot_candidates in &candidates {
+            first_variables.push(next_variable);
+            next_variable += slot_candidates.len();
+        }
+        WordVariables {
+            grid,
+            slots,
+            candidates,
+            first_variables,
+        }
+    }
+
+    /// Returns the distinct words of the given word list fitting the given slot of the given grid.
+    fn candidates_of(grid: &Grid, slot: &Slot, words: &[String]) -> Vec<String> {
+        let prefilled_letters: Vec<char> = slot
+            .positions()
+            .iter()
+            .map(|pos| grid.letter_at(pos.row(), pos.column()))
+            .collect();
+        let mut distinct_words = HashSet::new();
+        words
+            .iter()
+            .filter(|word| word.len() == slot.len())
+            .filter(|word| {
+                word.chars()
+                    .zip(&prefilled_letters)
+                    .all(|(letter, &prefilled)| prefilled == grid::EMPTY || prefilled == letter)
+            })
+            .filter(|word| distinct_words.insert(word.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the slots of the grid.
+    pub fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+
+    /// Returns the candidate words of the given slot.
+    pub fn candidates(&self, slot_index: usize) -> &[String] {
+        &self.candidates[slot_index]
+    }
+
+    /// Returns the variable associated to the given candidate at the given slot.
+    pub fn representing_slot(&self, slot_index: usize, candidate_index: usize) -> Var {
+        Var::new(self.first_variables[slot_index] + candidate_index)
+    }
+
+    /// Returns the number of variables.
+    pub fn count(&self) -> usize {
+        self.candidates.iter().map(Vec::len).sum()
+    }
+
+    /// Translates a model back to a crossword grid.
+    ///
+    /// Cells which belong to no slot keep their input value.
+    pub fn back_to_domain(&self, model: &Model) -> String {
+        let mut output_rows: Vec<Vec<char>> = (0..self.grid.row_count())
+            .map(|row| {
+                (0..self.grid.column_count())
+                    .map(|column| self.grid.letter_at(row, column))
+                    .collect()
+            })
+            .collect();
+        for (slot_index, slot) in self.slots.iter().enumerate() {
+            let chosen_word = self.candidates[slot_index]
+                .iter()
+                .enumerate()
+                .find(|&(candidate_index, _)| {
+                    model.is_true(self.representing_slot(slot_index, candidate_index))
+                })
+                .map(|(_, word)| word);
+            if let Some(word) = chosen_word {
+                for (pos, letter) in slot.positions().iter().zip(word.chars()) {
+                    output_rows[pos.row()][pos.column()] = letter;
+                }
+            }
+        }
+        output_rows
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Where the word-level crossword problem constraints are built.
+///
+/// The constraints are:
+///
+/// - Each slot must contain one and only one of its candidates. Prefilled cells are kept as is
+///   since candidates are compatible with them. See [add_one_word_per_slot_clauses_to].
+/// - Crossing slots must agree on the letter of their common cell: For each candidate of the
+///   first slot, the second slot must contain one of its candidates with the same letter at the
+///   crossing. Candidates of the second slot are grouped by letter, so that the disjunction for a
+///   given letter is computed only once per crossing. See [add_crossing_clauses_to].
+///
+/// [add_one_word_per_slot_clauses_to]: WordConstraints::add_one_word_per_slot_clauses_to
+/// [add_crossing_clauses_to]: WordConstraints::add_crossing_clauses_to
+pub struct WordConstraints<'variables> {
+    variables: &'variables WordVariables,
+}
+
+impl<'variables> WordConstraints<'variables> {
+    /// Constructs a new instance.
+    pub fn new(variables: &'variables WordVariables) -> Self {
+        WordConstraints { variables }
+    }
+
+    /// Adds the clauses ensuring that each slot must contain exactly one of its candidates to the
+    /// given solver.
+    pub fn add_one_word_per_slot_clauses_to(&self, solver: &mut dyn SolverConfigurator) {
+        let mut slot_literals_buffer = Vec::new();
+        for slot_index in 0..self.variables.slots().len() {
+            for candidate_index in 0..self.variables.candidates(slot_index).len() {
+                let slot_literal = self
+                    .variables
+                    .representing_slot(slot_index, candidate_index);
+                slot_literals_buffer.push(Lit::from(slot_literal));
+            }
+            solver.add_exactly_one(&slot_literals_buffer);
+            slot_literals_buffer.clear();
+        }
+    }
+
+    /// Adds the clauses ensuring that crossing slots have the same letter at their common cell to
+    /// the given solver.
+    pub fn add_crossing_clauses_to(&self, solver: &mut dyn SolverConfigurator) {
+        let mut clause_buffer = Vec::new();
+        for (first_slot, first_index, second_slot, second_index) in self.crossings() {
+            let second_literals_by_letter = self.literals_by_letter(second_slot, second_index);
+            for (candidate_index, word) in self.variables.candidates(first_slot).iter().enumerate()
+            {
+                let letter_index = Self::letter_index_at(word, first_index);
+                let first_literal = self
+                    .variables
+                    .representing_slot(first_slot, candidate_index);
+                clause_buffer.push(first_literal.literal(false));
+                clause_buffer.extend(&second_literals_by_letter[letter_index]);
+                solver.add_clause(&clause_buffer);
+                clause_buffer.clear();
+            }
+        }
+    }
+
+    /// Returns the crossings of the grid, as tuples (first slot index, index of the crossing cell
+    /// in the first slot, second slot index, index of the crossing cell in the second slot).
+    fn crossings(&self) -> Vec<(usize, usize, usize, usize)> {
+        let column_count = self.variables.grid.column_count();
+        let cell_count = self.variables.grid.row_count() * column_count;
+        let mut first_slot_at_cell: Vec<Option<(usize, usize)>> = vec![None; cell_count];
+        let mut crossings = Vec::new();
+        for (slot_index, slot) in self.variables.slots().iter().enumerate() {
+            for (index_in_slot, pos) in slot.positions().iter().enumerate() {
+                let cell = pos.row() * column_count + pos.column();
+                match first_slot_at_cell[cell] {
+                    None => first_slot_at_cell[cell] = Some((slot_index, index_in_slot)),
+                    Some((first_slot, first_index)) => {
+                        crossings.push((first_slot, first_index, slot_index, index_in_slot))
+                    }
+                }
+            }
+        }
+        crossings
+    }
+
+    /// Returns the literals of the candidates of the given slot, grouped by their letter at the
+    /// given index.
+    fn literals_by_letter(&self, slot_index: usize, index_in_slot: usize) -> Vec<Vec<Lit>> {
+        let mut literals_by_letter = vec![Vec::new(); alphabet::letter_count()];
+        for (candidate_index, word) in self.variables.candidates(slot_index).iter().enumerate() {
+            let letter_index = Self::letter_index_at(word, index_in_slot);
+            let literal = self
+                .variables
+                .representing_slot(slot_index, candidate_index);
+            literals_by_letter[letter_index].push(literal.into());
+        }
+        literals_by_letter
+    }
+
+    /// Returns the index in the alphabet of the letter of the given word at the given index.
+    ///
+    /// Panics if the letter is not in the [alphabet].
+    fn letter_index_at(word: &str, index: usize) -> usize {
+        let letter = word.as_bytes()[index] as char;
+        alphabet::index_of(letter).unwrap_or_else(|| panic!("Unsupported character {letter}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct TestSolverConfigurator {
+        clauses: Vec<Vec<i32>>,
+        exactly_one_clauses: Vec<Vec<i32>>,
+    }
+
+    impl TestSolverConfigurator {
+        fn new() -> Self {
+            TestSolverConfigurator {
+                clauses: vec![],
+                exactly_one_clauses: vec![],
+            }
+        }
+    }
+
+    impl SolverConfigurator for TestSolverConfigurator {
+        fn add_clause(&mut self, literals: &[Lit]) {
+            self.clauses.push(raw(literals))
+        }
+
+        fn add_exactly_one(&mut self, literals: &[Lit]) {
+            self.exactly_one_clauses.push(raw(literals))
+        }
+    }
+
+    /// Returns the raw representations of the given literals.
+    fn raw(literals: &[Lit]) -> Vec<i32> {
+        literals.iter().map(|&literal| i32::from(literal)).collect()
+    }
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|&word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn word_variables_candidates() {
+        let grid = Grid::try_from("A..\n#..").unwrap();
+        let words = words(&["ABC", "DEF", "AA", "BB", "ABC"]);
+
+        let variables = WordVariables::new(grid, &words);
+
+        assert_eq!(["ABC"], variables.candidates(0)); // prefilled 'A', no duplicate
+        assert_eq!(["AA", "BB"], variables.candidates(1));
+        assert_eq!(["AA", "BB"], variables.candidates(2));
+        assert_eq!(["AA", "BB"], variables.candidates(3));
+        assert_eq!(7, variables.count());
+    }
+
+    #[test]
+    fn word_variables_representing_slot() {
+        let grid = Grid::try_from("...\n#..").unwrap();
+        let words = words(&["ABC", "DEF", "AA", "BB"]);
+
+        let variables = WordVariables::new(grid, &words);
+
+        assert_eq!(Var::new(1), variables.representing_slot(0, 0));
+        assert_eq!(Var::new(2), variables.representing_slot(0, 1));
+        assert_eq!(Var::new(3), variables.representing_slot(1, 0));
+        assert_eq!(Var::new(5), variables.representing_slot(2, 0));
+        assert_eq!(Var::new(8), variables.representing_slot(3, 1));
+    }
+
+    #[test]
+    fn word_variables_back_to_domain() {
+        let grid = Grid::try_from("...\n#..\n#.#").unwrap();
+        let words = words(&["ABC", "DEF", "AA", "BB", "EBB", "FB"]);
+        let variables = WordVariables::new(grid, &words);
+
+        // Slots: across #0 (1: "ABC", 2: "DEF", 3: "EBB"), across #1 (4: "AA", 5: "BB", 6: "FB"),
+        // down #0 (7: "ABC", 8: "DEF", 9: "EBB"), down #1 (10: "AA", 11: "BB", 12: "FB")
+        let model: Model = [-1, 2, -3, -4, 5, -6, -7, -8, 9, -10, -11, 12]
+            .into_iter()
+            .map(Lit::from_raw)
+            .collect();
+
+        assert_eq!("DEF\n#BB\n#B#", variables.back_to_domain(&model));
+    }
+
+    #[test]
+    fn word_constraints_add_one_word_per_slot_clauses_to() {
+        let mut test_solver = TestSolverConfigurator::new();
+        let grid = Grid::try_from("...\n#..").unwrap();
+        let words = words(&["ABC", "DEF", "AA", "BB"]);
+        let variables = WordVariables::new(grid, &words);
+        let constraints = WordConstraints::new(&variables);
+
+        constraints.add_one_word_per_slot_clauses_to(&mut test_solver);
+
+        assert_eq!(
+            vec![vec![1, 2], vec![3, 4], vec![5, 6], vec![7, 8]],
+            test_solver.exactly_one_clauses
+        );
+        assert!(test_solver.clauses.is_empty(), "Unexpected clauses");
+    }
+
+    #[test]
+    fn word_constraints_add_crossing_clauses_to() {
+        let mut test_solver = TestSolverConfigurator::new();
+        let grid = Grid::try_from("..\n..").unwrap();
+        let words = words(&["AB", "BA", "BB"]);
+        let variables = WordVariables::new(grid, &words);
+        let constraints = WordConstraints::new(&variables);
+
+        constraints.add_crossing_clauses_to(&mut test_solver);
+
+        // Slots: across #0 (1,2,3), across #1 (4,5,6), down #0 (7,8,9), down #1 (10,11,12)
+        assert_eq!(
+            vec![
+                // across #0 and down #0 at (0,0)
+                vec![-1, 7],    // "AB" => "AB"
+                vec![-2, 8, 9], // "BA" => "BA" or "BB"
+                vec![-3, 8, 9], // "BB" => "BA" or "BB"
+                // across #1 and down #0 at (1,0)
+                vec![-4, 8],    // "AB" => "BA"
+                vec![-5, 7, 9], // "BA" => "AB" or "BB"
+                vec![-6, 7, 9], // "BB" => "AB" or "BB"
+                // across #0 and down #1 at (0,1)
+                vec![-1, 11, 12], // "AB" => "BA" or "BB"
+                vec![-2, 10],     // "BA" => "AB"
+                vec![-3, 11, 12], // "BB" => "BA" or "BB"
+                // across #1 and down #1 at (1,1)
+                vec![-4, 10, 12], // "AB" => "AB" or "BB"
+                vec![-5, 11],     // "BA" => "BA"
+                vec![-6, 10, 12], // "BB" => "AB" or "BB"
+            ],
+            test_solver.clauses
+        );
+        assert!(
+            test_solver.exactly_one_clauses.is_empty(),
+            "Unexpected clauses"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+/// The number of values a cell can take: The letters from 'A' to 'Z', then a block.
+pub const VALUE_COUNT: usize = 27;
+
+/// The value of a block.
+pub const BLOCK: u8 = 26;
+
+/// A set of word indices, one bit per word.
+pub type WordSet = Vec<u64>;
+
+/// The words of a given length, indexed by value at each position.
+pub struct WordIndex {
+    /// The words, as values, by order of preference.
+    words: Vec<Vec<u8>>,
+    /// For each position then each value, the set of the words having this value at this position.
+    having: Vec<Vec<WordSet>>,
+}
+
+impl WordIndex {
+    /// Creates the index of the given words having the given length, keeping their order. Words with other characters
+    /// than letters from 'A' to 'Z' are ignored, as well as repeated words.
+    pub fn new(words: &[String], length: usize) -> Self {
+        let mut seen = HashSet::new();
+        let words = words
+            .iter()
+            .filter(|word| word.len() == length && word.bytes().all(|b| b.is_ascii_uppercase()))
+            .filter(|word| seen.insert(word.as_str()))
+            .map(|word| word.bytes().map(|letter| letter - b'A').collect())
+            .collect();
+        Self::from_values(words, length)
+    }
+
+    /// Creates the index of the values of a single cell, i.e. the words made of one letter or of a block.
+    pub fn values() -> Self {
+        let words = (0..VALUE_COUNT as u8).map(|value| vec![value]).collect();
+        Self::from_values(words, 1)
+    }
+
+    /// Creates the index of the given words, given as values.
+    fn from_values(words: Vec<Vec<u8>>, length: usize) -> Self {
+        let mut having = vec![vec![empty(words.len()); VALUE_COUNT]; length];
+        for (index, word) in words.iter().enumerate() {
+            for (position, &value) in word.iter().enumerate() {
+                insert(&mut having[position][value as usize], index);
+            }
+        }
+        WordIndex { words, having }
+    }
+
+    /// Returns the word at the given index, as values.
+    pub fn word(&self, index: usize) -> &[u8] {
+        &self.words[index]
+    }
+
+    /// Returns the set of all the words.
+    pub fn all(&self) -> WordSet {
+        let mut all = empty(self.words.len());
+        for index in 0..self.words.len() {
+            insert(&mut all, index);
+        }
+        all
+    }
+
+    /// Returns the set of the words having the given value at the given position.
+    pub fn having(&self, position: usize, value: u8) -> &WordSet {
+        &self.having[position][value as usize]
+    }
+}
+
+/// Returns an empty set able to hold the given number of words.
+fn empty(word_count: usize) -> WordSet {
+    vec![0; word_count.div_ceil(64)]
+}
+
+/// Inserts the given word in the given set.
+fn insert(set: &mut WordSet, index: usize) {
+    set[index / 64] |= 1 << (index % 64);
+}
+
+/// Returns the number of words in the given set.
+pub fn count(set: &WordSet) -> usize {
+    set.iter().map(|bits| bits.count_ones() as usize).sum()
+}
+
+/// Returns the number of words in both given sets.
+pub fn count_both(set: &WordSet, other: &WordSet) -> usize {
+    set.iter()
+        .zip(other)
+        .map(|(bits, other_bits)| (bits & other_bits).count_ones() as usize)
+        .sum()
+}
+
+/// Returns the words in both given sets.
+pub fn intersection(set: &WordSet, other: &WordSet) -> WordSet {
+    set.iter()
+        .zip(other)
+        .map(|(bits, other_bits)| bits & other_bits)
+        .collect()
+}
+
+/// Returns the words of the given set, by increasing index.
+pub fn indices(set: &WordSet) -> impl Iterator<Item = usize> + '_ {
+    set.iter().enumerate().flat_map(|(block, &bits)| {
+        (0..64)
+            .filter(move |bit| bits & (1 << bit) != 0)
+            .map(move |bit| block * 64 + bit)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|&word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn new() {
+        let index = WordIndex::new(&words(&["ABC", "AB", "ABD", "ABC", "A-C", "BBD"]), 3);
+
+        assert_eq!(&[0, 1, 2], index.word(0));
+        assert_eq!(&[0, 1, 3], index.word(1));
+        assert_eq!(&[1, 1, 3], index.word(2));
+        assert_eq!(3, count(&index.all()));
+        assert_eq!(vec![1, 2], indices(index.having(2, 3)).collect::<Vec<_>>());
+        assert_eq!(vec![0, 1], indices(index.having(0, 0)).collect::<Vec<_>>());
+        assert_eq!(0, count(index.having(1, 0)));
+    }
+
+    #[test]
+    fn values() {
+        let index = WordIndex::values();
+
+        assert_eq!(VALUE_COUNT, count(&index.all()));
+        assert_eq!(&[BLOCK], index.word(BLOCK as usize));
+    }
+
+    #[test]
+    fn set_operations() {
+        let index = WordIndex::new(&words(&["AB", "AC", "BC"]), 2);
+
+        let first_a = index.having(0, 0);
+        let second_c = index.having(1, 2);
+        assert_eq!(1, count_both(first_a, second_c));
+        assert_eq!(
+            vec![1],
+            indices(&intersection(first_a, second_c)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn many_words() {
+        let many_words: Vec<String> = (0..100)
+            .map(|index| {
+                format!(
+                    "{}{}",
+                    (b'A' + index / 26) as char,
+                    (b'A' + index % 26) as char
+                )
+            })
+            .collect();
+
+        let index = WordIndex::new(&many_words, 2);
+
+        assert_eq!(100, count(&index.all()));
+        // 'B' is the first letter of the words #26 to #51
+        let first_b: Vec<usize> = indices(index.having(0, 1)).collect();
+        assert_eq!((26..52).collect::<Vec<_>>(), first_b);
+    }
+}
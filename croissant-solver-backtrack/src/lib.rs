@@ -0,0 +1,222 @@
+//! A native crossword solver, i.e. a solver working directly on the grid and the words rather than on clauses: A
+//! word-level backtracking filler, over a word list indexed by letter at each position.
+//!
+//! ## Example
+//!
+//! ```
+//! use croissant_crossword::crossword::Crossword;
+//! use croissant_solver_backtrack::BacktrackSolverBuilder;
+//!
+//! let words = ["AAA".to_string()];
+//! let crossword = Crossword::try_from("A..\n.#.\n...", &words).unwrap();
+//! let mut solutions = crossword.solve_natively_with(Box::new(BacktrackSolverBuilder::new())).unwrap();
+//! assert_eq!(Some("AAA\nA#A\nAAA".to_string()), solutions.next());
+//! ```
+
+mod index;
+mod search;
+
+use std::time::{Duration, Instant};
+
+use croissant_crossword::crossword::NativeSolverBuilder;
+use croissant_crossword::encoding::Decoder;
+use croissant_crossword::grid::Grid;
+use croissant_solver::{
+    CancellationToken, Lit, Model, ProgressCallback, SearchLimits, SolveOutcome, Solver,
+    SolverError, SolverStats, Var,
+};
+
+use crate::index::VALUE_COUNT;
+use crate::search::{Search, Step};
+
+/// The number of steps between two checks of the time budget, of the cancellation and of the progress.
+const STEPS_BETWEEN_CHECKS: u64 = 1024;
+
+/// The minimal duration between two progress reports.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Implementation of [NativeSolverBuilder], building a [BacktrackSolver].
+#[derive(Default)]
+pub struct BacktrackSolverBuilder;
+
+impl BacktrackSolverBuilder {
+    /// Creates an instance.
+    pub fn new() -> Self {
+        BacktrackSolverBuilder
+    }
+}
+
+impl NativeSolverBuilder for BacktrackSolverBuilder {
+    fn build(
+        self: Box<Self>,
+        grid: &Grid,
+        words: &[String],
+        decoder: &dyn Decoder,
+        limits: SearchLimits,
+        progress: Option<ProgressCallback>,
+    ) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+        Ok(Box::new(BacktrackSolver::new(
+            grid, words, decoder, limits, progress,
+        )))
+    }
+}
+
+/// Implementation of [Solver] filling the grid slot by slot, with the most constrained slot first, checking the
+/// crossing slots after each word and trying first the words leaving the most candidates to the crossing slots.
+///
+/// All the [SearchLimits] are supported, and checked regularly during the search: The conflict budget bounds the number
+/// of words rejected because they leave no candidate to a crossing slot. Progress is reported periodically during the
+/// search, at most once per second. Statistics count the conflicts, the words tried as decisions and the reductions of
+/// the candidates of the crossing slots as propagations; There is no clause.
+///
+/// Solving under assumptions is not supported.
+pub struct BacktrackSolver {
+    /// The search.
+    search: Search,
+    /// The cell literals, by cell then by value.
+    literals: Vec<[Lit; VALUE_COUNT]>,
+    /// The number of variables of the models.
+    variables_count: usize,
+    /// The limits of each search.
+    limits: SearchLimits,
+    /// The progress callback, if any.
+    progress: Option<ProgressCallback>,
+    /// The time spent searching so far.
+    solving_time: Duration,
+}
+
+impl BacktrackSolver {
+    /// Creates the solver of the given grid with the given words, by order of preference, giving models of the cell
+    /// literals of the given decoder.
+    pub fn new(
+        grid: &Grid,
+        words: &[String],
+        decoder: &dyn Decoder,
+        limits: SearchLimits,
+        progress: Option<ProgressCallback>,
+    ) -> Self {
+        let mut literals = Vec::with_capacity(grid.row_count() * grid.column_count());
+        for row in 0..grid.row_count() {
+            for column in 0..grid.column_count() {
+                literals.push(std::array::from_fn(|value| {
+                    decoder
+                        .cell_literal(row, column, value)
+                        .expect("Decoder should have cell literals")
+                }));
+            }
+        }
+        let variables_count = literals
+            .iter()
+            .flatten()
+            .map(|literal| literal.var().index())
+            .max()
+            .unwrap_or(0);
+        BacktrackSolver {
+            search: Search::new(grid, words),
+            literals,
+            variables_count,
+            limits,
+            progress,
+            solving_time: Duration::ZERO,
+        }
+    }
+
+    /// Returns the model of the current filling of the grid.
+    fn model(&self) -> Model {
+        let mut model: Model = (1..=self.variables_count)
+            .map(|variable| Var::new(variable).literal(false))
+            .collect();
+        for (cell, &value) in self.search.cells().iter().enumerate() {
+            model.set(self.literals[cell][value as usize]);
+        }
+        model
+    }
+
+    /// Returns `true` iff the search started at the given instant must stop because of its time budget or because it
+    /// is cancelled.
+    fn timed_out_or_cancelled(&self, started: Instant) -> bool {
+        let cancelled = self
+            .limits
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled);
+        let timed_out = self
+            .limits
+            .timeout
+            .is_some_and(|timeout| started.elapsed() >= timeout);
+        cancelled || timed_out
+    }
+
+    /// Returns `true` iff the search started with the given number of conflicts has exhausted its conflict budget.
+    fn out_of_conflicts(&self, conflicts_at_start: u64) -> bool {
+        let conflicts = self.search.counters().conflicts - conflicts_at_start;
+        self.limits
+            .conflicts
+            .is_some_and(|limit| conflicts >= limit)
+    }
+
+    /// Reports the progress, if a callback is set.
+    fn report_progress(&mut self, elapsed: Duration) {
+        let stats = SolverStats {
+            solving_time: self.solving_time + elapsed,
+            ..self.stats()
+        };
+        if let Some(progress) = self.progress.as_mut() {
+            progress(&stats);
+        }
+    }
+}
+
+impl Iterator for BacktrackSolver {
+    type Item = Model;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.solve_next() {
+            SolveOutcome::Sat(model) => Some(model),
+            _ => None,
+        }
+    }
+}
+
+impl Solver for BacktrackSolver {
+    fn solve_next(&mut self) -> SolveOutcome {
+        let started = Instant::now();
+        let conflicts_at_start = self.search.counters().conflicts;
+        let mut last_report = started;
+        let mut steps = 0u64;
+        let outcome = loop {
+            if steps.is_multiple_of(STEPS_BETWEEN_CHECKS) {
+                if self.timed_out_or_cancelled(started) {
+                    break SolveOutcome::Interrupted;
+                }
+                if last_report.elapsed() >= PROGRESS_INTERVAL {
+                    last_report = Instant::now();
+                    self.report_progress(started.elapsed());
+                }
+            }
+            steps += 1;
+            match self.search.step() {
+                Step::Solution => break SolveOutcome::Sat(self.model()),
+                Step::Exhausted => break SolveOutcome::Unsat,
+                Step::Pending if self.out_of_conflicts(conflicts_at_start) => {
+                    break SolveOutcome::Interrupted
+                }
+                Step::Pending => {}
+            }
+        };
+        self.solving_time += started.elapsed();
+        outcome
+    }
+
+    fn stats(&self) -> SolverStats {
+        let counters = self.search.counters();
+        SolverStats {
+            variables: self.variables_count,
+            conflicts: Some(counters.conflicts),
+            decisions: Some(counters.decisions),
+            propagations: Some(counters.propagations),
+            solving_time: self.solving_time,
+            ..SolverStats::default()
+        }
+    }
+}
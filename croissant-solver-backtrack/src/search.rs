@@ -0,0 +1,449 @@
+use std::collections::HashMap;
+use std::mem;
+
+use croissant_crossword::grid::{self, Grid};
+
+use crate::index::{self, WordIndex, WordSet, BLOCK};
+
+/// The value of an empty cell.
+const EMPTY: u8 = u8::MAX;
+
+/// A slot of the grid, or a single empty cell outside any slot, which can take any value.
+struct Slot {
+    /// The cells of the slot, as indices in the grid.
+    cells: Vec<usize>,
+    /// The crossings of the slot: The position in this slot, the crossing slot and the position in the crossing slot.
+    crossings: Vec<(usize, usize, usize)>,
+}
+
+/// A change of the search state, to undo when backtracking.
+enum Change {
+    /// The slot has been assigned.
+    Assigned(usize),
+    /// The cell has been filled.
+    Filled(usize),
+    /// The candidate words of the slot have been reduced from the given ones.
+    Reduced(usize, WordSet),
+}
+
+/// A decision point of the search: A slot and its candidate words, by order of preference.
+struct Frame {
+    /// The slot.
+    slot: usize,
+    /// The candidate words of the slot.
+    candidates: Vec<usize>,
+    /// The index of the next candidate to try.
+    next: usize,
+    /// The length of the trail before trying the candidates.
+    trail_length: usize,
+}
+
+/// The result of a step of the search.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Step {
+    /// The grid is filled.
+    Solution,
+    /// There is no solution left.
+    Exhausted,
+    /// The search continues.
+    Pending,
+}
+
+/// The counters of a search.
+#[derive(Clone, Debug, Default)]
+pub struct Counters {
+    /// The number of dead ends, i.e. of candidate words emptying the candidates of a crossing slot.
+    pub conflicts: u64,
+    /// The number of words tried.
+    pub decisions: u64,
+    /// The number of reductions of the candidates of a slot.
+    pub propagations: u64,
+}
+
+/// A resumable word-level backtracking search.
+///
+/// The next slot to fill is the unassigned one with the fewest candidate words (most-constrained slot first). When a
+/// word is tried, the candidates of the crossing slots are reduced to the words agreeing with it, and the word is
+/// rejected if one of them has no candidate left (forward checking). The candidates of a slot are tried by decreasing
+/// product of the numbers of candidates left in the crossing slots (letter-frequency lookahead), then by order of
+/// preference.
+pub struct Search {
+    /// The indices of the words, by slot length.
+    indices: HashMap<usize, WordIndex>,
+    /// The slots.
+    slots: Vec<Slot>,
+    /// The values of the cells.
+    cells: Vec<u8>,
+    /// Whether each slot is assigned.
+    assigned: Vec<bool>,
+    /// The candidate words of each slot.
+    candidates: Vec<WordSet>,
+    /// The changes made so far, to undo when backtracking.
+    trail: Vec<Change>,
+    /// The decision points.
+    frames: Vec<Frame>,
+    /// Whether the next step chooses a new slot, rather than the next candidate of the last chosen slot.
+    descending: bool,
+    /// The counters.
+    counters: Counters,
+}
+
+impl Search {
+    /// Creates the search of the fillings of the given grid with the given words, by order of preference.
+    pub fn new(grid: &Grid, words: &[String]) -> Self {
+        let column_count = grid.column_count();
+        let mut cells = Vec::with_capacity(grid.row_count() * column_count);
+        for row in 0..grid.row_count() {
+            for column in 0..column_count {
+                cells.push(match grid.letter_at(row, column) {
+                    grid::EMPTY => EMPTY,
+                    grid::BLOCK => BLOCK,
+                    letter => letter as u8 - b'A',
+                });
+            }
+        }
+
+        let mut slots: Vec<Slot> = grid
+            .slots()
+            .iter()
+            .map(|slot| Slot {
+                cells: slot
+                    .positions()
+                    .iter()
+                    .map(|pos| pos.row() * column_count + pos.column())
+                    .collect(),
+                crossings: Vec::new(),
+            })
+            .collect();
+        let mut slots_by_cell: Vec<Vec<(usize, usize)>> = vec![Vec::new(); cells.len()];
+        for (slot_index, slot) in slots.iter().enumerate() {
+            for (position, &cell) in slot.cells.iter().enumerate() {
+                slots_by_cell[cell].push((slot_index, position));
+            }
+        }
+        for (cell, cell_slots) in slots_by_cell.iter().enumerate() {
+            if cell_slots.is_empty() && cells[cell] == EMPTY {
+                slots.push(Slot {
+                    cells: vec![cell],
+                    crossings: Vec::new(),
+                });
+            }
+            for &(slot_index, position) in cell_slots {
+                for &(other_slot_index, other_position) in cell_slots {
+                    if other_slot_index != slot_index {
+                        let crossing = (position, other_slot_index, other_position);
+                        slots[slot_index].crossings.push(crossing);
+                    }
+                }
+            }
+        }
+
+        let mut indices = HashMap::new();
+        for slot in &slots {
+            let length = slot.cells.len();
+            indices.entry(length).or_insert_with(|| match length {
+                // Only single cells outside any slot have a length of 1
+                1 => WordIndex::values(),
+                _ => WordIndex::new(words, length),
+            });
+        }
+        let candidates = slots
+            .iter()
+            .map(|slot| {
+                let index = &indices[&slot.cells.len()];
+                let mut candidates = index.all();
+                for (position, &cell) in slot.cells.iter().enumerate() {
+                    if cells[cell] != EMPTY {
+                        candidates =
+                            index::intersection(&candidates, index.having(position, cells[cell]));
+                    }
+                }
+                candidates
+            })
+            .collect();
+
+        Search {
+            indices,
+            assigned: vec![false; slots.len()],
+            slots,
+            cells,
+            candidates,
+            trail: Vec::new(),
+            frames: Vec::new(),
+            descending: true,
+            counters: Counters::default(),
+        }
+    }
+
+    /// Returns the values of the cells: The index of the letter in the alphabet, or [BLOCK].
+    pub fn cells(&self) -> &[u8] {
+        &self.cells
+    }
+
+    /// Returns the counters of the search so far.
+    pub fn counters(&self) -> &Counters {
+        &self.counters
+    }
+
+    /// Makes a step of the search: Either chooses the next slot to fill, or tries the next candidate word of the last
+    /// chosen slot. After a [Step::Solution], the next steps search the next solution.
+    pub fn step(&mut self) -> Step {
+        if self.descending {
+            self.descending = false;
+            let Some(slot) = self.most_constrained_slot() else {
+                return Step::Solution;
+            };
+            let candidates = self.ordered_candidates(slot);
+            self.frames.push(Frame {
+                slot,
+                candidates,
+                next: 0,
+                trail_length: self.trail.len(),
+            });
+        }
+        let Some(frame) = self.frames.last_mut() else {
+            return Step::Exhausted;
+        };
+        let (slot, trail_length) = (frame.slot, frame.trail_length);
+        let word = frame.candidates.get(frame.next).copied();
+        frame.next += 1;
+        self.undo(trail_length);
+        match word {
+            None => {
+                self.frames.pop();
+            }
+            Some(word) => {
+                if self.assign(slot, word) {
+                    self.descending = true;
+                } else {
+                    self.counters.conflicts += 1;
+                }
+            }
+        }
+        Step::Pending
+    }
+
+    /// Returns the unassigned slot with the fewest candidates, preferring the most crossed one in case of tie, or
+    /// [None] if all slots are assigned.
+    fn most_constrained_slot(&self) -> Option<usize> {
+        (0..self.slots.len())
+            .filter(|&slot| !self.assigned[slot])
+            .min_by_key(|&slot| {
+                let crossing_count = self.slots[slot]
+                    .crossings
+                    .iter()
+                    .filter(|&&(_, other_slot, _)| !self.assigned[other_slot])
+                    .count();
+                (
+                    index::count(&self.candidates[slot]),
+                    usize::MAX - crossing_count,
+                )
+            })
+    }
+
+    /// Returns the candidate words of the given slot, by decreasing product of the numbers of candidates left in the
+    /// crossing slots, then by order of preference. Words leaving no candidate to a crossing slot are omitted, and
+    /// counted as conflicts.
+    fn ordered_candidates(&mut self, slot: usize) -> Vec<usize> {
+        let index = &self.indices[&self.slots[slot].cells.len()];
+        // For each open crossing, the number of candidates of the crossing slot left by each value
+        let mut open_crossings = Vec::new();
+        for &(position, other_slot, other_position) in &self.slots[slot].crossings {
+            if self.assigned[other_slot] || self.cells[self.slots[slot].cells[position]] != EMPTY {
+                continue;
+            }
+            let other_index = &self.indices[&self.slots[other_slot].cells.len()];
+            let counts: Vec<usize> = (0..BLOCK)
+                .map(|value| {
+                    index::count_both(
+                        &self.candidates[other_slot],
+                        other_index.having(other_position, value),
+                    )
+                })
+                .collect();
+            open_crossings.push((position, counts));
+        }
+        let mut rejected_count = 0;
+        let mut scored_candidates: Vec<(f64, usize)> = index::indices(&self.candidates[slot])
+            .filter_map(|word| {
+                let values = index.word(word);
+                let mut score = 0.0;
+                for (position, counts) in &open_crossings {
+                    let count = *counts.get(values[*position] as usize)?;
+                    if count == 0 {
+                        rejected_count += 1;
+                        return None;
+                    }
+                    score += (count as f64).ln();
+                }
+                Some((score, word))
+            })
+            .collect();
+        scored_candidates.sort_by(|(score, word), (other_score, other_word)| {
+            other_score.total_cmp(score).then(word.cmp(other_word))
+        });
+        self.counters.conflicts += rejected_count;
+        scored_candidates
+            .into_iter()
+            .map(|(_, word)| word)
+            .collect()
+    }
+
+    /// Assigns the given word to the given slot and reduces the candidates of the crossing slots accordingly. Returns
+    /// `false` if a crossing slot has no candidate left.
+    fn assign(&mut self, slot: usize, word: usize) -> bool {
+        self.counters.decisions += 1;
+        self.assigned[slot] = true;
+        self.trail.push(Change::Assigned(slot));
+        let values = self.indices[&self.slots[slot].cells.len()]
+            .word(word)
+            .to_vec();
+        for (position, &value) in values.iter().enumerate() {
+            let cell = self.slots[slot].cells[position];
+            if self.cells[cell] != EMPTY {
+                continue;
+            }
+            self.cells[cell] = value;
+            self.trail.push(Change::Filled(cell));
+            for crossing in 0..self.slots[slot].crossings.len() {
+                let (crossing_position, other_slot, other_position) =
+                    self.slots[slot].crossings[crossing];
+                if crossing_position != position || self.assigned[other_slot] {
+                    continue;
+                }
+                let other_index = &self.indices[&self.slots[other_slot].cells.len()];
+                let reduced = index::intersection(
+                    &self.candidates[other_slot],
+                    other_index.having(other_position, value),
+                );
+                self.counters.propagations += 1;
+                let empty = index::count(&reduced) == 0;
+                let previous = mem::replace(&mut self.candidates[other_slot], reduced);
+                self.trail.push(Change::Reduced(other_slot, previous));
+                if empty {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Undoes the changes until the trail has the given length.
+    fn undo(&mut self, trail_length: usize) {
+        while self.trail.len() > trail_length {
+            match self.trail.pop().unwrap() {
+                Change::Assigned(slot) => self.assigned[slot] = false,
+                Change::Filled(cell) => self.cells[cell] = EMPTY,
+                Change::Reduced(slot, candidates) => self.candidates[slot] = candidates,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|&word| word.to_string()).collect()
+    }
+
+    /// Returns all the fillings found by the given search, as strings of letters.
+    fn solutions(mut search: Search) -> Vec<String> {
+        let mut solutions = Vec::new();
+        loop {
+            match search.step() {
+                Step::Solution => solutions.push(
+                    search
+                        .cells()
+                        .iter()
+                        .map(|&value| {
+                            if value == BLOCK {
+                                '#'
+                            } else {
+                                (b'A' + value) as char
+                            }
+                        })
+                        .collect(),
+                ),
+                Step::Exhausted => return solutions,
+                Step::Pending => {}
+            }
+        }
+    }
+
+    #[test]
+    fn crossings() {
+        let grid = Grid::try_from("..\n.#").unwrap();
+
+        let search = Search::new(&grid, &words(&["AB"]));
+
+        assert_eq!(2, search.slots.len());
+        assert_eq!(vec![(0, 1, 0)], search.slots[0].crossings);
+        assert_eq!(vec![(0, 0, 0)], search.slots[1].crossings);
+    }
+
+    #[test]
+    fn prefilled_cells_reduce_candidates() {
+        let grid = Grid::try_from("A.").unwrap();
+
+        let search = Search::new(&grid, &words(&["AB", "BA", "AC"]));
+
+        assert_eq!(2, index::count(&search.candidates[0]));
+    }
+
+    #[test]
+    fn all_solutions() {
+        let grid = Grid::try_from("...\n...\n...").unwrap();
+        let search = Search::new(&grid, &words(&["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"]));
+
+        let mut solutions = solutions(search);
+
+        solutions.sort();
+        assert_eq!(
+            vec!["AAAAAAAAA", "AAABBBCDE", "ABCABDABE", "BBBBBBBBB"],
+            solutions
+        );
+    }
+
+    #[test]
+    fn no_solution() {
+        let grid = Grid::try_from("AB.\n...").unwrap();
+        let search = Search::new(&grid, &words(&["ABC", "AA", "BB", "CD", "XYZ"]));
+
+        assert_eq!(Vec::<String>::new(), solutions(search));
+    }
+
+    #[test]
+    fn isolated_cell_takes_any_value() {
+        let grid = Grid::try_from("AB#.").unwrap();
+
+        let search = Search::new(&grid, &words(&["AB"]));
+
+        assert_eq!(27, solutions(search).len());
+    }
+
+    #[test]
+    fn most_constrained_slot_first() {
+        let grid = Grid::try_from("...\n#.#").unwrap();
+        let search = Search::new(&grid, &words(&["ABC", "ABD", "AAA", "BA"]));
+
+        // The down slot has a single candidate, the across one has three
+        assert_eq!(Some(1), search.most_constrained_slot());
+    }
+
+    #[test]
+    fn lookahead_prefers_words_leaving_more_candidates() {
+        let grid = Grid::try_from("..\n.#").unwrap();
+        let mut search = Search::new(&grid, &words(&["AX", "BX", "BY", "BZ"]));
+
+        // Across slot: "BX" leaves 3 candidates to the down slot, "AX" only 1
+        let candidates: Vec<&[u8]> = search
+            .ordered_candidates(0)
+            .into_iter()
+            .map(|word| search.indices[&2].word(word))
+            .collect();
+
+        assert_eq!(vec![&[1, 23][..], &[1, 24], &[1, 25], &[0, 23]], candidates);
+    }
+}
@@ -0,0 +1,61 @@
+use croissant_crossword::crossword::{Crossword, CrosswordSolutions};
+use croissant_solver::{CancellationToken, SearchLimits};
+use croissant_solver_backtrack::BacktrackSolverBuilder;
+use std::time::Duration;
+
+/// The words of a 3x3 grid starting with "ABC" and having no solution, which needs a dead end to prove.
+const WORDS: [&str; 6] = ["AAA", "BBB", "CDF", "ABC", "ABD", "ABE"];
+
+#[test]
+fn conflicts() {
+    let mut solutions = solve(SearchLimits {
+        conflicts: Some(1),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should be interrupted");
+    assert_eq!(Some(1), solutions.stats().conflicts);
+}
+
+#[test]
+fn no_limit() {
+    let mut solutions = solve(SearchLimits::default());
+
+    assert_eq!(None, solutions.next());
+    assert!(!solutions.is_interrupted(), "Search should be complete");
+}
+
+#[test]
+fn timeout() {
+    let mut solutions = solve(SearchLimits {
+        timeout: Some(Duration::ZERO),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should be interrupted");
+}
+
+#[test]
+fn cancellation() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let mut solutions = solve(SearchLimits {
+        cancellation: Some(token),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should be interrupted");
+}
+
+/// Solves the grid with the given limits using the backtracking solver.
+fn solve(limits: SearchLimits) -> CrosswordSolutions {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    Crossword::try_from("ABC\n...\n...", &words)
+        .unwrap()
+        .with_limits(limits)
+        .solve_natively_with(Box::new(BacktrackSolverBuilder::new()))
+        .unwrap()
+}
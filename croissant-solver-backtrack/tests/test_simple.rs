@@ -0,0 +1,9 @@
+use croissant_crossword::crossword::NativeSolverBuilder;
+use croissant_solver_backtrack::BacktrackSolverBuilder;
+
+croissant_testkit::native_simple_tests!(builder);
+
+/// Returns the builder of backtracking solvers.
+fn builder() -> Box<dyn NativeSolverBuilder> {
+    Box::new(BacktrackSolverBuilder::new())
+}
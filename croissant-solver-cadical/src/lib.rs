@@ -1,15 +1,100 @@
-use croissant_solver::{ConfigurableSolver, Solver, SolverConfigurator};
+use cadical::Callbacks;
+use croissant_solver::{
+    CancellationToken, ConfigurableSolver, Lit, Model, ProgressCallback, SearchLimits,
+    SolveOutcome, Solver, SolverConfigurator, SolverStats, Var,
+};
+use std::time::{Duration, Instant};
+
+/// The minimal duration between two progress reports.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The [Callbacks] stopping a search when its time budget is exhausted or when it is cancelled, and reporting its
+/// progress.
+#[derive(Default)]
+struct SearchCallbacks {
+    /// The maximal duration of a search, if any.
+    timeout: Option<Duration>,
+    /// The cancellation token, if any.
+    cancellation: Option<CancellationToken>,
+    /// The progress callback, if any.
+    progress: Option<ProgressCallback>,
+    /// The statistics of the solver when the current search started.
+    stats: SolverStats,
+    /// The instant the current search started.
+    started: Option<Instant>,
+    /// The instant of the last progress report.
+    last_report: Option<Instant>,
+}
+
+impl SearchCallbacks {
+    /// Reports the progress, if a callback is set and if the last report is old enough.
+    fn report_progress(&mut self, now: Instant) {
+        let Some(progress) = self.progress.as_mut() else {
+            return;
+        };
+        if self
+            .last_report
+            .is_some_and(|last_report| now - last_report < PROGRESS_INTERVAL)
+        {
+            return;
+        }
+        self.last_report = Some(now);
+        let elapsed = self.started.map_or(Duration::ZERO, |started| now - started);
+        let stats = SolverStats {
+            solving_time: self.stats.solving_time + elapsed,
+            ..self.stats.clone()
+        };
+        progress(&stats);
+    }
+}
+
+impl Callbacks for SearchCallbacks {
+    fn started(&mut self) {
+        let now = Instant::now();
+        self.started = Some(now);
+        self.last_report = Some(now);
+    }
+
+    fn terminate(&mut self) -> bool {
+        let now = Instant::now();
+        self.report_progress(now);
+        let cancelled = self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled);
+        let timed_out = self
+            .timeout
+            .zip(self.started)
+            .is_some_and(|(timeout, started)| now - started >= timeout);
+        cancelled || timed_out
+    }
+}
 
 /// Implementation of [ConfigurableSolver].
+///
+/// All the [SearchLimits] are supported: The time budget and the cancellation token are checked regularly during the
+/// search, and the conflict budget is enforced by CaDiCaL itself. Progress is reported periodically during the search,
+/// at most once per second.
+///
+/// Statistics are limited to what CaDiCaL's C API exposes: The numbers of variables and of active clauses, and the
+/// solving time. Counters are unknown.
 pub struct CadicalSolver {
     /// The actual solver.
-    solver: cadical::Solver,
+    solver: cadical::Solver<SearchCallbacks>,
+    /// The time spent searching so far.
+    solving_time: Duration,
+    /// The maximal number of conflicts of a search, if any.
+    conflicts_limit: Option<u64>,
+    /// The biggest variable allocated so far, see [SolverConfigurator::allocate_variables].
+    allocated_variables: usize,
     /// The problem's relevant variables.
-    relevant_variables: Vec<usize>,
-    /// The last solution found, if any, or an empty vector.
-    last_solution: Vec<i32>,
+    relevant_variables: Vec<Var>,
+    /// The last solution found, if any, or an empty model.
+    last_solution: Model,
     /// Whether there is no solution left.
     no_more_solution: bool,
+    /// The assumptions responsible for the last unsatisfiable result under assumptions, if any.
+    failed_assumptions: Option<Vec<Lit>>,
 }
 
 impl Default for CadicalSolver {
@@ -24,73 +109,172 @@ impl CadicalSolver {
         let solver = cadical::Solver::default();
         CadicalSolver {
             solver,
+            solving_time: Duration::ZERO,
+            conflicts_limit: None,
+            allocated_variables: 0,
             relevant_variables: Vec::new(),
-            last_solution: Vec::new(),
+            last_solution: Model::new(),
             no_more_solution: false,
+            failed_assumptions: None,
         }
     }
 
     /// Refutes the last solution found, if any. Otherwise, does nothing.
     fn refute_last_solution(&mut self) {
-        if self.last_solution.is_empty() {
+        if self.last_solution.literals().next().is_none() {
             return;
         }
-        let not_last_solution: Vec<i32> = self
+        let not_last_solution = self
             .last_solution
-            .iter()
-            .enumerate()
-            .map(|(variable, value)| -value.signum() * ((variable + 1) as i32))
-            .collect();
+            .literals()
+            .map(|literal| i32::from(-literal));
         self.solver.add_clause(not_last_solution);
     }
 
+    /// Returns the callbacks of the solver, installing them first if needed.
+    fn callbacks(&mut self) -> &mut SearchCallbacks {
+        if self.solver.get_callbacks().is_none() {
+            self.solver.set_callbacks(Some(SearchCallbacks::default()));
+        }
+        self.solver.get_callbacks().unwrap()
+    }
+
+    /// Searches a model under the given assumptions, applying the conflict budget and measuring the solving time.
+    fn search(&mut self, assumptions: &[Lit]) -> Option<bool> {
+        if let Some(conflicts_limit) = self.conflicts_limit {
+            // CaDiCaL resets its limits after each search
+            let conflicts_limit = conflicts_limit.min(i32::MAX as u64) as i32;
+            self.solver
+                .set_limit("conflicts", conflicts_limit)
+                .expect("CaDiCaL should support conflict limit");
+        }
+        let stats = self.stats();
+        if let Some(callbacks) = self.solver.get_callbacks() {
+            callbacks.stats = stats;
+        }
+        let started = Instant::now();
+        let result = self
+            .solver
+            .solve_with(assumptions.iter().map(|&assumption| i32::from(assumption)));
+        self.solving_time += started.elapsed();
+        result
+    }
+
     /// Returns the relevant variable with biggest id.
     fn max_relevant_variable(&self) -> usize {
-        *self.relevant_variables.iter().max().unwrap_or(&0)
+        self.relevant_variables
+            .iter()
+            .max()
+            .map_or(0, |variable| variable.index())
     }
 
     /// Returns the model, i.e. the state of the variables in the solver.
-    fn model(&mut self) -> Vec<i32> {
-        let variables_count = self.max_relevant_variable();
-        let mut model = Vec::with_capacity(variables_count);
-        for variable in 1..(variables_count + 1) {
-            let variable_state = self
-                .solver
-                .value(variable as i32)
-                .map(|pos| if pos { 1 } else { -1 })
-                .unwrap_or_default();
-            model.push(variable_state);
+    fn model(&mut self) -> Model {
+        let mut model = Model::new();
+        for variable in 1..(self.max_relevant_variable() + 1) {
+            let variable = Var::new(variable);
+            if let Some(value) = self.solver.value(i32::from(variable)) {
+                model.set(variable.literal(value));
+            }
         }
         model
     }
 }
 
 impl SolverConfigurator for CadicalSolver {
-    fn set_relevant_variables(&mut self, relevant_variables: Vec<usize>) {
+    fn allocate_variables(&mut self, variables_count: usize) {
+        self.allocated_variables = self.allocated_variables.max(variables_count);
+    }
+    fn variables_count(&self) -> Option<usize> {
+        let max_variable = self.solver.max_variable() as usize;
+        Some(self.allocated_variables.max(max_variable))
+    }
+    fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
         self.relevant_variables = relevant_variables;
     }
-    fn add_clause(&mut self, literals: &[i32]) {
-        self.solver.add_clause(literals.to_vec());
+    fn set_limits(&mut self, limits: SearchLimits) {
+        self.conflicts_limit = limits.conflicts;
+        let no_callback_needed = limits.timeout.is_none() && limits.cancellation.is_none();
+        if no_callback_needed && self.solver.get_callbacks().is_none() {
+            return;
+        }
+        let callbacks = self.callbacks();
+        callbacks.timeout = limits.timeout;
+        callbacks.cancellation = limits.cancellation;
+    }
+    fn supports_limits(&self, _limits: &SearchLimits) -> bool {
+        true
+    }
+    fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.callbacks().progress = Some(callback);
+    }
+    fn add_clause(&mut self, literals: &[Lit]) {
+        self.solver
+            .add_clause(literals.iter().map(|&literal| i32::from(literal)));
     }
 }
 
 impl Iterator for CadicalSolver {
-    type Item = Vec<i32>;
+    type Item = Model;
 
     fn next(&mut self) -> Option<Self::Item> {
+        match self.solve_next() {
+            SolveOutcome::Sat(model) => Some(model),
+            _ => None,
+        }
+    }
+}
+
+impl Solver for CadicalSolver {
+    fn solve_next(&mut self) -> SolveOutcome {
         if self.no_more_solution {
-            return None;
+            return SolveOutcome::Unsat;
         }
         self.refute_last_solution();
-        if self.solver.solve() != Some(true) {
-            self.no_more_solution = true;
-            return None;
+        match self.search(&[]) {
+            Some(true) => {
+                let model = self.model();
+                self.last_solution.clone_from(&model);
+                SolveOutcome::Sat(model)
+            }
+            Some(false) => {
+                self.no_more_solution = true;
+                SolveOutcome::Unsat
+            }
+            None => SolveOutcome::Interrupted,
+        }
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[Lit]) -> SolveOutcome {
+        self.failed_assumptions = None;
+        match self.search(assumptions) {
+            Some(true) => return SolveOutcome::Sat(self.model()),
+            Some(false) => {}
+            None => return SolveOutcome::Interrupted,
+        }
+        let failed_assumptions = assumptions
+            .iter()
+            .copied()
+            .filter(|&assumption| self.solver.failed(i32::from(assumption)))
+            .collect();
+        self.failed_assumptions = Some(failed_assumptions);
+        SolveOutcome::Unsat
+    }
+
+    fn failed_assumptions(&self) -> Option<Vec<Lit>> {
+        self.failed_assumptions.clone()
+    }
+    fn as_configurator(&mut self) -> Option<&mut dyn SolverConfigurator> {
+        Some(self)
+    }
+    fn stats(&self) -> SolverStats {
+        SolverStats {
+            variables: self.solver.max_variable() as usize,
+            clauses: self.solver.num_clauses(),
+            solving_time: self.solving_time,
+            ..SolverStats::default()
         }
-        let model = self.model();
-        self.last_solution.clone_from(&model);
-        Some(model)
     }
 }
 
-impl Solver for CadicalSolver {}
 impl ConfigurableSolver for CadicalSolver {}
@@ -0,0 +1,92 @@
+use croissant_crossword::crossword::{Crossword, Diversity};
+use croissant_crossword::encoding::{DirectEncoding, Encoding, WordLevelEncoding};
+use croissant_solver::{AtMostOneEncoding, Lit, SolverConfigurator, Var};
+use croissant_solver_cadical::CadicalSolver;
+
+const ENCODINGS: [AtMostOneEncoding; 5] = [
+    AtMostOneEncoding::Pairwise,
+    AtMostOneEncoding::Sequential,
+    AtMostOneEncoding::Commander,
+    AtMostOneEncoding::Product,
+    AtMostOneEncoding::Bimander,
+];
+
+#[test]
+fn exactly_one_with_each_encoding() {
+    for encoding in ENCODINGS {
+        let mut solver = CadicalSolver::new();
+        solver.allocate_variables(50);
+        solver.set_relevant_variables((1..=50).map(Var::new).collect());
+        let literals: Vec<Lit> = (1..=50).map(|index| Var::new(index).into()).collect();
+
+        solver.add_exactly_one_with(&literals, encoding);
+
+        assert_eq!(50, solver.count(), "{encoding:?}");
+    }
+}
+
+#[test]
+fn at_most_one_with_auxiliary_variables() {
+    let mut solver = CadicalSolver::new();
+    solver.allocate_variables(40);
+    solver.set_relevant_variables((1..=40).map(Var::new).collect());
+    let literals: Vec<Lit> = (1..=40).map(|index| Var::new(index).into()).collect();
+
+    solver.add_at_most_one(&literals);
+
+    assert!(
+        solver.variables_count() > Some(40),
+        "Auxiliary variables expected"
+    );
+    assert_eq!(41, solver.count());
+}
+
+#[test]
+fn many_candidates() {
+    let words = words(40);
+    for encoding in encodings() {
+        let crossword = Crossword::try_from("...", &words)
+            .unwrap()
+            .with_encoding(encoding);
+
+        let solutions = crossword
+            .solve_with(Box::new(CadicalSolver::new()))
+            .unwrap();
+
+        assert_eq!(40, solutions.count());
+    }
+}
+
+#[test]
+fn many_candidates_with_diversity() {
+    let words = words(40);
+    let crossword = Crossword::try_from("...", &words)
+        .unwrap()
+        .with_encoding(Box::new(DirectEncoding));
+
+    let solutions = crossword
+        .solve_with(Box::new(CadicalSolver::new()))
+        .unwrap()
+        .with_diversity(Diversity::Cells(2))
+        .unwrap();
+
+    // Words differing by two letters at least: One per first letter
+    assert_eq!(2, solutions.count());
+}
+
+/// Returns the encodings with an *at-most-one* constraint over the candidates of each slot.
+fn encodings() -> Vec<Box<dyn Encoding>> {
+    vec![Box::new(DirectEncoding), Box::new(WordLevelEncoding)]
+}
+
+/// Returns the given number of three-letter words ending with an A, i.e. more candidates than the pairwise encoding
+/// is used for.
+fn words(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|index| {
+            let first = (b'A' + (index / 26) as u8) as char;
+            let second = (b'A' + (index % 26) as u8) as char;
+            format!("{first}{second}A")
+        })
+        .collect()
+}
@@ -0,0 +1,65 @@
+use croissant_solver::{Lit, SolverConfigurator, Var};
+use croissant_solver_cadical::CadicalSolver;
+
+#[test]
+fn at_most_k() {
+    for k in 0..=6 {
+        let mut solver = solver();
+        solver.add_at_most_k(&literals(&[1, 2, 3, 4, 5]), k);
+
+        assert_eq!(expected_count(|sum| sum <= k as i64), count(solver), "{k}");
+    }
+}
+
+#[test]
+fn at_least_k() {
+    for k in 0..=6 {
+        let mut solver = solver();
+        solver.add_at_least_k(&literals(&[1, 2, 3, 4, 5]), k);
+
+        assert_eq!(expected_count(|sum| sum >= k as i64), count(solver), "{k}");
+    }
+}
+
+#[test]
+fn pb_le() {
+    for bound in -4..=10 {
+        let mut solver = solver();
+        solver.add_pb_le(&literals(&[1, -2, 3, 4, 5]), &[3, -2, 5, 1, 1], bound);
+
+        let expected = (0..32_u32)
+            .filter(|assignment| {
+                let value = |variable: u32| (assignment >> (variable - 1) & 1) as i64;
+                3 * value(1) - 2 * (1 - value(2)) + 5 * value(3) + value(4) + value(5) <= bound
+            })
+            .count();
+        assert_eq!(expected, count(solver), "{bound}");
+    }
+}
+
+/// Returns a solver with five relevant variables.
+fn solver() -> CadicalSolver {
+    let mut solver = CadicalSolver::new();
+    solver.allocate_variables(5);
+    solver.set_relevant_variables((1..=5).map(Var::new).collect());
+    solver
+}
+
+/// Returns the number of assignments of the five variables whose count of true variables satisfies the given predicate.
+fn expected_count(predicate: impl Fn(i64) -> bool) -> usize {
+    (0..32_u32)
+        .filter(|assignment| predicate(assignment.count_ones() as i64))
+        .count()
+}
+
+fn count(solver: CadicalSolver) -> usize {
+    solver.count()
+}
+
+/// Returns the literals of the given raw representations.
+fn literals(literals: &[i32]) -> Vec<Lit> {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
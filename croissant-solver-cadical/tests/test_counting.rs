@@ -0,0 +1,9 @@
+use croissant_solver::SolverFactory;
+use croissant_solver_cadical::CadicalSolver;
+
+croissant_testkit::counting_tests!(factory);
+
+/// Returns the factory of CaDiCaL solvers.
+fn factory() -> SolverFactory {
+    SolverFactory::Configurable(Box::new(|| Box::new(CadicalSolver::new())))
+}
@@ -0,0 +1,80 @@
+use croissant_crossword::crossword::{Crossword, CrosswordSolutions, Diversity};
+use croissant_crossword::encoding::{Encoding, MddEncoding, WordLevelEncoding};
+use croissant_solver_cadical::CadicalSolver;
+
+const WORDS: [&str; 6] = ["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"];
+
+#[test]
+fn cells() {
+    let solutions: Vec<String> = diverse_solutions(Diversity::Cells(6)).collect();
+
+    assert_eq!(4, solutions.len());
+    for (i, first) in solutions.iter().enumerate() {
+        for second in &solutions[i + 1..] {
+            let different_cells = first
+                .chars()
+                .zip(second.chars())
+                .filter(|(a, b)| a != b)
+                .count();
+            assert!(different_cells >= 6, "{first} and {second} are too close");
+        }
+    }
+}
+
+#[test]
+fn cells_too_many() {
+    assert_eq!(1, count_diverse_solutions(Diversity::Cells(10)));
+}
+
+#[test]
+fn slots() {
+    assert_eq!(4, count_diverse_solutions(Diversity::Slots(5)));
+    assert_eq!(2, count_diverse_solutions(Diversity::Slots(6)));
+    assert_eq!(1, count_diverse_solutions(Diversity::Slots(7)));
+}
+
+#[test]
+fn word_level_encoding_unsupported() {
+    let result = solutions(Box::new(WordLevelEncoding)).with_diversity(Diversity::Slots(1));
+
+    assert!(result.is_err(), "Word-level encoding has no cell variable");
+}
+
+#[test]
+fn block_in_first_cell() {
+    let words: Vec<String> = ["AA", "BC", "BBB", "CDE", "ABD", "ABE"]
+        .iter()
+        .map(|&word| word.to_string())
+        .collect();
+    let crossword = Crossword::try_from("#..\nBBB\nCDE", &words).unwrap();
+    let solutions = crossword
+        .solve_with(Box::new(CadicalSolver::new()))
+        .unwrap()
+        .with_diversity(Diversity::Cells(1))
+        .unwrap();
+
+    assert_eq!(1, solutions.count_solutions(10).unwrap());
+}
+
+/// Counts the solutions of a 3x3 grid, using the given diversity.
+fn count_diverse_solutions(diversity: Diversity) -> usize {
+    diverse_solutions(diversity).count_solutions(10).unwrap()
+}
+
+/// Returns the solutions of a 3x3 grid, using the CaDiCaL solver and the given diversity.
+fn diverse_solutions(diversity: Diversity) -> CrosswordSolutions {
+    solutions(Box::new(MddEncoding))
+        .with_diversity(diversity)
+        .unwrap()
+}
+
+/// Returns the solutions of a 3x3 grid, using the CaDiCaL solver and the given encoding.
+fn solutions(encoding: Box<dyn Encoding>) -> CrosswordSolutions {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .with_encoding(encoding);
+    crossword
+        .solve_with(Box::new(CadicalSolver::new()))
+        .unwrap()
+}
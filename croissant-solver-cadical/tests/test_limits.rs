@@ -0,0 +1,113 @@
+use croissant_crossword::crossword::Crossword;
+use croissant_solver::{
+    CancellationToken, Lit, SearchLimits, SolveOutcome, Solver, SolverConfigurator, Var,
+};
+use croissant_solver_cadical::CadicalSolver;
+use std::time::Duration;
+
+const WORDS: [&str; 6] = ["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"];
+
+/// The pigeonhole problem: `holes + 1` pigeons in `holes` holes. Unsatisfiable, and hard to prove so.
+fn pigeonhole(holes: usize) -> CadicalSolver {
+    let mut solver = CadicalSolver::new();
+    let pigeons = holes + 1;
+    let variable = |pigeon: usize, hole: usize| Lit::from(Var::new(pigeon * holes + hole + 1));
+    solver.set_relevant_variables((1..=pigeons * holes).map(Var::new).collect());
+    for pigeon in 0..pigeons {
+        let clause: Vec<Lit> = (0..holes).map(|hole| variable(pigeon, hole)).collect();
+        solver.add_clause(&clause);
+    }
+    for hole in 0..holes {
+        for first in 0..pigeons {
+            for second in first + 1..pigeons {
+                solver.add_clause(&[-variable(first, hole), -variable(second, hole)]);
+            }
+        }
+    }
+    solver
+}
+
+#[test]
+fn conflicts() {
+    let mut solver = pigeonhole(6);
+    solver.set_limits(SearchLimits {
+        conflicts: Some(1),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(SolveOutcome::Interrupted, solver.solve_next());
+    assert_eq!(
+        SolveOutcome::Interrupted,
+        solver.solve_with_assumptions(&[Lit::from_raw(1)])
+    );
+
+    solver.set_limits(SearchLimits::default());
+    assert_eq!(SolveOutcome::Unsat, solver.solve_next());
+}
+
+#[test]
+fn timeout() {
+    let mut solver = pigeonhole(10);
+    solver.set_limits(SearchLimits {
+        timeout: Some(Duration::ZERO),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(SolveOutcome::Interrupted, solver.solve_next());
+}
+
+#[test]
+fn cancellation() {
+    let token = CancellationToken::new();
+    let mut solver = pigeonhole(10);
+    solver.set_limits(SearchLimits {
+        cancellation: Some(token.clone()),
+        ..SearchLimits::default()
+    });
+
+    token.cancel();
+
+    assert_eq!(SolveOutcome::Interrupted, solver.solve_next());
+    assert_eq!(None, solver.next());
+}
+
+#[test]
+fn crossword_interrupted() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .with_limits(SearchLimits {
+            cancellation: Some(token),
+            ..SearchLimits::default()
+        });
+
+    let mut solutions = crossword
+        .solve_with(Box::new(CadicalSolver::new()))
+        .unwrap();
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should be interrupted");
+}
+
+#[test]
+fn crossword_not_interrupted() {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .with_limits(SearchLimits {
+            timeout: Some(Duration::from_secs(60)),
+            ..SearchLimits::default()
+        });
+
+    let mut solutions = crossword
+        .solve_with(Box::new(CadicalSolver::new()))
+        .unwrap();
+
+    assert_eq!(4, solutions.by_ref().count());
+    assert!(
+        !solutions.is_interrupted(),
+        "Search should not be interrupted"
+    );
+}
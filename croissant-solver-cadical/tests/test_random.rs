@@ -0,0 +1,44 @@
+use croissant_crossword::crossword::Crossword;
+use croissant_crossword::encoding::{DirectEncoding, Encoding};
+use croissant_solver_cadical::CadicalSolver;
+
+// The cadical crate exposes no way to randomize the search: Only the word order changes with the seed, which may not be
+// enough to change the solutions.
+
+#[test]
+fn same_seed_same_solution() {
+    for seed in 0..5 {
+        assert_eq!(
+            first_solution(Some(seed), Box::new(DirectEncoding)),
+            first_solution(Some(seed), Box::new(DirectEncoding))
+        );
+    }
+}
+
+#[test]
+fn no_seed_same_solution() {
+    assert_eq!(
+        first_solution(None, Box::new(DirectEncoding)),
+        first_solution(None, Box::new(DirectEncoding))
+    );
+}
+
+/// Returns the first solution of a 2x2 grid accepting any combination of the letters A to D, using the CaDiCaL solver.
+fn first_solution(seed: Option<u64>, encoding: Box<dyn Encoding>) -> String {
+    let letters = ['A', 'B', 'C', 'D'];
+    let words: Vec<String> = letters
+        .iter()
+        .flat_map(|first| letters.iter().map(move |second| format!("{first}{second}")))
+        .collect();
+    let mut crossword = Crossword::try_from("..\n..", &words)
+        .unwrap()
+        .with_encoding(encoding);
+    if let Some(seed) = seed {
+        crossword = crossword.with_seed(seed);
+    }
+    crossword
+        .solve_with(Box::new(CadicalSolver::new()))
+        .unwrap()
+        .next()
+        .unwrap()
+}
@@ -0,0 +1,47 @@
+use croissant_crossword::session::CrosswordSession;
+use croissant_solver_cadical::CadicalSolver;
+
+fn words() -> Vec<String> {
+    ["AAA", "BBB", "CDE", "ABC", "ABD", "ABE", "AA", "AB", "BE"]
+        .iter()
+        .map(|&word| word.to_string())
+        .collect()
+}
+
+#[test]
+fn edit_and_solve() {
+    let words = words();
+    let solver = Box::new(CadicalSolver::new());
+    let mut session = CrosswordSession::try_from("...\n...\n...", &words, solver).unwrap();
+
+    session.set_cell(0, 2, 'C').unwrap();
+    assert_eq!(Some("ABC\nABD\nABE".to_string()), session.solve().unwrap());
+
+    session.set_block(1, 2).unwrap();
+    session.set_block(2, 0).unwrap();
+    assert_eq!(Some("ABC\nAB#\n#BE".to_string()), session.solve().unwrap());
+
+    session.exclude_word("BE").unwrap();
+    assert_eq!(None, session.solve().unwrap());
+
+    session.clear_cell(1, 2).unwrap();
+    session.clear_cell(2, 0).unwrap();
+    assert_eq!(Some("ABC\nABD\nABE".to_string()), session.solve().unwrap());
+
+    session.set_cell(0, 2, 'D').unwrap();
+    assert_eq!(None, session.solve().unwrap());
+}
+
+#[test]
+fn solve_again() {
+    let words = words();
+    let solver = Box::new(CadicalSolver::new());
+    let mut session = CrosswordSession::try_from("...\n...\n...", &words, solver).unwrap();
+
+    let first_solution = session.solve().unwrap();
+    assert!(first_solution.is_some(), "No solution found");
+    session.set_cell(0, 0, 'C').unwrap();
+    assert_eq!(None, session.solve().unwrap());
+    session.clear_cell(0, 0).unwrap();
+    assert!(session.solve().unwrap().is_some(), "No solution found");
+}
@@ -0,0 +1,94 @@
+use croissant_crossword::crossword::{Crossword, CrosswordSolutions};
+use croissant_solver::{Lit, SearchLimits, SolveOutcome, Solver, SolverConfigurator, Var};
+use croissant_solver_cadical::CadicalSolver;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const WORDS: [&str; 6] = ["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"];
+
+#[test]
+fn stats() {
+    let mut solutions = solutions();
+    solutions.next();
+
+    let stats = solutions.stats();
+
+    assert!(stats.variables > 0, "Variables should be counted");
+    assert!(stats.clauses > 0, "Clauses should be counted");
+    assert!(
+        stats.solving_time > Duration::ZERO,
+        "Search should take time"
+    );
+    assert_eq!(None, stats.conflicts);
+}
+
+#[test]
+fn solver_stats() {
+    let mut solver = CadicalSolver::new();
+    solver.set_relevant_variables(vec![Var::new(1), Var::new(2), Var::new(3)]);
+    solver.add_clause(&literals(&[1, 2]));
+    solver.add_clause(&literals(&[-1, 3]));
+    solver.add_clause(&literals(&[-2, 3]));
+
+    assert!(matches!(solver.solve_next(), SolveOutcome::Sat(_)));
+    let stats = solver.stats();
+
+    assert_eq!(3, stats.variables);
+    assert!(
+        stats.solving_time > Duration::ZERO,
+        "Search should take time"
+    );
+}
+
+#[test]
+fn progress() {
+    // The pigeonhole problem, 11 pigeons in 10 holes: Hard enough to last beyond the first report
+    let holes = 10;
+    let variable = |pigeon: usize, hole: usize| Lit::from(Var::new(pigeon * holes + hole + 1));
+    let mut solver = CadicalSolver::new();
+    for pigeon in 0..=holes {
+        let clause: Vec<Lit> = (0..holes).map(|hole| variable(pigeon, hole)).collect();
+        solver.add_clause(&clause);
+    }
+    for hole in 0..holes {
+        for first in 0..=holes {
+            for second in first + 1..=holes {
+                solver.add_clause(&[-variable(first, hole), -variable(second, hole)]);
+            }
+        }
+    }
+    let reports = Arc::new(AtomicUsize::new(0));
+    let reports_in_callback = reports.clone();
+    solver.set_progress_callback(Box::new(move |stats| {
+        assert!(stats.variables > 0, "Variables should be counted");
+        reports_in_callback.fetch_add(1, Ordering::Relaxed);
+    }));
+    solver.set_limits(SearchLimits {
+        timeout: Some(Duration::from_millis(2500)),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(SolveOutcome::Interrupted, solver.solve_next());
+    assert!(
+        reports.load(Ordering::Relaxed) >= 1,
+        "Progress should be reported"
+    );
+}
+
+/// Returns the solutions of a 3x3 grid, using the CaDiCaL solver.
+fn solutions() -> CrosswordSolutions {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("...\n...\n...", &words).unwrap();
+    crossword
+        .solve_with(Box::new(CadicalSolver::new()))
+        .unwrap()
+}
+
+/// Returns the literals of the given raw representations.
+fn literals(literals: &[i32]) -> Vec<Lit> {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
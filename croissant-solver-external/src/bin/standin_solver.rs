@@ -0,0 +1,156 @@
+//! A tiny DPLL solver reading a problem in DIMACS CNF format on its standard input and writing the result on its
+//! standard output, in the format of the SAT competitions. It is slow: It stands in for a real solver in the tests.
+//!
+//! Options:
+//!
+//! - `--sleep <MILLISECONDS>`: Waits before solving, to simulate a long search.
+//! - `--unknown`: Answers `s UNKNOWN` without solving, to simulate a search reaching its limits.
+//! - `--crash`: Answers no status, to simulate a crashing program.
+
+use std::io::{self, Read};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+fn main() -> ExitCode {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(index) = arguments.iter().position(|argument| argument == "--sleep") {
+        let milliseconds = arguments[index + 1]
+            .parse()
+            .expect("Invalid sleep duration");
+        thread::sleep(Duration::from_millis(milliseconds));
+    }
+    if arguments.iter().any(|argument| argument == "--unknown") {
+        println!("s UNKNOWN");
+        return ExitCode::from(0);
+    }
+    if arguments.iter().any(|argument| argument == "--crash") {
+        println!("Segmentation fault");
+        return ExitCode::from(139);
+    }
+
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .expect("Cannot read standard input");
+    let (variables_count, clauses) = parse(&input);
+    let mut assignment = vec![0; variables_count + 1];
+    if solve(&clauses, &mut assignment) {
+        println!("s SATISFIABLE");
+        let literals: Vec<String> = (1..=variables_count)
+            .map(|variable| (assignment[variable] as i32 * variable as i32).to_string())
+            .collect();
+        println!("v {} 0", literals.join(" "));
+        ExitCode::from(10)
+    } else {
+        println!("s UNSATISFIABLE");
+        ExitCode::from(20)
+    }
+}
+
+/// Parses the given DIMACS CNF problem. Returns the number of variables and the clauses.
+fn parse(input: &str) -> (usize, Vec<Vec<i32>>) {
+    let mut variables_count = 0;
+    let mut clauses = Vec::new();
+    let mut clause = Vec::new();
+    for line in input.lines() {
+        if line.starts_with('c') {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix("p cnf") {
+            variables_count = header
+                .split_whitespace()
+                .next()
+                .and_then(|count| count.parse().ok())
+                .expect("Invalid header");
+            continue;
+        }
+        for literal in line.split_whitespace() {
+            let literal: i32 = literal.parse().expect("Invalid literal");
+            if literal == 0 {
+                clauses.push(std::mem::take(&mut clause));
+            } else {
+                variables_count = variables_count.max(literal.unsigned_abs() as usize);
+                clause.push(literal);
+            }
+        }
+    }
+    (variables_count, clauses)
+}
+
+/// Searches an assignment satisfying the given clauses, extending the given one: 1 is true, -1 is false, 0 is
+/// unassigned. Returns `true` iff found, the assignment being then complete. Unassigned variables are set to false.
+fn solve(clauses: &[Vec<i32>], assignment: &mut Vec<i8>) -> bool {
+    if !propagate(clauses, assignment) {
+        return false;
+    }
+    let unassigned = clauses
+        .iter()
+        .filter(|clause| !clause.iter().any(|&literal| value(assignment, literal) > 0))
+        .flat_map(|clause| clause.iter())
+        .find(|&&literal| value(assignment, literal) == 0);
+    let Some(&literal) = unassigned else {
+        // All clauses are satisfied
+        for value in assignment.iter_mut().filter(|value| **value == 0) {
+            *value = -1;
+        }
+        return true;
+    };
+    for decision in [literal, -literal] {
+        let mut branch = assignment.clone();
+        assign(&mut branch, decision);
+        if solve(clauses, &mut branch) {
+            *assignment = branch;
+            return true;
+        }
+    }
+    false
+}
+
+/// Assigns the literals of the unit clauses until none is left. Returns `false` if a clause is falsified.
+fn propagate(clauses: &[Vec<i32>], assignment: &mut [i8]) -> bool {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for clause in clauses {
+            let mut unassigned = None;
+            let mut unassigned_count = 0;
+            let mut satisfied = false;
+            for &literal in clause {
+                match value(assignment, literal) {
+                    0 => {
+                        unassigned = Some(literal);
+                        unassigned_count += 1;
+                    }
+                    1 => {
+                        satisfied = true;
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            if satisfied {
+                continue;
+            }
+            match (unassigned_count, unassigned) {
+                (0, _) => return false,
+                (1, Some(literal)) => {
+                    assign(assignment, literal);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+    }
+    true
+}
+
+/// Returns the value of the given literal under the given assignment: 1 if true, -1 if false, 0 if unassigned.
+fn value(assignment: &[i8], literal: i32) -> i8 {
+    assignment[literal.unsigned_abs() as usize] * literal.signum() as i8
+}
+
+/// Makes the given literal true.
+fn assign(assignment: &mut [i8], literal: i32) {
+    assignment[literal.unsigned_abs() as usize] = literal.signum() as i8;
+}
@@ -0,0 +1,364 @@
+//! A [ConfigurableSolver] delegating the solving to an external program, e.g. [kissat](https://github.com/arminbiere/kissat)
+//! or [glucose](https://github.com/audemard/glucose), so that any solver can be used without linking it.
+//!
+//! The program is given the problem in DIMACS CNF format on its standard input, and must write the result on its
+//! standard output in the format of the SAT competitions:
+//!
+//! ```text
+//! s SATISFIABLE
+//! v 1 -2 3 0
+//! ```
+
+use std::env;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use croissant_solver::{
+    CancellationToken, ConfigurableSolver, Lit, Model, SearchLimits, SolveOutcome, Solver,
+    SolverConfigurator, SolverError, SolverStats, Var,
+};
+
+/// The interval at which the program is checked for termination, time budget and cancellation.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Implementation of [ConfigurableSolver] running an external program for each search.
+///
+/// The program is run from scratch for each search: Nothing is learnt from one search to the next. Solutions are
+/// enumerated by adding a clause blocking the relevant variables of each solution found, and assumptions are given as
+/// unit clauses. Failed assumptions are unknown.
+///
+/// The time budget and the cancellation token are supported: The program is killed when they are reached. The conflict
+/// budget, the seed and the progress callback are ignored, since there is no standard way to give them to a program:
+/// Pass the options of the program as arguments instead.
+///
+/// ## Errors
+///
+/// Searches fail with [SolveOutcome::Failed] if the program cannot be run or if its output is not understood.
+pub struct ExternalSolver {
+    /// The path to the program.
+    program: PathBuf,
+    /// The arguments given to the program.
+    arguments: Vec<String>,
+    /// The number of variables: The biggest allocated or used variable.
+    variables_count: usize,
+    /// The clauses of the problem, including the ones blocking the solutions already found.
+    clauses: Vec<Vec<Lit>>,
+    /// The problem's relevant variables.
+    relevant_variables: Vec<Var>,
+    /// The maximal duration of a search, if any.
+    timeout: Option<Duration>,
+    /// The cancellation token, if any.
+    cancellation: Option<CancellationToken>,
+    /// The time spent searching so far.
+    solving_time: Duration,
+    /// Whether there is no solution left.
+    no_more_solution: bool,
+}
+
+impl ExternalSolver {
+    /// Creates an instance running the program at the given path, e.g. `kissat`, with the given arguments, e.g.
+    /// `["-q"]`. The program is searched in the `PATH` if the path is a bare name. Returns an error if there is no such
+    /// program.
+    pub fn new(program: impl Into<PathBuf>, arguments: Vec<String>) -> Result<Self, SolverError> {
+        let program = program.into();
+        let program = locate(&program)
+            .ok_or_else(|| SolverError::new(format!("Cannot find {}", program.display())))?;
+        Ok(ExternalSolver {
+            program,
+            arguments,
+            variables_count: 0,
+            clauses: Vec::new(),
+            relevant_variables: Vec::new(),
+            timeout: None,
+            cancellation: None,
+            solving_time: Duration::ZERO,
+            no_more_solution: false,
+        })
+    }
+
+    /// Runs the program on the problem plus the given assumptions, as unit clauses, measuring the solving time.
+    fn search(&mut self, assumptions: &[Lit]) -> SolveOutcome {
+        let started = Instant::now();
+        let spawned = Command::new(&self.program)
+            .args(&self.arguments)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(error) => {
+                let message = format!("Cannot run {}: {error}", self.program.display());
+                return SolveOutcome::Failed(SolverError::new(message));
+            }
+        };
+
+        let input = self.dimacs(assumptions);
+        let stdin = child.stdin.take().expect("Standard input should be piped");
+        // A program may stop reading its input early, e.g. on an empty clause: Writing errors are not relevant
+        let writer = thread::spawn(move || BufWriter::new(stdin).write_all(&input).is_ok());
+        let stdout = child
+            .stdout
+            .take()
+            .expect("Standard output should be piped");
+        let reader = thread::spawn(move || {
+            let mut output = String::new();
+            BufReader::new(stdout).read_to_string(&mut output).ok();
+            output
+        });
+
+        let interrupted = self.wait_for(&mut child, started);
+        writer.join().expect("Writer thread should not panic");
+        let output = reader.join().expect("Reader thread should not panic");
+        self.solving_time += started.elapsed();
+        if interrupted {
+            return SolveOutcome::Interrupted;
+        }
+        parse(&output, self.variables_count).unwrap_or_else(|error| {
+            let message = format!("Cannot understand {}: {error}", self.program.display());
+            SolveOutcome::Failed(SolverError::new(message))
+        })
+    }
+
+    /// Waits for the given program to terminate, killing it if the time budget is exhausted or if the search is
+    /// cancelled. Returns whether it has been killed.
+    fn wait_for(&self, child: &mut Child, started: Instant) -> bool {
+        loop {
+            if child
+                .try_wait()
+                .expect("Program status should be available")
+                .is_some()
+            {
+                return false;
+            }
+            let cancelled = self
+                .cancellation
+                .as_ref()
+                .is_some_and(CancellationToken::is_cancelled);
+            let timed_out = self
+                .timeout
+                .is_some_and(|timeout| started.elapsed() >= timeout);
+            if cancelled || timed_out {
+                // The program may have terminated in the meantime
+                child.kill().ok();
+                child.wait().ok();
+                return true;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Returns the problem plus the given assumptions, as unit clauses, in DIMACS CNF format.
+    fn dimacs(&self, assumptions: &[Lit]) -> Vec<u8> {
+        let variables_count = assumptions
+            .iter()
+            .map(|assumption| assumption.var().index())
+            .fold(self.variables_count, usize::max);
+        let clauses_count = self.clauses.len() + assumptions.len();
+        let mut dimacs = format!("p cnf {variables_count} {clauses_count}\n");
+        let assumptions = assumptions.iter().map(std::slice::from_ref);
+        for clause in self.clauses.iter().map(Vec::as_slice).chain(assumptions) {
+            for literal in clause {
+                dimacs.push_str(&literal.to_string());
+                dimacs.push(' ');
+            }
+            dimacs.push_str("0\n");
+        }
+        dimacs.into_bytes()
+    }
+
+    /// Adds the clause blocking the relevant variables of the given model, or all its variables if no variable is
+    /// relevant.
+    fn block(&mut self, model: &Model) {
+        let blocking_clause: Vec<Lit> = if self.relevant_variables.is_empty() {
+            model.literals().map(|literal| -literal).collect()
+        } else {
+            self.relevant_variables
+                .iter()
+                .filter_map(|&variable| model.literal(variable))
+                .map(|literal| -literal)
+                .collect()
+        };
+        self.clauses.push(blocking_clause);
+    }
+}
+
+/// Returns the path to the given program: The path itself if it leads to a file, or else, if it is a bare name, the
+/// first file with this name in the directories of the `PATH`. Returns [None] if there is no such file.
+fn locate(program: &Path) -> Option<PathBuf> {
+    if program.is_file() {
+        return Some(program.to_path_buf());
+    }
+    if program.components().count() != 1 {
+        return None;
+    }
+    let mut file_name = program.as_os_str().to_owned();
+    file_name.push(env::consts::EXE_SUFFIX);
+    env::split_paths(&env::var_os("PATH")?)
+        .flat_map(|directory| [directory.join(program), directory.join(&file_name)])
+        .find(|path| path.is_file())
+}
+
+/// Parses the given output of a program, for a problem with the given number of variables. Returns the outcome of the
+/// search, or an error if the output does not tell it.
+fn parse(output: &str, variables_count: usize) -> Result<SolveOutcome, String> {
+    let mut status = None;
+    let mut model: Model = (1..=variables_count)
+        .map(|variable| Var::new(variable).literal(false))
+        .collect();
+    for line in output.lines() {
+        if let Some(line_status) = line.strip_prefix("s ") {
+            status = Some(line_status.trim());
+        } else if let Some(values) = line.strip_prefix("v ") {
+            for literal in values.split_whitespace() {
+                let literal: i32 = literal
+                    .parse()
+                    .map_err(|_| format!("Invalid literal {literal}"))?;
+                let variable = literal.unsigned_abs() as usize;
+                if (1..=variables_count).contains(&variable) {
+                    model.set(Lit::from_raw(literal));
+                }
+            }
+        }
+    }
+    match status {
+        Some("SATISFIABLE") => Ok(SolveOutcome::Sat(model)),
+        Some("UNSATISFIABLE") => Ok(SolveOutcome::Unsat),
+        Some("UNKNOWN") => Ok(SolveOutcome::Interrupted),
+        Some(status) => Err(format!("Unknown status {status}")),
+        None => Err("No status".to_string()),
+    }
+}
+
+impl SolverConfigurator for ExternalSolver {
+    fn allocate_variables(&mut self, variables_count: usize) {
+        self.variables_count = self.variables_count.max(variables_count);
+    }
+    fn variables_count(&self) -> Option<usize> {
+        Some(self.variables_count)
+    }
+    fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
+        if let Some(max_variable) = relevant_variables.iter().max() {
+            self.allocate_variables(max_variable.index());
+        }
+        self.relevant_variables = relevant_variables;
+    }
+    fn set_limits(&mut self, limits: SearchLimits) {
+        self.timeout = limits.timeout;
+        self.cancellation = limits.cancellation;
+    }
+    fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        limits.conflicts.is_none()
+    }
+    fn add_clause(&mut self, literals: &[Lit]) {
+        for &literal in literals {
+            self.allocate_variables(literal.var().index());
+        }
+        self.clauses.push(literals.to_vec());
+    }
+}
+
+impl Iterator for ExternalSolver {
+    type Item = Model;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.solve_next() {
+            SolveOutcome::Sat(model) => Some(model),
+            _ => None,
+        }
+    }
+}
+
+impl Solver for ExternalSolver {
+    fn solve_next(&mut self) -> SolveOutcome {
+        if self.no_more_solution {
+            return SolveOutcome::Unsat;
+        }
+        let outcome = self.search(&[]);
+        match &outcome {
+            SolveOutcome::Sat(model) => self.block(model),
+            SolveOutcome::Unsat => self.no_more_solution = true,
+            _ => {}
+        }
+        outcome
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[Lit]) -> SolveOutcome {
+        self.search(assumptions)
+    }
+
+    fn as_configurator(&mut self) -> Option<&mut dyn SolverConfigurator> {
+        Some(self)
+    }
+
+    fn stats(&self) -> SolverStats {
+        SolverStats {
+            variables: self.variables_count,
+            clauses: self.clauses.len(),
+            solving_time: self.solving_time,
+            ..SolverStats::default()
+        }
+    }
+}
+
+impl ConfigurableSolver for ExternalSolver {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_satisfiable() {
+        let output = "c Some comment\ns SATISFIABLE\nv 1 -2\nv 3 0\n";
+
+        let outcome = parse(output, 4);
+
+        let model = [1, -2, 3, -4].into_iter().map(Lit::from_raw).collect();
+        assert_eq!(Ok(SolveOutcome::Sat(model)), outcome);
+    }
+
+    #[test]
+    fn parse_unsatisfiable() {
+        assert_eq!(Ok(SolveOutcome::Unsat), parse("s UNSATISFIABLE\n", 2));
+    }
+
+    #[test]
+    fn parse_unknown() {
+        assert_eq!(Ok(SolveOutcome::Interrupted), parse("s UNKNOWN\n", 2));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            Err("No status".to_string()),
+            parse("Segmentation fault\n", 2)
+        );
+        assert_eq!(
+            Err("Invalid literal x".to_string()),
+            parse("s SATISFIABLE\nv x 0\n", 2)
+        );
+    }
+
+    #[test]
+    fn locate() {
+        let current_exe = env::current_exe().unwrap();
+
+        assert_eq!(Some(current_exe.clone()), super::locate(&current_exe));
+        assert_eq!(None, super::locate(Path::new("/nonexistent/solver")));
+        assert_eq!(None, super::locate(Path::new("nonexistent-solver")));
+    }
+
+    #[test]
+    fn dimacs() {
+        let mut solver = ExternalSolver::new(env::current_exe().unwrap(), vec![]).unwrap();
+        solver.add_clause(&[Lit::from_raw(1), Lit::from_raw(-2)]);
+        solver.add_clause(&[Lit::from_raw(2)]);
+
+        let dimacs = String::from_utf8(solver.dimacs(&[Lit::from_raw(-3)])).unwrap();
+
+        assert_eq!("p cnf 3 3\n1 -2 0\n2 0\n-3 0\n", dimacs);
+    }
+}
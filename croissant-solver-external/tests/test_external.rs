@@ -0,0 +1,178 @@
+use std::time::{Duration, Instant};
+
+use croissant_crossword::crossword::Crossword;
+use croissant_crossword::encoding::MddEncoding;
+use croissant_solver::{
+    CancellationToken, Lit, Model, SearchLimits, SolveOutcome, Solver, SolverConfigurator,
+    SolverError, Var,
+};
+use croissant_solver_external::ExternalSolver;
+
+const WORDS: [&str; 6] = ["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"];
+
+#[test]
+fn all_models() {
+    let mut solver = standin_solver(&[]);
+    solver.allocate_variables(2);
+    solver.add_clause(&literals(&[1, 2]));
+
+    let mut models: Vec<Vec<i32>> = solver
+        .by_ref()
+        .map(|model| model.literals().map(i32::from).collect())
+        .collect();
+
+    models.sort();
+    assert_eq!(vec![vec![-1, 2], vec![1, -2], vec![1, 2]], models);
+    assert_eq!(SolveOutcome::Unsat, solver.solve_next());
+}
+
+#[test]
+fn projected_models() {
+    let mut solver = standin_solver(&[]);
+    solver.set_relevant_variables(vec![Var::new(1)]);
+    solver.add_clause(&literals(&[1, 2]));
+    solver.add_clause(&literals(&[-1, -2]));
+
+    let models: Vec<Model> = solver.collect();
+
+    assert_eq!(2, models.len());
+    assert_ne!(models[0].value(Var::new(1)), models[1].value(Var::new(1)));
+}
+
+#[test]
+fn unsatisfiable() {
+    let mut solver = standin_solver(&[]);
+    solver.add_clause(&[Lit::from_raw(1)]);
+    solver.add_clause(&[Lit::from_raw(-1)]);
+
+    assert_eq!(SolveOutcome::Unsat, solver.solve_next());
+}
+
+#[test]
+fn assumptions() {
+    let mut solver = standin_solver(&[]);
+    solver.add_clause(&literals(&[1, 2]));
+
+    assert_eq!(
+        SolveOutcome::Sat(model(&[-1, 2])),
+        solver.solve_with_assumptions(&[Lit::from_raw(-1)])
+    );
+    assert_eq!(
+        SolveOutcome::Unsat,
+        solver.solve_with_assumptions(&literals(&[-1, -2]))
+    );
+    // Assumptions are not kept
+    assert!(matches!(solver.solve_next(), SolveOutcome::Sat(_)));
+    assert_eq!(None, solver.failed_assumptions());
+}
+
+#[test]
+fn unknown() {
+    let mut solver = standin_solver(&["--unknown"]);
+    solver.add_clause(&[Lit::from_raw(1)]);
+
+    assert_eq!(SolveOutcome::Interrupted, solver.solve_next());
+}
+
+#[test]
+fn timeout() {
+    let mut solver = standin_solver(&["--sleep", "10000"]);
+    solver.add_clause(&[Lit::from_raw(1)]);
+    solver.set_limits(SearchLimits {
+        timeout: Some(Duration::from_millis(100)),
+        ..SearchLimits::default()
+    });
+
+    let started = Instant::now();
+    assert_eq!(SolveOutcome::Interrupted, solver.solve_next());
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn cancelled() {
+    let mut solver = standin_solver(&["--sleep", "10000"]);
+    solver.add_clause(&[Lit::from_raw(1)]);
+    let cancellation = CancellationToken::new();
+    cancellation.cancel();
+    solver.set_limits(SearchLimits {
+        cancellation: Some(cancellation),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(SolveOutcome::Interrupted, solver.solve_next());
+}
+
+#[test]
+fn missing_program() {
+    let solver = ExternalSolver::new("/nonexistent/solver", vec![]);
+
+    assert_eq!(
+        Some(SolverError::new("Cannot find /nonexistent/solver")),
+        solver.err()
+    );
+}
+
+#[test]
+fn crashing_program() {
+    let mut solver = standin_solver(&["--crash"]);
+    solver.add_clause(&[Lit::from_raw(1)]);
+
+    let SolveOutcome::Failed(error) = solver.solve_next() else {
+        panic!("Search should fail");
+    };
+    assert!(
+        error.message().starts_with("Cannot understand"),
+        "Unexpected error: {error}"
+    );
+}
+
+#[test]
+fn crossword() {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("...\n...\n.DE", &words)
+        .unwrap()
+        .with_encoding(Box::new(MddEncoding));
+
+    let mut solutions = crossword.solve_with(Box::new(standin_solver(&[]))).unwrap();
+
+    assert_eq!(Some("AAA\nBBB\nCDE".to_string()), solutions.next());
+    assert_eq!(None, solutions.next());
+}
+
+#[test]
+fn crossword_solution_count() {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .with_encoding(Box::new(MddEncoding));
+
+    let solutions = crossword.solve_with(Box::new(standin_solver(&[]))).unwrap();
+
+    assert_eq!(Ok(4), solutions.count_solutions(10));
+}
+
+/// Returns a solver running the stand-in solver with the given arguments.
+fn standin_solver(arguments: &[&str]) -> ExternalSolver {
+    let arguments = arguments.iter().map(|&argument| argument.to_string());
+    ExternalSolver::new(
+        env!("CARGO_BIN_EXE_croissant-standin-solver"),
+        arguments.collect(),
+    )
+    .unwrap()
+}
+
+/// Returns the model of the given raw literals.
+fn model(literals: &[i32]) -> Model {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
+
+/// Returns the literals of the given raw representations.
+fn literals(literals: &[i32]) -> Vec<Lit> {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
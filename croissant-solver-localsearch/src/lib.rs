@@ -0,0 +1,290 @@
+//! A native crossword solver trading completeness for speed: A min-conflicts local search swapping the words of the
+//! slots until no crossing disagrees.
+//!
+//! ## Example
+//!
+//! ```
+//! use croissant_crossword::crossword::Crossword;
+//! use croissant_solver_localsearch::LocalSearchSolverBuilder;
+//!
+//! let words = ["AAA".to_string()];
+//! let crossword = Crossword::try_from("A..\n.#.\n...", &words).unwrap();
+//! let mut solutions = crossword.solve_natively_with(Box::new(LocalSearchSolverBuilder::new())).unwrap();
+//! assert_eq!(Some("AAA\nA#A\nAAA".to_string()), solutions.next());
+//! ```
+
+mod search;
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use croissant_crossword::crossword::NativeSolverBuilder;
+use croissant_crossword::encoding::Decoder;
+use croissant_crossword::grid::Grid;
+use croissant_solver::{
+    CancellationToken, Lit, Model, ProgressCallback, SearchLimits, SolveOutcome, Solver,
+    SolverError, SolverStats, Var,
+};
+
+use crate::search::{MinConflicts, SplitMix64, VALUE_COUNT};
+
+/// The number of word swaps between two checks of the time budget, of the cancellation and of the progress.
+const FLIPS_BETWEEN_CHECKS: u64 = 1024;
+
+/// The minimal duration between two progress reports.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Implementation of [NativeSolverBuilder], building a [LocalSearchSolver].
+pub struct LocalSearchSolverBuilder {
+    /// The seed of the pseudo-random number generator.
+    seed: u64,
+    /// The probability to swap a word for a random candidate rather than for the best one.
+    noise: f64,
+    /// The maximal number of word swaps before restarting from a random assignment.
+    max_flips: u64,
+    /// The maximal number of restarts before giving up a search.
+    max_restarts: u64,
+}
+
+impl Default for LocalSearchSolverBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalSearchSolverBuilder {
+    /// Creates an instance, with a seed of 0, a noise of 0.1, and 10 restarts after 100,000 word swaps each before
+    /// giving up a search.
+    pub fn new() -> Self {
+        LocalSearchSolverBuilder {
+            seed: 0,
+            noise: 0.1,
+            max_flips: 100_000,
+            max_restarts: 10,
+        }
+    }
+
+    /// Sets the seed of the pseudo-random number generator. The same seed gives the same solutions.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the probability, between 0 and 1, to swap a word for a random candidate rather than for the one agreeing
+    /// with the most crossing words. Noise helps escaping local minima.
+    pub fn with_noise(mut self, noise: f64) -> Self {
+        self.noise = noise.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the maximal number of word swaps before restarting from a random assignment.
+    pub fn with_max_flips(mut self, max_flips: u64) -> Self {
+        self.max_flips = max_flips.max(1);
+        self
+    }
+
+    /// Sets the maximal number of restarts before giving up a search.
+    pub fn with_max_restarts(mut self, max_restarts: u64) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+}
+
+impl NativeSolverBuilder for LocalSearchSolverBuilder {
+    fn build(
+        self: Box<Self>,
+        grid: &Grid,
+        words: &[String],
+        decoder: &dyn Decoder,
+        limits: SearchLimits,
+        progress: Option<ProgressCallback>,
+    ) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+        let search = MinConflicts::new(grid, words, self.noise, SplitMix64::new(self.seed));
+        let literals = cell_literals(grid, decoder);
+        let variables_count = literals
+            .iter()
+            .flatten()
+            .map(|literal| literal.var().index())
+            .max()
+            .unwrap_or(0);
+        Ok(Box::new(LocalSearchSolver {
+            search,
+            literals,
+            variables_count,
+            max_flips: self.max_flips,
+            max_restarts: self.max_restarts,
+            limits,
+            progress,
+            found: HashSet::new(),
+            solving_time: Duration::ZERO,
+        }))
+    }
+}
+
+/// Returns the cell literals of the given decoder, by cell then by value.
+fn cell_literals(grid: &Grid, decoder: &dyn Decoder) -> Vec<[Lit; VALUE_COUNT]> {
+    let mut literals = Vec::with_capacity(grid.row_count() * grid.column_count());
+    for row in 0..grid.row_count() {
+        for column in 0..grid.column_count() {
+            literals.push(std::array::from_fn(|value| {
+                decoder
+                    .cell_literal(row, column, value)
+                    .expect("Decoder should have cell literals")
+            }));
+        }
+    }
+    literals
+}
+
+/// Implementation of [Solver] searching fillings by min-conflicts local search.
+///
+/// The search is incomplete: When a search gives up, after its restarts, it returns [SolveOutcome::Interrupted]
+/// rather than [SolveOutcome::Unsat], since a solution may exist. Only a grid with a slot without candidate is
+/// reported as unsatisfiable. Each solution is returned once: A solution already found leads to a restart.
+///
+/// All the [SearchLimits] are supported, and checked regularly during the search: The conflict budget bounds the number
+/// of word swaps. Progress is reported periodically during the search, at most once per second. Statistics count the
+/// word swaps as decisions, and the restarts; There is no clause.
+///
+/// Solving under assumptions is not supported.
+pub struct LocalSearchSolver {
+    /// The search.
+    search: MinConflicts,
+    /// The cell literals, by cell then by value.
+    literals: Vec<[Lit; VALUE_COUNT]>,
+    /// The number of variables of the models.
+    variables_count: usize,
+    /// The maximal number of word swaps before restarting.
+    max_flips: u64,
+    /// The maximal number of restarts before giving up.
+    max_restarts: u64,
+    /// The limits of each search.
+    limits: SearchLimits,
+    /// The progress callback, if any.
+    progress: Option<ProgressCallback>,
+    /// The solutions found so far, as cell values.
+    found: HashSet<Vec<u8>>,
+    /// The time spent searching so far.
+    solving_time: Duration,
+}
+
+impl LocalSearchSolver {
+    /// Returns the model of the given cell values.
+    fn model(&self, cells: &[u8]) -> Model {
+        let mut model: Model = (1..=self.variables_count)
+            .map(|variable| Var::new(variable).literal(false))
+            .collect();
+        for (cell, &value) in cells.iter().enumerate() {
+            model.set(self.literals[cell][value as usize]);
+        }
+        model
+    }
+
+    /// Returns the filling of the grid if it is solved and not found yet.
+    fn new_solution(&mut self) -> Option<Vec<u8>> {
+        if !self.search.is_solved() {
+            return None;
+        }
+        let cells = self.search.cells();
+        self.found.insert(cells.clone()).then_some(cells)
+    }
+
+    /// Returns `true` iff the search started at the given instant must stop because of its time budget or because it
+    /// is cancelled.
+    fn timed_out_or_cancelled(&self, started: Instant) -> bool {
+        let cancelled = self
+            .limits
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled);
+        let timed_out = self
+            .limits
+            .timeout
+            .is_some_and(|timeout| started.elapsed() >= timeout);
+        cancelled || timed_out
+    }
+
+    /// Reports the progress, if a callback is set.
+    fn report_progress(&mut self, elapsed: Duration) {
+        let stats = SolverStats {
+            solving_time: self.solving_time + elapsed,
+            ..self.stats()
+        };
+        if let Some(progress) = self.progress.as_mut() {
+            progress(&stats);
+        }
+    }
+
+    /// Searches a new solution, restarting when a try gives up or finds an old solution.
+    fn search(&mut self, started: Instant) -> SolveOutcome {
+        let flips_budget = self.limits.conflicts.unwrap_or(u64::MAX);
+        let mut flips = 0u64;
+        let mut last_report = started;
+        for restart in 0..=self.max_restarts {
+            if restart > 0 {
+                self.search.restart();
+            }
+            for _ in 0..self.max_flips {
+                if let Some(cells) = self.new_solution() {
+                    return SolveOutcome::Sat(self.model(&cells));
+                }
+                if self.search.is_solved() {
+                    // A solution already returned
+                    break;
+                }
+                if flips.is_multiple_of(FLIPS_BETWEEN_CHECKS) {
+                    if self.timed_out_or_cancelled(started) {
+                        return SolveOutcome::Interrupted;
+                    }
+                    if last_report.elapsed() >= PROGRESS_INTERVAL {
+                        last_report = Instant::now();
+                        self.report_progress(started.elapsed());
+                    }
+                }
+                if flips >= flips_budget {
+                    return SolveOutcome::Interrupted;
+                }
+                self.search.flip();
+                flips += 1;
+            }
+            if let Some(cells) = self.new_solution() {
+                return SolveOutcome::Sat(self.model(&cells));
+            }
+        }
+        SolveOutcome::Interrupted
+    }
+}
+
+impl Iterator for LocalSearchSolver {
+    type Item = Model;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.solve_next() {
+            SolveOutcome::Sat(model) => Some(model),
+            _ => None,
+        }
+    }
+}
+
+impl Solver for LocalSearchSolver {
+    fn solve_next(&mut self) -> SolveOutcome {
+        if self.search.is_unsatisfiable() {
+            return SolveOutcome::Unsat;
+        }
+        let started = Instant::now();
+        let outcome = self.search(started);
+        self.solving_time += started.elapsed();
+        outcome
+    }
+
+    fn stats(&self) -> SolverStats {
+        let counters = self.search.counters();
+        SolverStats {
+            variables: self.variables_count,
+            decisions: Some(counters.flips),
+            restarts: Some(counters.restarts),
+            solving_time: self.solving_time,
+            ..SolverStats::default()
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::{HashMap, HashSet};
+
+use croissant_crossword::grid::{self, Grid};
+
+/// The number of values a cell can take: The letters from 'A' to 'Z', then a block.
+pub const VALUE_COUNT: usize = 27;
+
+/// The value of a block.
+const BLOCK: u8 = 26;
+
+/// The value of an empty cell.
+const EMPTY: u8 = u8::MAX;
+
+/// The words of a given length, indexed by value at each position.
+struct Words {
+    /// The words, as values.
+    words: Vec<Vec<u8>>,
+    /// For each position then each value, the words having this value at this position.
+    having: Vec<Vec<Vec<u32>>>,
+}
+
+impl Words {
+    /// Creates the index of the given words having the given length. Words with other characters than letters from
+    /// 'A' to 'Z' are ignored, as well as repeated words.
+    fn new(words: &[String], length: usize) -> Self {
+        let mut seen = HashSet::new();
+        let words = words
+            .iter()
+            .filter(|word| word.len() == length && word.bytes().all(|b| b.is_ascii_uppercase()))
+            .filter(|word| seen.insert(word.as_str()))
+            .map(|word| word.bytes().map(|letter| letter - b'A').collect())
+            .collect();
+        Self::from_values(words, length)
+    }
+
+    /// Creates the index of the values of a single cell, i.e. the words made of one letter or of a block.
+    fn values() -> Self {
+        let words = (0..VALUE_COUNT as u8).map(|value| vec![value]).collect();
+        Self::from_values(words, 1)
+    }
+
+    /// Creates the index of the given words, given as values.
+    fn from_values(words: Vec<Vec<u8>>, length: usize) -> Self {
+        let mut having = vec![vec![Vec::new(); VALUE_COUNT]; length];
+        for (index, word) in words.iter().enumerate() {
+            for (position, &value) in word.iter().enumerate() {
+                having[position][value as usize].push(index as u32);
+            }
+        }
+        Words { words, having }
+    }
+}
+
+/// A slot of the grid, or a single cell outside any slot.
+struct Slot {
+    /// The cells of the slot, as indices in the grid.
+    cells: Vec<usize>,
+    /// The crossings of the slot: The position in this slot, the crossing slot and the position in the crossing slot.
+    crossings: Vec<(usize, usize, usize)>,
+    /// The candidate words of the slot, i.e. the words agreeing with the prefilled cells.
+    candidates: Vec<u32>,
+    /// Whether each word of the length of the slot is a candidate.
+    is_candidate: Vec<bool>,
+}
+
+/// A minimal pseudo-random number generator.
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Creates an instance with the given seed.
+    pub fn new(seed: u64) -> Self {
+        SplitMix64(seed)
+    }
+
+    /// Returns the next pseudo-random integer.
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a pseudo-random integer lower than the given bound, which must be positive.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    /// Returns `true` with the given probability.
+    fn chance(&mut self, probability: f64) -> bool {
+        ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+}
+
+/// The counters of a search.
+#[derive(Clone, Debug, Default)]
+pub struct Counters {
+    /// The number of word swaps.
+    pub flips: u64,
+    /// The number of restarts from a random assignment.
+    pub restarts: u64,
+}
+
+/// A min-conflicts local search over the words of the slots.
+///
+/// Each slot holds a candidate word; Two crossing slots conflict when their words disagree on the crossing cell. At
+/// each step, a conflicting slot is picked at random, and its word is swapped for the candidate agreeing with the most
+/// crossing words, or, with the noise probability, for a random candidate.
+pub struct MinConflicts {
+    /// The words, by length.
+    words: HashMap<usize, Words>,
+    /// The slots.
+    slots: Vec<Slot>,
+    /// The number of cells of the grid.
+    cell_count: usize,
+    /// The word of each slot, as an index in the words of its length.
+    assignment: Vec<u32>,
+    /// The number of conflicting crossings of each slot.
+    conflicts: Vec<usize>,
+    /// The probability to swap for a random candidate rather than for the best one.
+    noise: f64,
+    /// The pseudo-random number generator.
+    random: SplitMix64,
+    /// The number of agreeing crossings of each word, by length, zero between two swaps.
+    agreements: HashMap<usize, Vec<u16>>,
+    /// The counters.
+    counters: Counters,
+}
+
+impl MinConflicts {
+    /// Creates the search of the fillings of the given grid with the given words, with the given noise and random
+    /// number generator, starting from a random assignment.
+    pub fn new(grid: &Grid, words: &[String], noise: f64, random: SplitMix64) -> Self {
+        let column_count = grid.column_count();
+        let mut cells = Vec::with_capacity(grid.row_count() * column_count);
+        for row in 0..grid.row_count() {
+            for column in 0..column_count {
+                cells.push(match grid.letter_at(row, column) {
+                    grid::EMPTY => EMPTY,
+                    grid::BLOCK => BLOCK,
+                    letter => letter as u8 - b'A',
+                });
+            }
+        }
+
+        let mut slot_cells: Vec<Vec<usize>> = grid
+            .slots()
+            .iter()
+            .map(|slot| {
+                slot.positions()
+                    .iter()
+                    .map(|pos| pos.row() * column_count + pos.column())
+                    .collect()
+            })
+            .collect();
+        let mut slots_by_cell: Vec<Vec<(usize, usize)>> = vec![Vec::new(); cells.len()];
+        for (slot_index, slot) in slot_cells.iter().enumerate() {
+            for (position, &cell) in slot.iter().enumerate() {
+                slots_by_cell[cell].push((slot_index, position));
+            }
+        }
+        for (cell, cell_slots) in slots_by_cell.iter().enumerate() {
+            // A cell outside any slot, e.g. a block, is a slot of its own: Its candidates are its value if prefilled
+            if cell_slots.is_empty() {
+                slot_cells.push(vec![cell]);
+            }
+        }
+
+        let mut indices = HashMap::new();
+        for slot in &slot_cells {
+            indices
+                .entry(slot.len())
+                .or_insert_with(|| match slot.len() {
+                    1 => Words::values(),
+                    length => Words::new(words, length),
+                });
+        }
+        let slots: Vec<Slot> = slot_cells
+            .into_iter()
+            .enumerate()
+            .map(|(slot_index, cells_of_slot)| {
+                let index: &Words = &indices[&cells_of_slot.len()];
+                let mut is_candidate = vec![true; index.words.len()];
+                for (position, &cell) in cells_of_slot.iter().enumerate() {
+                    if cells[cell] == EMPTY {
+                        continue;
+                    }
+                    for (word, candidate) in is_candidate.iter_mut().enumerate() {
+                        *candidate &= index.words[word][position] == cells[cell];
+                    }
+                }
+                let candidates = (0..index.words.len() as u32)
+                    .filter(|&word| is_candidate[word as usize])
+                    .collect();
+                let mut crossings = Vec::new();
+                for (position, &cell) in cells_of_slot.iter().enumerate() {
+                    for &(other_slot, other_position) in &slots_by_cell[cell] {
+                        if other_slot != slot_index {
+                            crossings.push((position, other_slot, other_position));
+                        }
+                    }
+                }
+                Slot {
+                    cells: cells_of_slot,
+                    crossings,
+                    candidates,
+                    is_candidate,
+                }
+            })
+            .collect();
+
+        let agreements = indices
+            .iter()
+            .map(|(&length, index)| (length, vec![0; index.words.len()]))
+            .collect();
+        let mut search = MinConflicts {
+            words: indices,
+            assignment: vec![0; slots.len()],
+            conflicts: vec![0; slots.len()],
+            slots,
+            cell_count: cells.len(),
+            noise,
+            random,
+            agreements,
+            counters: Counters::default(),
+        };
+        if !search.is_unsatisfiable() {
+            search.randomize();
+        }
+        search
+    }
+
+    /// Returns `true` iff a slot has no candidate, in which case there is no solution.
+    pub fn is_unsatisfiable(&self) -> bool {
+        self.slots.iter().any(|slot| slot.candidates.is_empty())
+    }
+
+    /// Returns `true` iff no crossing is conflicting.
+    pub fn is_solved(&self) -> bool {
+        self.conflicts.iter().all(|&conflicts| conflicts == 0)
+    }
+
+    /// Returns the values of the cells for the current assignment: The index of the letter in the alphabet, or a
+    /// block.
+    pub fn cells(&self) -> Vec<u8> {
+        let mut cells = vec![BLOCK; self.cell_count];
+        for (slot_index, slot) in self.slots.iter().enumerate() {
+            let word = self.word(slot_index);
+            for (position, &cell) in slot.cells.iter().enumerate() {
+                cells[cell] = word[position];
+            }
+        }
+        cells
+    }
+
+    /// Returns the counters of the search so far.
+    pub fn counters(&self) -> &Counters {
+        &self.counters
+    }
+
+    /// Restarts the search from a random assignment.
+    pub fn restart(&mut self) {
+        self.counters.restarts += 1;
+        self.randomize();
+    }
+
+    /// Swaps the word of a conflicting slot, if any. Returns `true` iff no crossing is conflicting afterward.
+    pub fn flip(&mut self) -> bool {
+        let conflicting: Vec<usize> = (0..self.slots.len())
+            .filter(|&slot| self.conflicts[slot] > 0)
+            .collect();
+        if conflicting.is_empty() {
+            return true;
+        }
+        let slot = conflicting[self.random.below(conflicting.len())];
+        let word = if self.random.chance(self.noise) {
+            self.random_candidate(slot)
+        } else {
+            self.best_candidate(slot)
+        };
+        self.assign(slot, word);
+        self.counters.flips += 1;
+        self.is_solved()
+    }
+
+    /// Assigns a random candidate to each slot.
+    fn randomize(&mut self) {
+        for slot in 0..self.slots.len() {
+            self.assignment[slot] = self.random_candidate(slot);
+        }
+        self.count_conflicts();
+    }
+
+    /// Counts the conflicting crossings of each slot from scratch.
+    fn count_conflicts(&mut self) {
+        for slot in 0..self.slots.len() {
+            let word = self.word(slot);
+            let conflicts = self.slots[slot]
+                .crossings
+                .iter()
+                .filter(|&&(position, other_slot, other_position)| {
+                    word[position] != self.word(other_slot)[other_position]
+                })
+                .count();
+            self.conflicts[slot] = conflicts;
+        }
+    }
+
+    /// Returns a random candidate of the given slot.
+    fn random_candidate(&mut self, slot: usize) -> u32 {
+        let candidates = &self.slots[slot].candidates;
+        candidates[self.random.below(candidates.len())]
+    }
+
+    /// Returns the candidate of the given slot agreeing with the most crossing words, other than its current word if
+    /// possible. Ties are broken at random.
+    fn best_candidate(&mut self, slot: usize) -> u32 {
+        let length = self.slots[slot].cells.len();
+        let index = &self.words[&length];
+        let agreements = self.agreements.get_mut(&length).unwrap();
+        let mut touched = Vec::new();
+        for &(position, other_slot, other_position) in &self.slots[slot].crossings {
+            let other_word = self.assignment[other_slot] as usize;
+            let other_length = self.slots[other_slot].cells.len();
+            let value = self.words[&other_length].words[other_word][other_position];
+            for &word in &index.having[position][value as usize] {
+                if agreements[word as usize] == 0 {
+                    touched.push(word);
+                }
+                agreements[word as usize] += 1;
+            }
+        }
+        let current = self.assignment[slot];
+        let mut best = None;
+        let mut best_agreements = 0;
+        let mut tie_count = 0;
+        for &word in &touched {
+            let word_agreements = agreements[word as usize];
+            if word == current || !self.slots[slot].is_candidate[word as usize] {
+                continue;
+            }
+            if word_agreements > best_agreements {
+                best = Some(word);
+                best_agreements = word_agreements;
+                tie_count = 1;
+            } else if word_agreements == best_agreements {
+                tie_count += 1;
+                if self.random.below(tie_count) == 0 {
+                    best = Some(word);
+                }
+            }
+        }
+        for word in touched {
+            agreements[word as usize] = 0;
+        }
+        match best {
+            Some(word) => word,
+            None => self.random_candidate(slot),
+        }
+    }
+
+    /// Assigns the given word to the given slot, updating the conflicts.
+    fn assign(&mut self, slot: usize, word: u32) {
+        let previous = self.word(slot).to_vec();
+        self.assignment[slot] = word;
+        let current = self.word(slot).to_vec();
+        for crossing in 0..self.slots[slot].crossings.len() {
+            let (position, other_slot, other_position) = self.slots[slot].crossings[crossing];
+            let other_value = self.word(other_slot)[other_position];
+            let was_conflicting = previous[position] != other_value;
+            let is_conflicting = current[position] != other_value;
+            if was_conflicting && !is_conflicting {
+                self.conflicts[slot] -= 1;
+                self.conflicts[other_slot] -= 1;
+            } else if !was_conflicting && is_conflicting {
+                self.conflicts[slot] += 1;
+                self.conflicts[other_slot] += 1;
+            }
+        }
+    }
+
+    /// Returns the word of the given slot, as values.
+    fn word(&self, slot: usize) -> &[u8] {
+        let length = self.slots[slot].cells.len();
+        &self.words[&length].words[self.assignment[slot] as usize]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|&word| word.to_string()).collect()
+    }
+
+    fn search(grid: &str, words: &[String]) -> MinConflicts {
+        let grid = Grid::try_from(grid).unwrap();
+        MinConflicts::new(&grid, words, 0.1, SplitMix64::new(42))
+    }
+
+    #[test]
+    fn solves() {
+        let words = words(&["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"]);
+        let mut search = search("ABC\n...\n...", &words);
+
+        let solved = (0..10_000).any(|_| search.flip());
+
+        assert!(solved, "No solution found");
+        // "ABC\nABD\nABE" is the only solution
+        assert_eq!(vec![0, 1, 2, 0, 1, 3, 0, 1, 4], search.cells());
+    }
+
+    #[test]
+    fn unsatisfiable_without_candidate() {
+        let words = words(&["ABC"]);
+
+        assert!(search("...\n...", &words).is_unsatisfiable());
+        assert!(!search("...", &words).is_unsatisfiable());
+    }
+
+    #[test]
+    fn conflicts_are_consistent() {
+        let words = words(&["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"]);
+        let mut search = search("...\n...\n...", &words);
+
+        for _ in 0..100 {
+            search.flip();
+            let conflicts = search.conflicts.clone();
+            search.count_conflicts();
+            assert_eq!(conflicts, search.conflicts);
+        }
+    }
+
+    #[test]
+    fn blocks_and_isolated_cells() {
+        let words = words(&["AB"]);
+        let mut search = search("AB#.", &words);
+
+        (0..100).any(|_| search.flip());
+
+        let cells = search.cells();
+        assert_eq!(&[0, 1, BLOCK], &cells[..3]);
+        assert!(cells[3] < VALUE_COUNT as u8);
+    }
+}
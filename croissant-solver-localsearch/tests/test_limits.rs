@@ -0,0 +1,66 @@
+use croissant_crossword::crossword::{Crossword, CrosswordSolutions};
+use croissant_solver::{CancellationToken, SearchLimits};
+use croissant_solver_localsearch::LocalSearchSolverBuilder;
+use std::time::Duration;
+
+/// The words of a 3x3 grid starting with "ABC" and having no solution, which local search cannot prove.
+const WORDS: [&str; 6] = ["AAA", "BBB", "CDF", "ABC", "ABD", "ABE"];
+
+#[test]
+fn conflicts() {
+    let mut solutions = solve(SearchLimits {
+        conflicts: Some(10),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should be interrupted");
+    assert_eq!(Some(10), solutions.stats().decisions);
+}
+
+#[test]
+fn give_up() {
+    let mut solutions = solve(SearchLimits::default());
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should give up");
+    assert_eq!(Some(200), solutions.stats().decisions);
+    assert_eq!(Some(1), solutions.stats().restarts);
+}
+
+#[test]
+fn timeout() {
+    let mut solutions = solve(SearchLimits {
+        timeout: Some(Duration::ZERO),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should be interrupted");
+}
+
+#[test]
+fn cancellation() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let mut solutions = solve(SearchLimits {
+        cancellation: Some(token),
+        ..SearchLimits::default()
+    });
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should be interrupted");
+}
+
+/// Solves the grid with the given limits using the local search solver, giving up after 2 tries of 100 word swaps.
+fn solve(limits: SearchLimits) -> CrosswordSolutions {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let builder = LocalSearchSolverBuilder::new()
+        .with_max_flips(100)
+        .with_max_restarts(1);
+    Crossword::try_from("ABC\n...\n...", &words)
+        .unwrap()
+        .with_limits(limits)
+        .solve_natively_with(Box::new(builder))
+        .unwrap()
+}
@@ -0,0 +1,57 @@
+use croissant_crossword::crossword::{Crossword, CrosswordSolutions, NativeSolverBuilder};
+use croissant_solver_localsearch::LocalSearchSolverBuilder;
+use croissant_testkit::{Solvers, WORDS};
+
+// Local search cannot prove that a grid has no solution: It gives up instead
+croissant_testkit::tests!(
+    Solvers::Native(builder),
+    simple: empty,
+    trivial,
+    partially_prefilled_1x3,
+    partially_prefilled_3x3,
+    with_blocks,
+    impossible_no_candidate,
+    impossible_prefilled_word_not_in_list,
+    duplicate_words
+);
+
+#[test]
+fn trivial_gives_up() {
+    let mut solutions = solve("...\n...\n...", &WORDS);
+    assert_eq!(4, solutions.by_ref().count());
+    assert!(solutions.is_interrupted(), "Search should give up");
+}
+
+#[test]
+fn impossible_no_solution_is_unknown() {
+    let words = [
+        "AAA", "BBB", "CDF", /* should be CDE */
+        "ABC", "ABD", "ABE",
+    ];
+    let mut solutions = solve("ABC\n...\n...", &words);
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should give up");
+}
+
+#[test]
+fn same_seed_same_solutions() {
+    let first: Vec<String> = solve("...\n...\n...", &WORDS).collect();
+    let second: Vec<String> = solve("...\n...\n...", &WORDS).collect();
+    assert_eq!(first, second);
+}
+
+/// Returns the builder of local search solvers, giving up quickly.
+fn builder() -> Box<dyn NativeSolverBuilder> {
+    let builder = LocalSearchSolverBuilder::new()
+        .with_seed(7)
+        .with_max_flips(1_000)
+        .with_max_restarts(20);
+    Box::new(builder)
+}
+
+/// Solves the given grid using the local search solver.
+fn solve(grid: &str, words: &[&str]) -> CrosswordSolutions {
+    let words = croissant_testkit::words(words);
+    let crossword = Crossword::try_from(grid, &words).unwrap();
+    crossword.solve_natively_with(builder()).unwrap()
+}
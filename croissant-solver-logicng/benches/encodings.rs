@@ -0,0 +1,124 @@
+//! Compares the encodings on the grids of the ignored complex tests, in terms of problem size and of time to
+//! find the first solution.
+//!
+//! Run with `cargo bench -p croissant-solver-logicng`.
+
+use std::cell::Cell;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Instant;
+
+use croissant_crossword::crossword::Crossword;
+use croissant_crossword::encoding::{DirectEncoding, Encoding, MddEncoding, WordLevelEncoding};
+use croissant_solver::{ConfigurableSolver, Lit, Model, Solver, SolverConfigurator};
+use croissant_solver_logicng::LogicngSolverBuilder;
+
+/// The benchmarked grids.
+const GRIDS: [(&str, &str); 2] = [
+    ("empty4x4", "....\n....\n....\n...."),
+    ("shaded5x5", "##..#\n#...#\n.....\n#...#\n##.##"),
+];
+
+/// A function creating an [Encoding].
+type EncodingFactory = fn() -> Box<dyn Encoding>;
+
+/// The benchmarked encodings.
+const ENCODINGS: [(&str, EncodingFactory); 3] = [
+    ("direct", || Box::new(DirectEncoding)),
+    ("mdd", || Box::new(MddEncoding)),
+    ("word-level", || Box::new(WordLevelEncoding)),
+];
+
+fn main() {
+    let words = ukacd();
+    println!(
+        "{:<10} {:<10} {:>12} {:>14} {:>12}",
+        "grid", "encoding", "variables", "clauses", "solve time"
+    );
+    for (grid_name, grid) in GRIDS {
+        for (encoding_name, encoding) in ENCODINGS {
+            let (variables, clauses) = count(grid, &words, encoding());
+            let start = Instant::now();
+            let solution = solve(grid, &words, encoding());
+            let elapsed = start.elapsed();
+            assert!(solution.is_some(), "No solution found for {grid_name}");
+            println!(
+                "{grid_name:<10} {encoding_name:<10} {variables:>12} {clauses:>14} {elapsed:>12.2?}"
+            );
+        }
+    }
+}
+
+/// Returns the number of variables and the number of clauses of the given problem, when expressed in conjunctive
+/// normal form using the default [SolverConfigurator] function implementations.
+fn count(grid: &str, words: &[String], encoding: Box<dyn Encoding>) -> (usize, usize) {
+    let counter = ClauseCounter::default();
+    let variables = counter.variables.clone();
+    let clauses = counter.clauses.clone();
+    let crossword = Crossword::try_from(grid, words)
+        .unwrap()
+        .with_encoding(encoding);
+    crossword.solve_with(Box::new(counter)).unwrap();
+    (variables.get(), clauses.get())
+}
+
+/// Solves the given problem using the logic-ng solver, returning the first solution.
+fn solve(grid: &str, words: &[String], encoding: Box<dyn Encoding>) -> Option<String> {
+    let crossword = Crossword::try_from(grid, words)
+        .unwrap()
+        .with_encoding(encoding);
+    let solver = Box::new(LogicngSolverBuilder::new());
+    crossword.solve_with_solver_built_by(solver).unwrap().next()
+}
+
+/// A fake solver which just counts the variables and clauses it is given.
+#[derive(Default)]
+struct ClauseCounter {
+    variables: Rc<Cell<usize>>,
+    clauses: Rc<Cell<usize>>,
+}
+
+impl SolverConfigurator for ClauseCounter {
+    fn allocate_variables(&mut self, variables_count: usize) {
+        self.variables
+            .set(self.variables.get().max(variables_count));
+    }
+
+    // Auxiliary variables of the at-most-one constraints are counted as well.
+    fn variables_count(&self) -> Option<usize> {
+        Some(self.variables.get())
+    }
+
+    fn add_clause(&mut self, _literals: &[Lit]) {
+        self.clauses.set(self.clauses.get() + 1);
+    }
+}
+
+impl Iterator for ClauseCounter {
+    type Item = Model;
+    fn next(&mut self) -> Option<Self::Item> {
+        None
+    }
+}
+
+impl Solver for ClauseCounter {}
+impl ConfigurableSolver for ClauseCounter {}
+
+/// Reads the UKACD word list.
+fn ukacd() -> Vec<String> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("wordlist")
+        .join("UKACD18plus.txt");
+    let file = File::open(path).expect("Test word list not found");
+    BufReader::new(file)
+        .lines()
+        .map(Result::unwrap)
+        .map(|word| word.replace(['-', '\'', '.'], "").to_uppercase())
+        .filter(|word| word.chars().all(|letter| letter.is_ascii_uppercase()))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
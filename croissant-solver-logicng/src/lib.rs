@@ -1,19 +1,35 @@
+use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
-use logicng::datastructures::Model;
+use logicng::datastructures::Model as LogicngModel;
 use logicng::formulas::{CType, EncodedFormula, FormulaFactory, Literal, Variable};
-use logicng::solver::minisat::MiniSat;
+use logicng::solver::minisat::sat::Tristate;
+use logicng::solver::minisat::{MiniSat, MiniSatConfig, SatBuilder};
 
-use croissant_solver::{Solver, SolverBuilder, SolverConfigurator};
+use croissant_solver::{
+    CancellationToken, Lit, Model, ProgressCallback, SearchLimits, SolveOutcome, Solver,
+    SolverBuilder, SolverConfigurator, SolverError, SolverStats, Var,
+};
 
 /// Implementation of [SolverBuilder].
+///
+/// Only the cancellation token of the [SearchLimits] is supported, and it is only checked before each search: MiniSat in
+/// LogicNG offers no way to stop a running search, nor to bound its time or its conflicts. For the same reason, progress
+/// is only reported after each search, and statistics are limited to the solving time.
 pub struct LogicngSolverBuilder {
     /// The helper to create/register boolean formulas.
     formula_factory: Rc<FormulaFactory>,
     /// The created formulas.
     formulas: Vec<EncodedFormula>,
     /// The relevant variables of the problem.
-    relevant_variables: Vec<usize>,
+    relevant_variables: Vec<Var>,
+    /// The seed randomizing the search, if any.
+    seed: Option<u64>,
+    /// The token cancelling the searches, if any.
+    cancellation: Option<CancellationToken>,
+    /// The progress callback, if any, given to the next built solver.
+    progress: RefCell<Option<ProgressCallback>>,
 }
 
 impl Default for LogicngSolverBuilder {
@@ -31,29 +47,52 @@ impl LogicngSolverBuilder {
             formula_factory,
             formulas,
             relevant_variables: Vec::new(),
+            seed: None,
+            cancellation: None,
+            progress: RefCell::new(None),
         }
     }
 
-    /// Converts a raw literal to an [EncodedFormula].
-    fn encoded_formula_from(&self, literal: i32) -> EncodedFormula {
-        EncodedFormula::from(self.literal_from_raw(literal))
+    /// Converts a literal to an [EncodedFormula].
+    fn encoded_formula_from(&self, literal: Lit) -> EncodedFormula {
+        EncodedFormula::from(literal_of(&self.formula_factory, literal))
     }
 
-    /// Converts a raw literal to a [Literal].
-    fn literal_from_raw(&self, literal: i32) -> Literal {
-        let variable_name = literal.abs().to_string();
-        let literal_phase = literal > 0;
-        self.formula_factory
-            .lit(variable_name.as_str(), literal_phase)
+    /// Adds the pseudo-Boolean constraint comparing the weighted sum of the given literals to the given bound.
+    fn add_pb(&mut self, comparator: CType, literals: &[Lit], weights: Vec<i64>, bound: i64) {
+        let lits: Vec<Literal> = literals
+            .iter()
+            .map(|&literal| literal_of(&self.formula_factory, literal))
+            .collect();
+        let formula = self.formula_factory.pbc(comparator, bound, lits, weights);
+        self.formulas.push(formula);
     }
 }
 
 impl SolverConfigurator for LogicngSolverBuilder {
-    fn set_relevant_variables(&mut self, relevant_variables: Vec<usize>) {
+    fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
         self.relevant_variables = relevant_variables;
     }
 
-    fn add_clause(&mut self, literals: &[i32]) {
+    // MiniSat decides on variables in the order they are added when their activities are equal: The formulas are
+    // shuffled on build. The initial phase is random as well.
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    fn set_limits(&mut self, limits: SearchLimits) {
+        self.cancellation = limits.cancellation;
+    }
+
+    fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        limits.timeout.is_none() && limits.conflicts.is_none()
+    }
+
+    fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.progress.replace(Some(callback));
+    }
+
+    fn add_clause(&mut self, literals: &[Lit]) {
         let operands: Vec<EncodedFormula> = literals
             .iter()
             .map(|&literal| self.encoded_formula_from(literal))
@@ -63,10 +102,10 @@ impl SolverConfigurator for LogicngSolverBuilder {
     }
 
     // Overriding default implementation for performance.
-    fn add_exactly_one(&mut self, literals: &[i32]) {
+    fn add_exactly_one(&mut self, literals: &[Lit]) {
         let lits: Vec<Literal> = literals
             .iter()
-            .map(|&literal| self.literal_from_raw(literal))
+            .map(|&literal| literal_of(&self.formula_factory, literal))
             .collect();
         let formula = self
             .formula_factory
@@ -75,10 +114,10 @@ impl SolverConfigurator for LogicngSolverBuilder {
     }
 
     // Overriding default implementation for performance.
-    fn add_at_most_one(&mut self, literals: &[i32]) {
+    fn add_at_most_one(&mut self, literals: &[Lit]) {
         let lits: Vec<Literal> = literals
             .iter()
-            .map(|&literal| self.literal_from_raw(literal))
+            .map(|&literal| literal_of(&self.formula_factory, literal))
             .collect();
         let formula = self
             .formula_factory
@@ -86,8 +125,28 @@ impl SolverConfigurator for LogicngSolverBuilder {
         self.formulas.push(formula);
     }
 
+    // Overriding default implementations for performance: LogicNG encodes pseudo-Boolean constraints itself.
+    fn add_at_most_k(&mut self, literals: &[Lit], k: usize) {
+        let weights = vec![1; literals.len()];
+        self.add_pb(CType::LE, literals, weights, k as i64);
+    }
+
+    fn add_at_least_k(&mut self, literals: &[Lit], k: usize) {
+        let weights = vec![1; literals.len()];
+        self.add_pb(CType::GE, literals, weights, k as i64);
+    }
+
+    fn add_pb_le(&mut self, literals: &[Lit], weights: &[i64], bound: i64) {
+        assert_eq!(
+            literals.len(),
+            weights.len(),
+            "Literals and weights should have the same length"
+        );
+        self.add_pb(CType::LE, literals, weights.to_vec(), bound);
+    }
+
     // Overriding default implementation for performance.
-    fn add_and(&mut self, literal: i32, conjunction: &[i32]) {
+    fn add_and(&mut self, literal: Lit, conjunction: &[Lit]) {
         let and_operands: Vec<EncodedFormula> = conjunction
             .iter()
             .map(|&literal| self.encoded_formula_from(literal))
@@ -100,12 +159,53 @@ impl SolverConfigurator for LogicngSolverBuilder {
 }
 
 impl SolverBuilder for LogicngSolverBuilder {
-    fn build(&self) -> Box<dyn Solver<Item = Vec<i32>>> {
-        Box::new(LogicngSolver::new(
-            &self.formulas,
+    fn build(&self) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+        let Some(seed) = self.seed else {
+            return Ok(Box::new(LogicngSolver::new(
+                &self.formulas,
+                self.formula_factory.clone(),
+                &self.relevant_variables,
+                MiniSatConfig::default(),
+                self.cancellation.clone(),
+                self.progress.take(),
+            )));
+        };
+        let mut random = SplitMix64(seed);
+        let mut formulas = self.formulas.clone();
+        // Fisher-Yates shuffle
+        for i in (1..formulas.len()).rev() {
+            let j = (random.next() % (i as u64 + 1)) as usize;
+            formulas.swap(i, j);
+        }
+        let config = MiniSatConfig::default().initial_phase(random.next().is_multiple_of(2));
+        Ok(Box::new(LogicngSolver::new(
+            &formulas,
             self.formula_factory.clone(),
             &self.relevant_variables,
-        ))
+            config,
+            self.cancellation.clone(),
+            self.progress.take(),
+        )))
+    }
+}
+
+/// Returns the [Literal] registered in the given factory for the given literal: Its variable is named after the index
+/// of the variable.
+fn literal_of(formula_factory: &FormulaFactory, literal: Lit) -> Literal {
+    formula_factory.lit(literal.var().to_string().as_str(), literal.is_positive())
+}
+
+/// A minimal pseudo-random number generator, to shuffle formulas.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Returns the next pseudo-random integer.
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
     }
 }
 
@@ -121,6 +221,12 @@ pub struct LogicngSolver {
     last_solution_literals: Vec<Literal>,
     /// Whether all solutions have been found.
     no_more_solution: bool,
+    /// The token cancelling the searches, if any.
+    cancellation: Option<CancellationToken>,
+    /// The progress callback, if any.
+    progress: Option<ProgressCallback>,
+    /// The time spent searching so far.
+    solving_time: Duration,
 }
 
 impl LogicngSolver {
@@ -128,13 +234,16 @@ impl LogicngSolver {
     fn new(
         formulas: &[EncodedFormula],
         formula_factory: Rc<FormulaFactory>,
-        relevant_variables: &[usize],
+        relevant_variables: &[Var],
+        config: MiniSatConfig,
+        cancellation: Option<CancellationToken>,
+        progress: Option<ProgressCallback>,
     ) -> Self {
-        let mut solver = MiniSat::new();
+        let mut solver = MiniSat::new_with_config(config);
         solver.add_all(formulas, &formula_factory);
         let relevant_variables = relevant_variables
             .iter()
-            .map(|&id| formula_factory.var(id.to_string().as_str()))
+            .map(|&variable| formula_factory.var(variable.to_string().as_str()))
             .collect();
         LogicngSolver {
             solver,
@@ -142,15 +251,37 @@ impl LogicngSolver {
             relevant_variables,
             last_solution_literals: Vec::new(),
             no_more_solution: false,
+            cancellation,
+            progress,
+            solving_time: Duration::ZERO,
         }
     }
 
-    /// Solves the problem. Returns Some [Model] satisfying the problem, or [None] if no solution found.
-    fn solve(&mut self) -> Option<Model> {
-        self.solver.sat();
+    /// Solves the problem. Returns Some [LogicngModel] satisfying the problem, or [None] if no solution found.
+    fn solve(&mut self) -> Option<LogicngModel> {
+        self.timed(|solver| solver.sat());
         self.solver.model(Some(&self.relevant_variables))
     }
 
+    /// Runs the given search on MiniSat, measuring its time and reporting progress afterwards.
+    fn timed(&mut self, search: impl FnOnce(&mut MiniSat) -> Tristate) -> Tristate {
+        let started = Instant::now();
+        let result = search(&mut self.solver);
+        self.solving_time += started.elapsed();
+        let stats = self.stats();
+        if let Some(progress) = self.progress.as_mut() {
+            progress(&stats);
+        }
+        result
+    }
+
+    /// Returns `true` iff the searches have been cancelled.
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
     /// Refutes the last solution, i.e. don't propose the last solution again.
     /// Does nothing if no solution has been found yet.
     fn refute_previous_solution(&mut self) {
@@ -168,44 +299,48 @@ impl LogicngSolver {
         self.last_solution_literals.clear();
     }
 
-    /// Translates solver [Model] to a vector of variables states.
-    fn variable_states_from(&self, model: Model) -> Vec<i32> {
-        let mut literals = vec![0; self.max_relevant_variable() + 1];
+    /// Translates solver [LogicngModel] to a [Model].
+    fn variable_states_from(&self, model: LogicngModel) -> Model {
+        let mut states = Model::new();
         for positive_variable in model.pos() {
-            literals[self.index_of(positive_variable)] = 1;
+            states.set(self.var_of(positive_variable).literal(true));
         }
         for negative_variable in model.neg() {
-            literals[self.index_of(negative_variable)] = -1;
+            states.set(self.var_of(negative_variable).literal(false));
         }
-        literals
-    }
-
-    /// Returns the relevant variable with the biggest id.
-    fn max_relevant_variable(&self) -> usize {
-        self.relevant_variables
-            .iter()
-            .map(|variable| self.index_of(variable))
-            .max()
-            .unwrap_or(0)
+        states
     }
 
-    /// Returns the index of the given [Variable].
-    fn index_of(&self, variable: &Variable) -> usize {
-        // Variable name stores the variable index as a string. See SolverBuilder's add_clause().
-        variable
+    /// Returns the [Var] of the given [Variable].
+    fn var_of(&self, variable: &Variable) -> Var {
+        // Variable name stores the variable index as a string. See literal_of().
+        let index = variable
             .name(&self.formula_factory)
             .parse::<usize>()
-            .unwrap()
-            - 1
+            .unwrap();
+        Var::new(index)
     }
 }
 
 impl Iterator for LogicngSolver {
-    type Item = Vec<i32>;
+    type Item = Model;
 
     fn next(&mut self) -> Option<Self::Item> {
+        match self.solve_next() {
+            SolveOutcome::Sat(solution) => Some(solution),
+            _ => None,
+        }
+    }
+}
+
+// Failed assumptions are not provided: MiniSat computes them but LogicNG does not expose them.
+impl Solver for LogicngSolver {
+    fn solve_next(&mut self) -> SolveOutcome {
         if self.no_more_solution {
-            return None;
+            return SolveOutcome::Unsat;
+        }
+        if self.is_cancelled() {
+            return SolveOutcome::Interrupted;
         }
         self.refute_previous_solution();
 
@@ -213,13 +348,63 @@ impl Iterator for LogicngSolver {
 
         if optional_model.is_none() {
             self.no_more_solution = true;
-            return None;
+            return SolveOutcome::Unsat;
         }
         let model = optional_model.unwrap();
         self.last_solution_literals = model.literals();
         let solution = self.variable_states_from(model);
-        Some(solution)
+        SolveOutcome::Sat(solution)
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[Lit]) -> SolveOutcome {
+        if self.is_cancelled() {
+            return SolveOutcome::Interrupted;
+        }
+        let assumptions: Vec<Literal> = assumptions
+            .iter()
+            .map(|&literal| literal_of(&self.formula_factory, literal))
+            .collect();
+        let sat_builder = SatBuilder::new().assumptions(&assumptions);
+        if self.timed(|solver| solver.sat_with(&sat_builder)) != Tristate::True {
+            return SolveOutcome::Unsat;
+        }
+        let model = self
+            .solver
+            .model(Some(&self.relevant_variables))
+            .expect("Solver is satisfiable but has no model");
+        SolveOutcome::Sat(self.variable_states_from(model))
+    }
+    fn as_configurator(&mut self) -> Option<&mut dyn SolverConfigurator> {
+        Some(self)
+    }
+    fn stats(&self) -> SolverStats {
+        SolverStats {
+            solving_time: self.solving_time,
+            ..SolverStats::default()
+        }
     }
 }
 
-impl Solver for LogicngSolver {}
+// Clauses added between two solutions go directly to MiniSat.
+impl SolverConfigurator for LogicngSolver {
+    fn set_limits(&mut self, limits: SearchLimits) {
+        self.cancellation = limits.cancellation;
+    }
+
+    fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        limits.timeout.is_none() && limits.conflicts.is_none()
+    }
+
+    fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.progress = Some(callback);
+    }
+
+    fn add_clause(&mut self, literals: &[Lit]) {
+        let literals: Vec<Literal> = literals
+            .iter()
+            .map(|&literal| literal_of(&self.formula_factory, literal))
+            .collect();
+        let clause = self.formula_factory.clause(literals.as_slice());
+        self.solver.add(clause, &self.formula_factory);
+    }
+}
@@ -0,0 +1,67 @@
+use croissant_solver::{Lit, Model, SolveOutcome, Solver, SolverBuilder, SolverConfigurator, Var};
+use croissant_solver_logicng::LogicngSolverBuilder;
+
+/// (1 ∨ 2) ∧ (¬1 ∨ 3) ∧ (¬2 ∨ 3)
+fn solver() -> Box<dyn Solver<Item = Model>> {
+    let mut solver_builder = LogicngSolverBuilder::new();
+    solver_builder.set_relevant_variables((1..=3).map(Var::new).collect());
+    solver_builder.add_clause(&literals(&[1, 2]));
+    solver_builder.add_clause(&literals(&[-1, 3]));
+    solver_builder.add_clause(&literals(&[-2, 3]));
+    solver_builder.build().unwrap()
+}
+
+#[test]
+fn sat() {
+    let mut solver = solver();
+
+    assert_eq!(
+        SolveOutcome::Sat(model(&[1, -2, 3])),
+        solver.solve_with_assumptions(&literals(&[1, -2]))
+    );
+}
+
+#[test]
+fn unsat() {
+    let mut solver = solver();
+
+    assert_eq!(
+        SolveOutcome::Unsat,
+        solver.solve_with_assumptions(&literals(&[1, -3]))
+    );
+    assert_eq!(None, solver.failed_assumptions()); // Not supported
+}
+
+#[test]
+fn assumptions_are_temporary() {
+    let mut solver = solver();
+
+    assert_eq!(
+        SolveOutcome::Unsat,
+        solver.solve_with_assumptions(&literals(&[-3]))
+    );
+    assert!(
+        matches!(
+            solver.solve_with_assumptions(&literals(&[3])),
+            SolveOutcome::Sat(_)
+        ),
+        "No solution found"
+    );
+    assert!(solver.next().is_some(), "No solution found");
+}
+
+/// Returns the model of the given raw literals.
+fn model(literals: &[i32]) -> Model {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
+
+/// Returns the literals of the given raw representations.
+fn literals(literals: &[i32]) -> Vec<Lit> {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
@@ -1,8 +1,10 @@
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
 
 use croissant_crossword::crossword::{Crossword, CrosswordSolutions};
+use croissant_crossword::encoding::{DirectEncoding, Encoding, MddEncoding, WordLevelEncoding};
 use croissant_solver_logicng::LogicngSolverBuilder;
 
 #[test]
@@ -28,15 +30,41 @@ fn shaded5x5() {
     );
 }
 
+#[test]
+fn same_solutions_3x3_mdd() {
+    assert_same_solutions_as_direct_encoding("BIZ\nO..\nA..", Box::new(MddEncoding));
+}
+
+#[test]
+fn same_solutions_3x3_word_level() {
+    assert_same_solutions_as_direct_encoding("BIZ\nO..\nA..", Box::new(WordLevelEncoding));
+}
+
+/// Verifies that the given grid has the same solutions with the given encoding as with the direct encoding.
+fn assert_same_solutions_as_direct_encoding(grid: &str, encoding: Box<dyn Encoding>) {
+    let expected: HashSet<String> = solve(grid).collect();
+    let actual: HashSet<String> = solve_using(grid, encoding).collect();
+    assert!(!expected.is_empty(), "Grid should have solutions");
+    assert_eq!(expected, actual);
+}
+
 /// Solves the given grid using the logic-ng solver.
 fn solve(grid: &str) -> CrosswordSolutions {
+    solve_using(grid, Box::new(DirectEncoding))
+}
+
+/// Solves the given grid using the logic-ng solver and the given encoding.
+fn solve_using(grid: &str, encoding: Box<dyn Encoding>) -> CrosswordSolutions {
     let words = ukacd();
-    let crossword = Crossword::try_from(grid, &words).unwrap();
+    let crossword = Crossword::try_from(grid, &words)
+        .unwrap()
+        .with_encoding(encoding);
     let solver = Box::new(LogicngSolverBuilder::new());
-    crossword.solve_with_solver_built_by(solver)
+    crossword.solve_with_solver_built_by(solver).unwrap()
 }
 
 /// Reads the UKACD word list.
+#[allow(clippy::manual_range_contains)]
 fn ukacd() -> Vec<String> {
     let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .parent()
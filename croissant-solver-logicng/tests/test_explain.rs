@@ -0,0 +1,42 @@
+use croissant_crossword::crossword::{Crossword, Explanation};
+use croissant_crossword::pos::Pos;
+use croissant_crossword::slot::Slot;
+use croissant_solver_logicng::LogicngSolverBuilder;
+
+#[test]
+fn solvable() {
+    let explanation = explain("...\n...", ["ABC", "AA", "BB", "CC"]);
+    assert_eq!(None, explanation);
+}
+
+#[test]
+fn prefilled_letter_and_slot() {
+    let explanation = explain("Q..\n#..", ["ABC", "BA", "CA", "AA"]).unwrap();
+
+    assert_eq!([Pos::new(0, 0)], explanation.cells());
+    assert_eq!([Slot::across(0, 3, 0)], explanation.slots());
+    assert_eq!(
+        "Prefilled letter at (0,0) plus slot 1-Across have no compatible words",
+        explanation.to_string()
+    );
+}
+
+#[test]
+fn slot_without_candidate() {
+    let explanation = explain("...\n...", ["ABC"]).unwrap();
+
+    assert!(explanation.cells().is_empty(), "Unexpected cells");
+    assert_eq!([Slot::down(0, 2, 2)], explanation.slots());
+    assert_eq!(
+        "Slot 3-Down has no compatible words",
+        explanation.to_string()
+    );
+}
+
+/// Explains why the given grid has no solution, using the logic-ng solver.
+fn explain<const N: usize>(grid: &str, words: [&str; N]) -> Option<Explanation> {
+    let words_vec: Vec<String> = words.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from(grid, &words_vec).unwrap();
+    let solver = Box::new(LogicngSolverBuilder::new());
+    crossword.explain_with_solver_built_by(solver).unwrap()
+}
@@ -0,0 +1,79 @@
+use croissant_crossword::crossword::Crossword;
+use croissant_solver::{
+    CancellationToken, Lit, SearchLimits, SolveOutcome, SolverBuilder, SolverConfigurator,
+    SolverError, Var,
+};
+use croissant_solver_logicng::LogicngSolverBuilder;
+
+const WORDS: [&str; 6] = ["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"];
+
+#[test]
+fn cancellation() {
+    let token = CancellationToken::new();
+    let mut solver_builder = LogicngSolverBuilder::new();
+    solver_builder.set_relevant_variables(vec![Var::new(1), Var::new(2)]);
+    solver_builder.add_clause(&literals(&[1, 2]));
+    solver_builder.set_limits(SearchLimits {
+        cancellation: Some(token.clone()),
+        ..SearchLimits::default()
+    });
+    let mut solver = solver_builder.build().unwrap();
+    assert!(matches!(solver.solve_next(), SolveOutcome::Sat(_)));
+
+    token.cancel();
+
+    assert_eq!(SolveOutcome::Interrupted, solver.solve_next());
+    assert_eq!(
+        SolveOutcome::Interrupted,
+        solver.solve_with_assumptions(&[Lit::from_raw(1)])
+    );
+    assert_eq!(None, solver.next());
+}
+
+#[test]
+fn crossword_interrupted() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .with_limits(SearchLimits {
+            cancellation: Some(token),
+            ..SearchLimits::default()
+        });
+
+    let mut solutions = crossword
+        .solve_with_solver_built_by(Box::new(LogicngSolverBuilder::new()))
+        .unwrap();
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should be interrupted");
+}
+
+#[test]
+fn crossword_explain_interrupted() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .with_limits(SearchLimits {
+            cancellation: Some(token),
+            ..SearchLimits::default()
+        });
+
+    let explanation = crossword.explain_with_solver_built_by(Box::new(LogicngSolverBuilder::new()));
+
+    assert_eq!(
+        Err(SolverError::new("Search interrupted")),
+        explanation.map(|_| ())
+    );
+}
+
+/// Returns the literals of the given raw representations.
+fn literals(literals: &[i32]) -> Vec<Lit> {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+
+use croissant_crossword::crossword::Crossword;
+use croissant_crossword::encoding::{DirectEncoding, Encoding, MddEncoding};
+use croissant_solver_logicng::LogicngSolverBuilder;
+
+#[test]
+fn same_seed_same_solution() {
+    for seed in 0..5 {
+        assert_eq!(
+            first_solution(Some(seed), Box::new(DirectEncoding)),
+            first_solution(Some(seed), Box::new(DirectEncoding))
+        );
+    }
+}
+
+#[test]
+fn different_seeds_different_solutions() {
+    let direct_solutions: HashSet<String> = (0..20)
+        .map(|seed| first_solution(Some(seed), Box::new(DirectEncoding)))
+        .collect();
+    let mdd_solutions: HashSet<String> = (0..20)
+        .map(|seed| first_solution(Some(seed), Box::new(MddEncoding)))
+        .collect();
+
+    assert!(direct_solutions.len() > 1, "Seed has no effect");
+    assert!(mdd_solutions.len() > 1, "Seed has no effect");
+}
+
+#[test]
+fn no_seed_same_solution() {
+    assert_eq!(
+        first_solution(None, Box::new(DirectEncoding)),
+        first_solution(None, Box::new(DirectEncoding))
+    );
+}
+
+/// Returns the first solution of a 2x2 grid accepting any combination of the letters A to D, using the LogicNG solver.
+fn first_solution(seed: Option<u64>, encoding: Box<dyn Encoding>) -> String {
+    let letters = ['A', 'B', 'C', 'D'];
+    let words: Vec<String> = letters
+        .iter()
+        .flat_map(|first| letters.iter().map(move |second| format!("{first}{second}")))
+        .collect();
+    let mut crossword = Crossword::try_from("..\n..", &words)
+        .unwrap()
+        .with_encoding(encoding);
+    if let Some(seed) = seed {
+        crossword = crossword.with_seed(seed);
+    }
+    crossword
+        .solve_with_solver_built_by(Box::new(LogicngSolverBuilder::new()))
+        .unwrap()
+        .next()
+        .unwrap()
+}
@@ -0,0 +1,51 @@
+use croissant_crossword::crossword::Crossword;
+use croissant_solver_logicng::LogicngSolverBuilder;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const WORDS: [&str; 6] = ["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"];
+
+#[test]
+fn stats() {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("...\n...\n...", &words).unwrap();
+    let mut solutions = crossword
+        .solve_with_solver_built_by(Box::new(LogicngSolverBuilder::new()))
+        .unwrap();
+    solutions.next();
+
+    let stats = solutions.stats();
+
+    assert!(stats.variables > 0, "Variables should be counted");
+    assert!(stats.clauses > 0, "Clauses should be counted");
+    assert!(
+        stats.encoding_time > Duration::ZERO,
+        "Encoding should take time"
+    );
+    assert!(
+        stats.solving_time > Duration::ZERO,
+        "Search should take time"
+    );
+    assert_eq!(None, stats.conflicts);
+}
+
+#[test]
+fn progress() {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let reports = Arc::new(AtomicUsize::new(0));
+    let reports_in_callback = reports.clone();
+    let crossword = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .with_progress(Box::new(move |_| {
+            reports_in_callback.fetch_add(1, Ordering::Relaxed);
+        }));
+
+    let solutions = crossword
+        .solve_with_solver_built_by(Box::new(LogicngSolverBuilder::new()))
+        .unwrap();
+
+    // 4 solutions, and a last search finding no more solution
+    assert_eq!(4, solutions.count());
+    assert_eq!(5, reports.load(Ordering::Relaxed));
+}
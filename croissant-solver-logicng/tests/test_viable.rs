@@ -0,0 +1,9 @@
+use croissant_solver::SolverFactory;
+use croissant_solver_logicng::LogicngSolverBuilder;
+
+croissant_testkit::viable_tests!(factory);
+
+/// Returns the factory of LogicNG solvers.
+fn factory() -> SolverFactory {
+    SolverFactory::Builder(Box::new(|| Box::new(LogicngSolverBuilder::new())))
+}
@@ -0,0 +1,809 @@
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use croissant_solver::{
+    add_at_least_k_clauses_to, add_at_most_k_clauses_to, add_pb_le_clauses_to, AtMostOneEncoding,
+    CancellationToken, Lit, Model, ProgressCallback, SearchLimits, SolveOutcome, Solver,
+    SolverBuilder, SolverConfigurator, SolverError, SolverFactory, SolverStats, Var,
+};
+
+/// How often the members are checked while waiting for their answers, e.g. to notice a cancellation.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Creates the solver of a member with the given factory, configured with the given seed, limits and progress
+/// callback, and containing the given problem.
+fn create_member_solver(
+    factory: &SolverFactory,
+    problem: &Problem,
+    seed: u64,
+    limits: SearchLimits,
+    progress: Option<ProgressCallback>,
+) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+    factory.create(|configurator| {
+        configurator.set_seed(seed);
+        configurator.set_limits(limits);
+        if let Some(progress) = progress {
+            configurator.set_progress_callback(progress);
+        }
+        problem.replay_to(configurator);
+    })
+}
+
+/// A constraint of the problem, as given to the portfolio.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Constraint {
+    Clause(Vec<Lit>),
+    ExactlyOne(Vec<Lit>),
+    AtMostOne(Vec<Lit>),
+    AtMostK(Vec<Lit>, usize),
+    AtLeastK(Vec<Lit>, usize),
+    PbLe(Vec<Lit>, Vec<i64>, i64),
+    And(Lit, Vec<Lit>),
+}
+
+/// The problem given to the portfolio, as plain data shared by the members.
+#[derive(Clone, Debug, Default)]
+struct Problem {
+    /// The number of variables, if given.
+    variables_count: Option<usize>,
+    /// The relevant variables.
+    relevant_variables: Vec<Var>,
+    /// The constraints, in the order they were given.
+    constraints: Vec<Constraint>,
+}
+
+impl Problem {
+    /// Gives this problem to the given configurator.
+    fn replay_to(&self, configurator: &mut dyn SolverConfigurator) {
+        if let Some(variables_count) = self.variables_count {
+            configurator.allocate_variables(variables_count);
+        }
+        configurator.set_relevant_variables(self.relevant_variables.clone());
+        for constraint in &self.constraints {
+            match constraint {
+                Constraint::Clause(literals) => configurator.add_clause(literals),
+                Constraint::ExactlyOne(literals) => configurator.add_exactly_one(literals),
+                Constraint::AtMostOne(literals) => configurator.add_at_most_one(literals),
+                Constraint::AtMostK(literals, k) => configurator.add_at_most_k(literals, *k),
+                Constraint::AtLeastK(literals, k) => configurator.add_at_least_k(literals, *k),
+                Constraint::PbLe(literals, weights, bound) => {
+                    configurator.add_pb_le(literals, weights, *bound)
+                }
+                Constraint::And(literal, conjunction) => {
+                    configurator.add_and(*literal, conjunction)
+                }
+            }
+        }
+    }
+
+    /// Returns the state of the relevant variables in the given model, or of all its variables if no variable has
+    /// been declared relevant. Models of different members may differ on the other variables.
+    fn projection_of(&self, model: &Model) -> Vec<bool> {
+        if self.relevant_variables.is_empty() {
+            return model.literals().map(Lit::is_positive).collect();
+        }
+        self.relevant_variables
+            .iter()
+            .map(|&variable| model.is_true(variable))
+            .collect()
+    }
+
+    /// Returns the clause excluding the given model, i.e. the negation of its relevant variables.
+    fn blocking_clause_of(&self, model: &Model) -> Vec<Lit> {
+        if self.relevant_variables.is_empty() {
+            return model.literals().map(|literal| -literal).collect();
+        }
+        self.relevant_variables
+            .iter()
+            .map(|&variable| variable.literal(!model.is_true(variable)))
+            .collect()
+    }
+}
+
+/// A configurator dropping the clauses, to count the auxiliary variables of an encoding.
+struct AuxiliaryVariablesCounter;
+
+impl SolverConfigurator for AuxiliaryVariablesCounter {
+    fn add_clause(&mut self, _literals: &[Lit]) {
+        // Only auxiliary variables are counted
+    }
+}
+
+/// Implementation of [SolverBuilder] creating a portfolio: The problem is given once to the builder, then the built
+/// solver runs all the members on separate threads, each with a different seed, and answers with the first member to
+/// answer. Each member is created in its own thread, see [SolverFactory]. Building fails if no member can be created.
+///
+/// Each search is a race: The first member finding a new model wins and the other members are cancelled, see
+/// [SearchLimits::cancellation]. Models already reported are never reported again: They are added as clauses to the
+/// members able to take clauses between two searches, see [Solver::as_configurator], and filtered out for the others.
+///
+/// Members are given the [SearchLimits] and the progress callback of the portfolio. Member *i* is given the seed of the
+/// portfolio, or 0, plus *i*.
+///
+/// Members may encode the *at-most-one* and *exactly-one* constraints with auxiliary variables: The
+/// [variables count](SolverConfigurator::variables_count) of the portfolio includes enough variables for them, whatever
+/// the members.
+pub struct PortfolioSolverBuilder {
+    /// The factories of the members.
+    members: Vec<Arc<SolverFactory>>,
+    /// The problem.
+    problem: Arc<Problem>,
+    /// The biggest variable allocated or used so far.
+    max_variable: usize,
+    /// The number of variables reserved for the auxiliary variables of the members.
+    reserved_variables: usize,
+    /// The seed of the portfolio, if any.
+    seed: Option<u64>,
+    /// The limits of the searches.
+    limits: SearchLimits,
+    /// The progress callback, if any, shared by the members.
+    progress: Option<Arc<Mutex<ProgressCallback>>>,
+}
+
+impl Default for PortfolioSolverBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PortfolioSolverBuilder {
+    /// Creates an instance, without any member.
+    pub fn new() -> Self {
+        PortfolioSolverBuilder {
+            members: Vec::new(),
+            problem: Arc::new(Problem::default()),
+            max_variable: 0,
+            reserved_variables: 0,
+            seed: None,
+            limits: SearchLimits::default(),
+            progress: None,
+        }
+    }
+
+    /// Adds a member created by the given factory.
+    pub fn with_member(mut self, factory: SolverFactory) -> Self {
+        self.members.push(Arc::new(factory));
+        self
+    }
+
+    /// Returns the problem, for modification.
+    fn problem(&mut self) -> &mut Problem {
+        Arc::make_mut(&mut self.problem)
+    }
+
+    /// Records the variables of the given literals.
+    fn record(&mut self, literals: &[Lit]) {
+        let max_variable = literals.iter().map(|literal| literal.var().index()).max();
+        self.max_variable = self.max_variable.max(max_variable.unwrap_or(0));
+    }
+
+    /// Reserves the auxiliary variables a member may use to encode a constraint with the given default encoding of
+    /// [SolverConfigurator], i.e. a function adding the clauses of the constraint from the given free variable and
+    /// returning the next free variable.
+    fn reserve_auxiliary_variables(
+        &mut self,
+        encoding: impl FnOnce(usize, &mut AuxiliaryVariablesCounter) -> usize,
+    ) {
+        let next_variable = self.max_variable + self.reserved_variables + 1;
+        self.reserved_variables +=
+            encoding(next_variable, &mut AuxiliaryVariablesCounter) - next_variable;
+    }
+}
+
+impl SolverConfigurator for PortfolioSolverBuilder {
+    fn allocate_variables(&mut self, variables_count: usize) {
+        self.max_variable = self.max_variable.max(variables_count);
+        self.problem().variables_count = Some(variables_count);
+    }
+
+    // Members number their auxiliary variables from their own count, which never exceeds the one of the portfolio.
+    fn variables_count(&self) -> Option<usize> {
+        Some(self.max_variable + self.reserved_variables)
+    }
+
+    fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
+        self.problem().relevant_variables = relevant_variables;
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    fn set_limits(&mut self, limits: SearchLimits) {
+        self.limits = limits;
+    }
+
+    // The portfolio cancels its members itself, the other limits are up to them.
+    fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        let limits = SearchLimits {
+            cancellation: None,
+            ..limits.clone()
+        };
+        self.members
+            .iter()
+            .all(|member| member.supports_limits(&limits))
+    }
+
+    fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.progress = Some(Arc::new(Mutex::new(callback)));
+    }
+
+    fn add_clause(&mut self, literals: &[Lit]) {
+        self.record(literals);
+        let constraint = Constraint::Clause(literals.to_vec());
+        self.problem().constraints.push(constraint);
+    }
+
+    fn add_exactly_one(&mut self, literals: &[Lit]) {
+        self.record(literals);
+        let encoding = AtMostOneEncoding::auto(literals.len());
+        self.reserve_auxiliary_variables(|next_variable, counter| {
+            encoding.add_to(literals, next_variable, counter)
+        });
+        let constraint = Constraint::ExactlyOne(literals.to_vec());
+        self.problem().constraints.push(constraint);
+    }
+
+    fn add_at_most_one(&mut self, literals: &[Lit]) {
+        self.record(literals);
+        let encoding = AtMostOneEncoding::auto(literals.len());
+        self.reserve_auxiliary_variables(|next_variable, counter| {
+            encoding.add_to(literals, next_variable, counter)
+        });
+        let constraint = Constraint::AtMostOne(literals.to_vec());
+        self.problem().constraints.push(constraint);
+    }
+
+    fn add_at_most_k(&mut self, literals: &[Lit], k: usize) {
+        self.record(literals);
+        self.reserve_auxiliary_variables(|next_variable, counter| {
+            add_at_most_k_clauses_to(literals, k, next_variable, counter)
+        });
+        let constraint = Constraint::AtMostK(literals.to_vec(), k);
+        self.problem().constraints.push(constraint);
+    }
+
+    fn add_at_least_k(&mut self, literals: &[Lit], k: usize) {
+        self.record(literals);
+        self.reserve_auxiliary_variables(|next_variable, counter| {
+            add_at_least_k_clauses_to(literals, k, next_variable, counter)
+        });
+        let constraint = Constraint::AtLeastK(literals.to_vec(), k);
+        self.problem().constraints.push(constraint);
+    }
+
+    fn add_pb_le(&mut self, literals: &[Lit], weights: &[i64], bound: i64) {
+        self.record(literals);
+        self.reserve_auxiliary_variables(|next_variable, counter| {
+            add_pb_le_clauses_to(literals, weights, bound, next_variable, counter)
+        });
+        let constraint = Constraint::PbLe(literals.to_vec(), weights.to_vec(), bound);
+        self.problem().constraints.push(constraint);
+    }
+
+    fn add_and(&mut self, literal: Lit, conjunction: &[Lit]) {
+        self.record(&[literal]);
+        self.record(conjunction);
+        let constraint = Constraint::And(literal, conjunction.to_vec());
+        self.problem().constraints.push(constraint);
+    }
+}
+
+impl SolverBuilder for PortfolioSolverBuilder {
+    /// Builds the portfolio, starting one thread per member, and waits for the first member to create its solver.
+    /// Returns an error if there is no member, or the error of the first member failing to create its solver if no
+    /// member succeeds.
+    ///
+    /// Members failing to create their solver or to search just stop: The portfolio goes on with the others, and
+    /// searches fail once all the members have stopped, one of them at least having failed to search.
+    fn build(&self) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+        if self.members.is_empty() {
+            return Err(SolverError::new("Portfolio has no member"));
+        }
+        let (reports_sender, reports) = mpsc::channel();
+        let mut members: Vec<Member> = self
+            .members
+            .iter()
+            .enumerate()
+            .map(|(index, factory)| {
+                let seed = self.seed.unwrap_or(0).wrapping_add(index as u64);
+                let limits = SearchLimits {
+                    cancellation: None,
+                    ..self.limits.clone()
+                };
+                let progress = self.progress.clone().map(|progress| {
+                    let callback: ProgressCallback = Box::new(move |stats| {
+                        if let Ok(mut progress) = progress.lock() {
+                            progress(stats);
+                        }
+                    });
+                    callback
+                });
+                let configuration = MemberConfiguration {
+                    index,
+                    factory: factory.clone(),
+                    problem: self.problem.clone(),
+                    seed,
+                    limits,
+                    progress,
+                };
+                Member::start(configuration, reports_sender.clone())
+            })
+            .collect();
+        // Only the members may report from now on, so that waiting stops if they all stop
+        drop(reports_sender);
+        let mut first_error = None;
+        loop {
+            match reports.recv() {
+                Ok(Report::Built(_, Ok(()))) => break,
+                Ok(Report::Built(member, Err(error))) => {
+                    members[member].dead = true;
+                    first_error.get_or_insert(error);
+                    if members.iter().all(|member| member.dead) {
+                        return Err(first_error.unwrap());
+                    }
+                }
+                // No search before the first member is created
+                Ok(Report::Answered(_)) => unreachable!("Search without solver"),
+                Err(_) => {
+                    return Err(first_error
+                        .unwrap_or_else(|| SolverError::new("Portfolio members stopped")));
+                }
+            }
+        }
+        Ok(Box::new(PortfolioSolver {
+            members,
+            reports,
+            problem: self.problem.clone(),
+            reported: Vec::new(),
+            reported_projections: HashSet::new(),
+            exhausted: false,
+            round: 0,
+            round_cancellation: CancellationToken::new(),
+            cancellation: self.limits.cancellation.clone(),
+            stats: SolverStats::default(),
+            error: None,
+        }))
+    }
+}
+
+/// What a member needs to create its solver.
+struct MemberConfiguration {
+    /// The index of the member.
+    index: usize,
+    /// The factory of the solver.
+    factory: Arc<SolverFactory>,
+    /// The problem.
+    problem: Arc<Problem>,
+    /// The seed of the solver.
+    seed: u64,
+    /// The limits of the searches, without cancellation token: Each search has its own.
+    limits: SearchLimits,
+    /// The progress callback, if any.
+    progress: Option<ProgressCallback>,
+}
+
+/// A request to search a new model.
+struct Search {
+    /// The round of the search.
+    round: u64,
+    /// The models reported since the last search of the member, to exclude.
+    reported: Vec<Model>,
+    /// The token cancelling this search.
+    cancellation: CancellationToken,
+}
+
+/// A message of a member to the portfolio.
+enum Report {
+    /// The result of the creation of the solver of the given member.
+    Built(usize, Result<(), SolverError>),
+    /// The answer to a search.
+    Answered(Answer),
+}
+
+/// The answer of a member to a [Search].
+struct Answer {
+    /// The index of the member.
+    member: usize,
+    /// The round of the search.
+    round: u64,
+    /// The outcome of the search.
+    outcome: SolveOutcome,
+    /// The statistics of the member after the search.
+    stats: SolverStats,
+}
+
+/// A member of a running portfolio, as seen from the portfolio.
+struct Member {
+    /// The channel to send the searches to the member.
+    searches: Sender<Search>,
+    /// The thread of the member.
+    thread: JoinHandle<()>,
+    /// Whether the member is searching.
+    busy: bool,
+    /// Whether the member has stopped, e.g. panicked.
+    dead: bool,
+    /// The last round in which a search of the member has been interrupted, if any.
+    interrupted_round: Option<u64>,
+    /// The number of reported models already sent to the member.
+    synced: usize,
+}
+
+impl Member {
+    /// Starts the thread of a member.
+    fn start(configuration: MemberConfiguration, reports: Sender<Report>) -> Self {
+        let (searches, searches_receiver) = mpsc::channel();
+        let thread = thread::spawn(move || Self::run(configuration, searches_receiver, reports));
+        Member {
+            searches,
+            thread,
+            busy: false,
+            dead: false,
+            interrupted_round: None,
+            synced: 0,
+        }
+    }
+
+    /// The loop of the member thread: Creates the solver and reports the result, then answers the searches until the
+    /// portfolio is dropped.
+    fn run(
+        configuration: MemberConfiguration,
+        searches: Receiver<Search>,
+        reports: Sender<Report>,
+    ) {
+        let MemberConfiguration {
+            index,
+            factory,
+            problem,
+            seed,
+            limits,
+            progress,
+        } = configuration;
+        let mut solver =
+            match create_member_solver(&factory, &problem, seed, limits.clone(), progress) {
+                Ok(solver) => solver,
+                Err(error) => {
+                    // Seen as a dead member by the portfolio
+                    let _ = reports.send(Report::Built(index, Err(error)));
+                    return;
+                }
+            };
+        if reports.send(Report::Built(index, Ok(()))).is_err() {
+            // Portfolio is gone
+            return;
+        }
+        for search in searches {
+            if let Some(configurator) = solver.as_configurator() {
+                for model in &search.reported {
+                    configurator.add_clause(&problem.blocking_clause_of(model));
+                }
+                configurator.set_limits(SearchLimits {
+                    cancellation: Some(search.cancellation),
+                    ..limits.clone()
+                });
+            }
+            let outcome = solver.solve_next();
+            let answer = Answer {
+                member: index,
+                round: search.round,
+                outcome,
+                stats: solver.stats(),
+            };
+            if reports.send(Report::Answered(answer)).is_err() {
+                // Portfolio is gone
+                break;
+            }
+        }
+    }
+}
+
+/// The solver built by [PortfolioSolverBuilder].
+///
+/// Dropping it cancels the running searches; Member threads stop as soon as their search stops. They are not waited
+/// for: Members unable to be cancelled may run a bit longer.
+struct PortfolioSolver {
+    /// The members.
+    members: Vec<Member>,
+    /// The channel receiving the reports of the members.
+    reports: Receiver<Report>,
+    /// The problem.
+    problem: Arc<Problem>,
+    /// The models reported so far.
+    reported: Vec<Model>,
+    /// The projections of the models reported so far, see [Problem::projection_of].
+    reported_projections: HashSet<Vec<bool>>,
+    /// Whether there is no model left.
+    exhausted: bool,
+    /// The current round, i.e. the number of calls to [Solver::solve_next] so far.
+    round: u64,
+    /// The token cancelling the searches of the current round.
+    round_cancellation: CancellationToken,
+    /// The token cancelling the searches of the portfolio, if any.
+    cancellation: Option<CancellationToken>,
+    /// The statistics of the member which gave the last model.
+    stats: SolverStats,
+    /// The first error of a member which failed to search, if any.
+    error: Option<SolverError>,
+}
+
+impl PortfolioSolver {
+    /// Sends a search to the idle members which have not been interrupted in the current round. Returns `true` iff at
+    /// least one member is searching.
+    fn dispatch(&mut self) -> bool {
+        for member in self.members.iter_mut() {
+            if member.busy || member.dead || member.interrupted_round == Some(self.round) {
+                continue;
+            }
+            let search = Search {
+                round: self.round,
+                reported: self.reported[member.synced..].to_vec(),
+                cancellation: self.round_cancellation.clone(),
+            };
+            if member.searches.send(search).is_err() {
+                member.dead = true;
+                continue;
+            }
+            member.synced = self.reported.len();
+            member.busy = true;
+        }
+        self.members.iter().any(|member| member.busy)
+    }
+
+    /// Waits for the next answer. Returns [None] if the portfolio is cancelled or if all the members have stopped.
+    fn next_answer(&mut self) -> Option<Answer> {
+        loop {
+            if self
+                .cancellation
+                .as_ref()
+                .is_some_and(CancellationToken::is_cancelled)
+            {
+                return None;
+            }
+            match self.reports.recv_timeout(POLL_INTERVAL) {
+                Ok(Report::Answered(answer)) => return Some(answer),
+                Ok(Report::Built(_, Ok(()))) => {}
+                Ok(Report::Built(member, Err(_))) => {
+                    let member = &mut self.members[member];
+                    member.busy = false;
+                    member.dead = true;
+                    if !self.members.iter().any(|member| member.busy) {
+                        return None;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    for member in self.members.iter_mut() {
+                        if member.busy && member.thread.is_finished() {
+                            member.busy = false;
+                            member.dead = true;
+                        }
+                    }
+                    if !self.members.iter().any(|member| member.busy) {
+                        return None;
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+}
+
+impl Iterator for PortfolioSolver {
+    type Item = Model;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.solve_next() {
+            SolveOutcome::Sat(model) => Some(model),
+            _ => None,
+        }
+    }
+}
+
+impl Solver for PortfolioSolver {
+    fn solve_next(&mut self) -> SolveOutcome {
+        if self.exhausted {
+            return SolveOutcome::Unsat;
+        }
+        self.round += 1;
+        self.round_cancellation = CancellationToken::new();
+        while self.dispatch() {
+            let Some(answer) = self.next_answer() else {
+                break;
+            };
+            let member = &mut self.members[answer.member];
+            member.busy = false;
+            match answer.outcome {
+                SolveOutcome::Sat(model) => {
+                    if self
+                        .reported_projections
+                        .insert(self.problem.projection_of(&model))
+                    {
+                        self.round_cancellation.cancel();
+                        self.reported.push(model.clone());
+                        self.stats = answer.stats;
+                        return SolveOutcome::Sat(model);
+                    }
+                    // Already reported by another member: Search again
+                }
+                SolveOutcome::Unsat => {
+                    // All the models of the member have been reported, or excluded because reported
+                    self.round_cancellation.cancel();
+                    self.exhausted = true;
+                    return SolveOutcome::Unsat;
+                }
+                SolveOutcome::Interrupted | SolveOutcome::Unsupported => {
+                    if answer.round == self.round {
+                        member.interrupted_round = Some(self.round);
+                    }
+                    // Otherwise, cancelled at the end of a previous round: Search again
+                }
+                SolveOutcome::Failed(error) => {
+                    // The portfolio goes on with the others
+                    member.dead = true;
+                    self.error.get_or_insert(error);
+                }
+            }
+        }
+        self.round_cancellation.cancel();
+        match &self.error {
+            Some(error) if self.members.iter().all(|member| member.dead) => {
+                SolveOutcome::Failed(error.clone())
+            }
+            _ => SolveOutcome::Interrupted,
+        }
+    }
+
+    fn stats(&self) -> SolverStats {
+        self.stats.clone()
+    }
+}
+
+impl Drop for PortfolioSolver {
+    fn drop(&mut self) {
+        self.round_cancellation.cancel();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct TestSolverConfigurator {
+        calls: Vec<String>,
+    }
+
+    impl SolverConfigurator for TestSolverConfigurator {
+        fn allocate_variables(&mut self, variables_count: usize) {
+            self.calls.push(format!("allocate {variables_count}"));
+        }
+        fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
+            let relevant_variables: Vec<usize> =
+                relevant_variables.into_iter().map(Var::index).collect();
+            self.calls.push(format!("relevant {relevant_variables:?}"));
+        }
+        fn add_clause(&mut self, literals: &[Lit]) {
+            self.calls.push(format!("clause {:?}", raw(literals)));
+        }
+        fn add_exactly_one(&mut self, literals: &[Lit]) {
+            self.calls.push(format!("exactly one {:?}", raw(literals)));
+        }
+        fn add_and(&mut self, literal: Lit, conjunction: &[Lit]) {
+            self.calls
+                .push(format!("and {literal} {:?}", raw(conjunction)));
+        }
+    }
+
+    #[test]
+    fn replay() {
+        let mut portfolio = PortfolioSolverBuilder::new();
+        portfolio.allocate_variables(4);
+        portfolio.set_relevant_variables(vec![Var::new(1), Var::new(2)]);
+        portfolio.add_clause(&literals(&[1, -2]));
+        portfolio.add_exactly_one(&literals(&[1, 2, 3]));
+        portfolio.add_and(Lit::from_raw(4), &literals(&[1, -3]));
+        let mut configurator = TestSolverConfigurator::default();
+
+        portfolio.problem.replay_to(&mut configurator);
+
+        assert_eq!(
+            vec![
+                "allocate 4",
+                "relevant [1, 2]",
+                "clause [1, -2]",
+                "exactly one [1, 2, 3]",
+                "and 4 [1, -3]"
+            ],
+            configurator.calls
+        );
+    }
+
+    #[test]
+    fn variables_count_with_auxiliary_variables() {
+        let mut portfolio = PortfolioSolverBuilder::new();
+        let literals: Vec<Lit> = (1..=40).map(|variable| Var::new(variable).into()).collect();
+        portfolio.allocate_variables(40);
+
+        portfolio.add_at_most_one(&literals);
+        portfolio.add_clause(&[Lit::from_raw(-41)]);
+
+        let auxiliary_count = AtMostOneEncoding::auto(40).auxiliary_variable_count(40);
+        assert!(auxiliary_count > 0, "Auxiliary variables expected");
+        assert_eq!(Some(41 + auxiliary_count), portfolio.variables_count());
+    }
+
+    #[test]
+    fn projection_and_blocking_clause() {
+        let problem = Problem {
+            relevant_variables: vec![Var::new(1), Var::new(3)],
+            ..Problem::default()
+        };
+        let model: Model = [1, -2, -3, 4].into_iter().map(Lit::from_raw).collect();
+
+        assert_eq!(vec![true, false], problem.projection_of(&model));
+        assert_eq!(literals(&[-1, 3]), problem.blocking_clause_of(&model));
+    }
+
+    #[test]
+    fn projection_and_blocking_clause_without_relevant_variables() {
+        let problem = Problem::default();
+        let model: Model = [1, -2].into_iter().map(Lit::from_raw).collect();
+
+        assert_eq!(vec![true, false], problem.projection_of(&model));
+        assert_eq!(literals(&[-1, 2]), problem.blocking_clause_of(&model));
+    }
+
+    /// A builder failing with the given message.
+    struct FailingSolverBuilder(&'static str);
+    impl SolverConfigurator for FailingSolverBuilder {
+        fn add_clause(&mut self, _literals: &[Lit]) {
+            // Fails anyway
+        }
+    }
+    impl SolverBuilder for FailingSolverBuilder {
+        fn build(&self) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+            Err(SolverError::new(self.0))
+        }
+    }
+
+    #[test]
+    fn build_with_failing_members() {
+        let portfolio = PortfolioSolverBuilder::new()
+            .with_member(SolverFactory::Builder(Box::new(|| {
+                Box::new(FailingSolverBuilder("Cannot build solver"))
+            })))
+            .with_member(SolverFactory::Builder(Box::new(|| {
+                Box::new(FailingSolverBuilder("Cannot build solver"))
+            })));
+
+        assert_eq!(
+            Some(SolverError::new("Cannot build solver")),
+            portfolio.build().err()
+        );
+    }
+
+    #[test]
+    fn build_without_member() {
+        let portfolio = PortfolioSolverBuilder::new();
+
+        assert_eq!(
+            Some(SolverError::new("Portfolio has no member")),
+            portfolio.build().err()
+        );
+    }
+
+    /// Returns the literals of the given raw representations.
+    fn literals(literals: &[i32]) -> Vec<Lit> {
+        literals
+            .iter()
+            .map(|&literal| Lit::from_raw(literal))
+            .collect()
+    }
+
+    /// Returns the raw representations of the given literals.
+    fn raw(literals: &[Lit]) -> Vec<i32> {
+        literals.iter().map(|&literal| i32::from(literal)).collect()
+    }
+}
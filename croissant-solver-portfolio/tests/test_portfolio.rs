@@ -0,0 +1,136 @@
+use croissant_crossword::crossword::{Crossword, CrosswordSolutions};
+use croissant_crossword::encoding::MddEncoding;
+use croissant_solver::{
+    CancellationToken, Lit, Model, SearchLimits, Solver, SolverBuilder, SolverConfigurator,
+    SolverError, SolverFactory,
+};
+use croissant_solver_cadical::CadicalSolver;
+use croissant_solver_logicng::LogicngSolverBuilder;
+use croissant_solver_portfolio::PortfolioSolverBuilder;
+use std::collections::HashSet;
+
+const WORDS: [&str; 6] = ["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"];
+
+/// Returns a portfolio of a CaDiCaL solver and two LogicNG solvers.
+fn portfolio() -> PortfolioSolverBuilder {
+    PortfolioSolverBuilder::new()
+        .with_member(SolverFactory::Configurable(Box::new(|| {
+            Box::new(CadicalSolver::new())
+        })))
+        .with_member(SolverFactory::Builder(Box::new(|| {
+            Box::new(LogicngSolverBuilder::new())
+        })))
+        .with_member(SolverFactory::Builder(Box::new(|| {
+            Box::new(LogicngSolverBuilder::new())
+        })))
+}
+
+/// Returns the solutions of the given grid, using the portfolio and the given limits.
+fn solutions(grid: &str, limits: SearchLimits) -> CrosswordSolutions {
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from(grid, &words)
+        .unwrap()
+        .with_encoding(Box::new(MddEncoding))
+        .with_limits(limits);
+    crossword
+        .solve_with_solver_built_by(Box::new(portfolio()))
+        .unwrap()
+}
+
+#[test]
+fn all_solutions_without_duplicate() {
+    let solutions: Vec<String> = solutions("...\n...\n...", SearchLimits::default()).collect();
+
+    let distinct_solutions: HashSet<&String> = solutions.iter().collect();
+    assert_eq!(4, solutions.len());
+    assert_eq!(4, distinct_solutions.len());
+}
+
+#[test]
+fn first_solution() {
+    let mut solutions = solutions("..C\n...\n...", SearchLimits::default());
+
+    assert_eq!(Some("ABC\nABD\nABE".to_string()), solutions.next());
+    assert_eq!(None, solutions.next());
+    assert!(
+        !solutions.is_interrupted(),
+        "Search should not be interrupted"
+    );
+}
+
+#[test]
+fn no_solution() {
+    let mut solutions = solutions("Z..\n...\n...", SearchLimits::default());
+
+    assert_eq!(None, solutions.next());
+    assert!(
+        !solutions.is_interrupted(),
+        "Search should not be interrupted"
+    );
+}
+
+#[test]
+fn cancelled() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let limits = SearchLimits {
+        cancellation: Some(token),
+        ..SearchLimits::default()
+    };
+
+    let mut solutions = solutions("...\n...\n...", limits);
+
+    assert_eq!(None, solutions.next());
+    assert!(solutions.is_interrupted(), "Search should be interrupted");
+}
+
+#[test]
+fn failing_member() {
+    let portfolio = PortfolioSolverBuilder::new()
+        .with_member(SolverFactory::Builder(Box::new(|| {
+            Box::new(FailingSolverBuilder {})
+        })))
+        .with_member(SolverFactory::Configurable(Box::new(|| {
+            Box::new(CadicalSolver::new())
+        })));
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("..C\n...\n...", &words).unwrap();
+
+    let mut solutions = crossword
+        .solve_with_solver_built_by(Box::new(portfolio))
+        .unwrap();
+
+    assert_eq!(Some("ABC\nABD\nABE".to_string()), solutions.next());
+    assert_eq!(None, solutions.next());
+    assert!(
+        !solutions.is_interrupted(),
+        "Search should not be interrupted"
+    );
+}
+
+#[test]
+fn all_members_failing() {
+    let portfolio =
+        PortfolioSolverBuilder::new().with_member(SolverFactory::Builder(Box::new(|| {
+            Box::new(FailingSolverBuilder {})
+        })));
+    let words: Vec<String> = WORDS.iter().map(|&word| word.to_string()).collect();
+    let crossword = Crossword::try_from("..C\n...\n...", &words).unwrap();
+
+    let result = crossword.solve_with_solver_built_by(Box::new(portfolio));
+
+    assert_eq!(Some(SolverError::new("Cannot build solver")), result.err());
+}
+
+/// A builder always failing to build its solver.
+struct FailingSolverBuilder {}
+impl SolverConfigurator for FailingSolverBuilder {
+    fn add_clause(&mut self, _literals: &[Lit]) {
+        // Fails anyway
+    }
+}
+impl SolverBuilder for FailingSolverBuilder {
+    fn build(&self) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+        Err(SolverError::new("Cannot build solver"))
+    }
+}
@@ -1,62 +1,306 @@
-use splr::solver::SolverIter;
-use splr::Config;
+//! A [ConfigurableSolver] backed by [splr](https://github.com/shnarazk/splr), a CDCL solver written in pure Rust.
 
-use croissant_solver::{Solver, SolverBuilder, SolverConfigurator};
+use std::time::{Duration, Instant};
 
-/// Implementation of [SolverBuilder].
-// TODO don't use splr's SolverIter (there is now way to specify the relevant variables, so solutions may be repeated
-// TODO implement ConfigurableSolver instead
-pub struct SplrSolverBuilder {
+use splr::{Certificate, Config, SatSolverIF, SolveIF};
+
+use croissant_solver::{
+    CancellationToken, ConfigurableSolver, Lit, Model, ProgressCallback, SearchLimits,
+    SolveOutcome, Solver, SolverConfigurator, SolverError, SolverStats, Var,
+};
+
+/// Implementation of [ConfigurableSolver].
+///
+/// The actual splr solver is created by the first search, with all the clauses added so far; Clauses added later are
+/// given to it incrementally. If splr rejects the problem, e.g. an empty one, or a later clause, the searches fail with
+/// [SolveOutcome::Failed].
+///
+/// Solutions are enumerated by adding a clause blocking the relevant variables of each solution found, so that
+/// solutions differing only on irrelevant variables, e.g. auxiliary variables of an encoding, are found only once. If
+/// no variable is relevant, all variables are.
+///
+/// Only the timeout and the cancellation token of the [SearchLimits] are supported. splr counts its timeout from the
+/// creation of the actual solver: With a timeout, the actual solver is thus created anew before each search, with all
+/// the clauses added so far. The cancellation token is only checked before each search, and progress is only reported
+/// after each search, since splr offers no callback. The seed is ignored.
+///
+/// Statistics are limited to the numbers of variables and of clauses added, and to the solving time. Solving under
+/// assumptions is not supported.
+pub struct SplrSolver {
+    /// The actual solver, created by the next search if [None].
+    solver: Option<splr::Solver>,
+    /// All the clauses added, as raw literals, from which the actual solver is created.
     clauses: Vec<Vec<i32>>,
+    /// The maximal duration of a search, if any.
+    timeout: Option<Duration>,
+    /// The number of variables: The biggest allocated or used variable.
+    variables_count: usize,
+    /// The number of variables known to the actual solver.
+    solver_variables_count: usize,
+    /// The number of clauses added, including the ones blocking the solutions already found.
+    clauses_count: usize,
+    /// The problem's relevant variables.
+    relevant_variables: Vec<Var>,
+    /// The cancellation token, if any.
+    cancellation: Option<CancellationToken>,
+    /// The progress callback, if any.
+    progress: Option<ProgressCallback>,
+    /// The time spent searching so far.
+    solving_time: Duration,
+    /// Whether there is no solution left.
+    no_more_solution: bool,
+    /// The error which made the actual solver unusable, if any.
+    error: Option<SolverError>,
 }
 
-impl Default for SplrSolverBuilder {
+impl Default for SplrSolver {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl SplrSolverBuilder {
+/// Returns the raw representations of the given literals, as given to splr.
+fn raw(literals: &[Lit]) -> Vec<i32> {
+    literals.iter().map(|&literal| i32::from(literal)).collect()
+}
+
+impl SplrSolver {
+    /// Creates an instance.
     pub fn new() -> Self {
-        SplrSolverBuilder {
+        SplrSolver {
+            solver: None,
             clauses: Vec::new(),
+            timeout: None,
+            variables_count: 0,
+            solver_variables_count: 0,
+            clauses_count: 0,
+            relevant_variables: Vec::new(),
+            cancellation: None,
+            progress: None,
+            solving_time: Duration::ZERO,
+            no_more_solution: false,
+            error: None,
         }
     }
-}
 
-impl SolverConfigurator for SplrSolverBuilder {
-    fn add_clause(&mut self, literals: &[i32]) {
-        self.clauses.push(literals.to_vec())
+    /// Creates the actual solver with all the clauses added so far, if not created yet. On failure, keeps the error, or
+    /// marks the problem as unsatisfiable if splr found it so.
+    fn ensure_solver(&mut self) {
+        if self.solver.is_some() {
+            return;
+        }
+        let mut config = Config::default();
+        if let Some(timeout) = self.timeout {
+            config.c_timeout = timeout.as_secs_f64();
+        }
+        match splr::Solver::try_from((config, self.clauses.as_slice())) {
+            Ok(solver) => {
+                self.solver = Some(solver);
+                self.solver_variables_count = self
+                    .clauses
+                    .iter()
+                    .flatten()
+                    .map(|literal| literal.unsigned_abs() as usize)
+                    .max()
+                    .unwrap_or(0);
+                // Variables in no clause are still to enumerate if relevant
+                self.declare_variables(self.variables_count);
+            }
+            Err(Ok(Certificate::UNSAT)) => self.no_more_solution = true,
+            Err(error) => {
+                let message = format!("Cannot create splr solver: {error:?}");
+                self.error = Some(SolverError::new(message));
+            }
+        }
     }
-}
 
-impl SolverBuilder for SplrSolverBuilder {
-    fn build(&self) -> Box<dyn Solver<Item = Vec<i32>>> {
-        Box::new(SplrSolverWrapper::new(&self.clauses))
+    /// Declares the variables up to the given one to the actual solver, if not known yet.
+    fn declare_variables(&mut self, variables_count: usize) {
+        let Some(solver) = self.solver.as_mut() else {
+            return;
+        };
+        while self.solver_variables_count < variables_count {
+            solver.add_var();
+            self.solver_variables_count += 1;
+        }
+    }
+
+    /// Gives the given clause to the actual solver, declaring its new variables first.
+    fn add_clause_to_solver(&mut self, literals: &[Lit]) {
+        self.declare_variables(self.variables_count);
+        let Some(solver) = self.solver.as_mut() else {
+            return;
+        };
+        match solver.add_clause(raw(literals)) {
+            Ok(solver) => solver.reset(),
+            Err(splr::SolverError::EmptyClause | splr::SolverError::Inconsistent) => {
+                self.no_more_solution = true;
+            }
+            Err(error) => {
+                let message = format!("Cannot add clause to splr solver: {error:?}");
+                self.error = Some(SolverError::new(message));
+            }
+        }
+    }
+
+    /// Searches a model, measuring the solving time and reporting the progress.
+    fn search(&mut self) -> SolveOutcome {
+        if self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return SolveOutcome::Interrupted;
+        }
+        if self.timeout.is_some() {
+            // The timeout of the actual solver runs from its creation
+            self.solver = None;
+        }
+        self.ensure_solver();
+        if let Some(error) = &self.error {
+            return SolveOutcome::Failed(error.clone());
+        }
+        let Some(solver) = self.solver.as_mut() else {
+            // Unsatisfiable at creation
+            return SolveOutcome::Unsat;
+        };
+        let started = Instant::now();
+        let outcome = solver.solve();
+        self.solving_time += started.elapsed();
+        let stats = self.stats();
+        if let Some(progress) = self.progress.as_mut() {
+            progress(&stats);
+        }
+        match outcome {
+            Ok(Certificate::SAT(literals)) => SolveOutcome::Sat(self.model(&literals)),
+            Ok(Certificate::UNSAT) => SolveOutcome::Unsat,
+            Err(splr::SolverError::TimeOut) => SolveOutcome::Interrupted,
+            Err(error) => {
+                let error = SolverError::new(format!("splr failed: {error:?}"));
+                self.error = Some(error.clone());
+                SolveOutcome::Failed(error)
+            }
+        }
+    }
+
+    /// Returns the variables to report and to block: The relevant variables, or all variables if none is relevant.
+    fn reported_variables(&self) -> Vec<Var> {
+        if self.relevant_variables.is_empty() {
+            (1..=self.variables_count).map(Var::new).collect()
+        } else {
+            self.relevant_variables.clone()
+        }
     }
-}
 
-/// Implementation of [Solver] wrapping the splr SAT solver.
-struct SplrSolverWrapper {
-    iter: SolverIter,
+    /// Returns the model of the given literals found by splr, up to the biggest reported variable.
+    fn model(&self, literals: &[i32]) -> Model {
+        let variables_count = self
+            .reported_variables()
+            .into_iter()
+            .max()
+            .map_or(0, Var::index);
+        let mut model: Model = (1..=variables_count)
+            .map(|variable| Var::new(variable).literal(false))
+            .collect();
+        for &literal in literals {
+            let variable = literal.unsigned_abs() as usize;
+            if (1..=variables_count).contains(&variable) {
+                model.set(Lit::from_raw(literal));
+            }
+        }
+        model
+    }
+
+    /// Adds the clause blocking the reported variables of the given model.
+    fn block(&mut self, model: &Model) {
+        let blocking_clause: Vec<Lit> = self
+            .reported_variables()
+            .into_iter()
+            .filter_map(|variable| model.literal(variable))
+            .map(|literal| -literal)
+            .collect();
+        self.add_clause(&blocking_clause);
+    }
 }
 
-impl SplrSolverWrapper {
-    fn new(clauses: &Vec<Vec<i32>>) -> Self {
-        let iter = splr::Solver::try_from((Config::default(), clauses.as_slice()))
-            .map(splr::solver::Solver::into_iter)
-            .unwrap(); // TODO error handling
-        SplrSolverWrapper { iter }
+impl SolverConfigurator for SplrSolver {
+    fn allocate_variables(&mut self, variables_count: usize) {
+        self.variables_count = self.variables_count.max(variables_count);
+    }
+    fn variables_count(&self) -> Option<usize> {
+        Some(self.variables_count)
+    }
+    fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
+        if let Some(max_variable) = relevant_variables.iter().max() {
+            self.allocate_variables(max_variable.index());
+        }
+        self.relevant_variables = relevant_variables;
+    }
+    fn set_limits(&mut self, limits: SearchLimits) {
+        self.timeout = limits.timeout;
+        self.cancellation = limits.cancellation;
+    }
+    fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        limits.conflicts.is_none()
+    }
+    fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.progress = Some(callback);
+    }
+    fn add_clause(&mut self, literals: &[Lit]) {
+        for &literal in literals {
+            self.allocate_variables(literal.var().index());
+        }
+        self.clauses_count += 1;
+        if literals.is_empty() {
+            // splr rejects empty clauses, which make the problem unsatisfiable anyway
+            self.no_more_solution = true;
+        } else {
+            self.clauses.push(raw(literals));
+            self.add_clause_to_solver(literals);
+        }
     }
 }
 
-impl Iterator for SplrSolverWrapper {
-    type Item = Vec<i32>;
+impl Iterator for SplrSolver {
+    type Item = Model;
+
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        match self.solve_next() {
+            SolveOutcome::Sat(model) => Some(model),
+            _ => None,
+        }
     }
 }
 
-impl Solver for SplrSolverWrapper {
-    // Nothing to do.
+impl Solver for SplrSolver {
+    fn solve_next(&mut self) -> SolveOutcome {
+        if self.no_more_solution {
+            return SolveOutcome::Unsat;
+        }
+        match self.search() {
+            SolveOutcome::Sat(model) => {
+                self.block(&model);
+                SolveOutcome::Sat(model)
+            }
+            SolveOutcome::Unsat => {
+                self.no_more_solution = true;
+                SolveOutcome::Unsat
+            }
+            outcome => outcome,
+        }
+    }
+
+    fn as_configurator(&mut self) -> Option<&mut dyn SolverConfigurator> {
+        Some(self)
+    }
+    fn stats(&self) -> SolverStats {
+        SolverStats {
+            variables: self.variables_count,
+            clauses: self.clauses_count,
+            solving_time: self.solving_time,
+            ..SolverStats::default()
+        }
+    }
 }
+
+impl ConfigurableSolver for SplrSolver {}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use croissant_solver::{Lit, Model, SearchLimits, SolveOutcome, Solver, SolverConfigurator, Var};
+use croissant_solver_splr::SplrSolver;
+
+#[test]
+fn all_models() {
+    let mut solver = SplrSolver::new();
+    solver.add_clause(&clause(&[1, 2]));
+
+    let mut models: Vec<Vec<i32>> = solver.by_ref().map(|model| literals(&model)).collect();
+
+    models.sort();
+    assert_eq!(vec![vec![-1, 2], vec![1, -2], vec![1, 2]], models);
+    assert_eq!(SolveOutcome::Unsat, solver.solve_next());
+}
+
+#[test]
+fn projected_models() {
+    let mut solver = SplrSolver::new();
+    solver.set_relevant_variables(vec![Var::new(1)]);
+    // 1 ∨ 2 ∨ 3: 7 models, but only 2 assignments of 1
+    solver.add_clause(&clause(&[1, 2, 3]));
+
+    let mut models: Vec<Vec<i32>> = solver.map(|model| literals(&model)).collect();
+
+    models.sort();
+    assert_eq!(vec![vec![-1], vec![1]], models);
+}
+
+#[test]
+fn relevant_variable_not_in_clauses() {
+    let mut solver = SplrSolver::new();
+    solver.set_relevant_variables(vec![Var::new(1), Var::new(2)]);
+    solver.add_clause(&[Lit::from_raw(1)]);
+
+    let mut models: Vec<Vec<i32>> = solver.map(|model| literals(&model)).collect();
+
+    models.sort();
+    assert_eq!(vec![vec![1, -2], vec![1, 2]], models);
+}
+
+#[test]
+fn clauses_added_between_solutions() {
+    let mut solver = SplrSolver::new();
+    solver.set_relevant_variables(vec![Var::new(1), Var::new(2)]);
+    solver.add_clause(&clause(&[1, 2]));
+
+    assert!(matches!(solver.solve_next(), SolveOutcome::Sat(_)));
+    solver.add_clause(&[Lit::from_raw(-1)]);
+    solver.add_clause(&[Lit::from_raw(-2)]);
+
+    assert_eq!(SolveOutcome::Unsat, solver.solve_next());
+}
+
+#[test]
+fn timeout() {
+    let mut solver = SplrSolver::new();
+    solver.set_limits(SearchLimits {
+        timeout: Some(Duration::from_secs(60)),
+        ..SearchLimits::default()
+    });
+    solver.add_clause(&clause(&[1, 2]));
+
+    let mut models: Vec<Vec<i32>> = solver.by_ref().map(|model| literals(&model)).collect();
+
+    models.sort();
+    assert_eq!(vec![vec![-1, 2], vec![1, -2], vec![1, 2]], models);
+    assert_eq!(SolveOutcome::Unsat, solver.solve_next());
+}
+
+#[test]
+fn empty_problem() {
+    let mut solver = SplrSolver::new();
+
+    let outcome = solver.solve_next();
+
+    assert!(
+        matches!(&outcome, SolveOutcome::Failed(error) if error.message().starts_with("Cannot create splr solver")),
+        "Search should fail, not {outcome:?}"
+    );
+}
+
+#[test]
+fn trivially_unsatisfiable() {
+    let mut solver = SplrSolver::new();
+    solver.add_clause(&[]);
+
+    assert_eq!(SolveOutcome::Unsat, solver.solve_next());
+}
+
+/// Returns the raw literals of the given model.
+fn literals(model: &Model) -> Vec<i32> {
+    model.literals().map(i32::from).collect()
+}
+
+/// Returns the clause of the given raw literals.
+fn clause(literals: &[i32]) -> Vec<Lit> {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
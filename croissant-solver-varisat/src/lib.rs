@@ -0,0 +1,240 @@
+//! A [ConfigurableSolver] backed by [varisat](https://github.com/jix/varisat), a CDCL solver written in pure Rust. Unlike
+//! CaDiCaL, it compiles to WebAssembly.
+
+use croissant_solver::{
+    CancellationToken, ConfigurableSolver, Lit, Model, ProgressCallback, SearchLimits,
+    SolveOutcome, Solver, SolverConfigurator, SolverStats, Var,
+};
+use std::time::{Duration, Instant};
+use varisat::ExtendFormula;
+
+/// Implementation of [ConfigurableSolver].
+///
+/// Solutions are enumerated by adding a clause blocking the relevant variables of each solution found, so that
+/// solutions differing only on irrelevant variables, e.g. auxiliary variables of an encoding, are found only once. If
+/// no variable is relevant, all variables are.
+///
+/// Only the cancellation token of the [SearchLimits] is supported, and it is only checked before each search: Varisat
+/// offers no way to stop a running search, nor to bound its time or its conflicts. For the same reason, progress is
+/// only reported after each search. The seed is ignored.
+///
+/// Statistics are limited to the numbers of variables and of clauses added, and to the solving time.
+pub struct VarisatSolver {
+    /// The actual solver.
+    solver: varisat::Solver<'static>,
+    /// The number of variables: The biggest used variable.
+    variables_count: usize,
+    /// The number of clauses added, including the ones blocking the solutions already found.
+    clauses_count: usize,
+    /// The problem's relevant variables.
+    relevant_variables: Vec<Var>,
+    /// The cancellation token, if any.
+    cancellation: Option<CancellationToken>,
+    /// The progress callback, if any.
+    progress: Option<ProgressCallback>,
+    /// The time spent searching so far.
+    solving_time: Duration,
+    /// Whether there is no solution left.
+    no_more_solution: bool,
+    /// The assumptions responsible for the last unsatisfiable result under assumptions, if any.
+    failed_assumptions: Option<Vec<Lit>>,
+}
+
+impl Default for VarisatSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VarisatSolver {
+    /// Creates an instance.
+    pub fn new() -> Self {
+        VarisatSolver {
+            solver: varisat::Solver::new(),
+            variables_count: 0,
+            clauses_count: 0,
+            relevant_variables: Vec::new(),
+            cancellation: None,
+            progress: None,
+            solving_time: Duration::ZERO,
+            no_more_solution: false,
+            failed_assumptions: None,
+        }
+    }
+
+    /// Searches a model under the given assumptions, measuring the solving time and reporting the progress. Returns
+    /// `None` if the search is cancelled.
+    fn search(&mut self, assumptions: &[Lit]) -> Option<bool> {
+        if self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return None;
+        }
+        let assumptions: Vec<varisat::Lit> =
+            assumptions.iter().map(|&literal| lit(literal)).collect();
+        // Assumptions are kept by varisat until replaced
+        self.solver.assume(&assumptions);
+        let started = Instant::now();
+        // Errors only come from proof writing, which is not enabled
+        let result = self.solver.solve().ok();
+        self.solving_time += started.elapsed();
+        let stats = self.stats();
+        if let Some(progress) = self.progress.as_mut() {
+            progress(&stats);
+        }
+        result
+    }
+
+    /// Returns the variables to report and to block: The relevant variables, or all variables if none is relevant.
+    fn reported_variables(&self) -> Vec<Var> {
+        if self.relevant_variables.is_empty() {
+            (1..=self.variables_count).map(Var::new).collect()
+        } else {
+            self.relevant_variables.clone()
+        }
+    }
+
+    /// Returns the model of the last search, up to the biggest reported variable. Variables unknown to varisat, i.e.
+    /// not appearing in any clause, are reported as false.
+    fn model(&self) -> Model {
+        let variables_count = self
+            .reported_variables()
+            .into_iter()
+            .max()
+            .map_or(0, Var::index);
+        let mut model: Model = (1..=variables_count)
+            .map(|variable| Var::new(variable).literal(false))
+            .collect();
+        let literals = self
+            .solver
+            .model()
+            .expect("Model should be available after a satisfiable search");
+        for literal in literals {
+            let variable = literal.var().to_dimacs() as usize;
+            if variable <= variables_count {
+                model.set(Var::new(variable).literal(literal.is_positive()));
+            }
+        }
+        model
+    }
+
+    /// Adds the clause blocking the reported variables of the given model.
+    fn block(&mut self, model: &Model) {
+        let blocking_clause: Vec<Lit> = self
+            .reported_variables()
+            .into_iter()
+            .filter_map(|variable| model.literal(variable))
+            .map(|literal| -literal)
+            .collect();
+        self.add_clause(&blocking_clause);
+    }
+}
+
+/// Converts the given literal to a varisat literal.
+fn lit(literal: Lit) -> varisat::Lit {
+    varisat::Lit::from_dimacs(i32::from(literal) as isize)
+}
+
+impl SolverConfigurator for VarisatSolver {
+    fn allocate_variables(&mut self, variables_count: usize) {
+        self.variables_count = self.variables_count.max(variables_count);
+    }
+    fn variables_count(&self) -> Option<usize> {
+        Some(self.variables_count)
+    }
+    fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
+        if let Some(max_variable) = relevant_variables.iter().max() {
+            self.allocate_variables(max_variable.index());
+        }
+        self.relevant_variables = relevant_variables;
+    }
+    fn set_limits(&mut self, limits: SearchLimits) {
+        self.cancellation = limits.cancellation;
+    }
+    fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        limits.timeout.is_none() && limits.conflicts.is_none()
+    }
+    fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.progress = Some(callback);
+    }
+    fn add_clause(&mut self, literals: &[Lit]) {
+        for &literal in literals {
+            self.allocate_variables(literal.var().index());
+        }
+        let literals: Vec<varisat::Lit> = literals.iter().map(|&literal| lit(literal)).collect();
+        self.solver.add_clause(&literals);
+        self.clauses_count += 1;
+    }
+}
+
+impl Iterator for VarisatSolver {
+    type Item = Model;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.solve_next() {
+            SolveOutcome::Sat(model) => Some(model),
+            _ => None,
+        }
+    }
+}
+
+impl Solver for VarisatSolver {
+    fn solve_next(&mut self) -> SolveOutcome {
+        if self.no_more_solution {
+            return SolveOutcome::Unsat;
+        }
+        match self.search(&[]) {
+            Some(true) => {
+                let model = self.model();
+                self.block(&model);
+                SolveOutcome::Sat(model)
+            }
+            Some(false) => {
+                self.no_more_solution = true;
+                SolveOutcome::Unsat
+            }
+            None => SolveOutcome::Interrupted,
+        }
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[Lit]) -> SolveOutcome {
+        self.failed_assumptions = None;
+        match self.search(assumptions) {
+            Some(true) => SolveOutcome::Sat(self.model()),
+            Some(false) => {
+                let failed_assumptions = self
+                    .solver
+                    .failed_core()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|literal| {
+                        let variable = Var::new(literal.var().to_dimacs() as usize);
+                        variable.literal(literal.is_positive())
+                    })
+                    .collect();
+                self.failed_assumptions = Some(failed_assumptions);
+                SolveOutcome::Unsat
+            }
+            None => SolveOutcome::Interrupted,
+        }
+    }
+
+    fn failed_assumptions(&self) -> Option<Vec<Lit>> {
+        self.failed_assumptions.clone()
+    }
+    fn as_configurator(&mut self) -> Option<&mut dyn SolverConfigurator> {
+        Some(self)
+    }
+    fn stats(&self) -> SolverStats {
+        SolverStats {
+            variables: self.variables_count,
+            clauses: self.clauses_count,
+            solving_time: self.solving_time,
+            ..SolverStats::default()
+        }
+    }
+}
+
+impl ConfigurableSolver for VarisatSolver {}
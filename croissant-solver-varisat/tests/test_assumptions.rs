@@ -0,0 +1,90 @@
+use croissant_solver::{Lit, Model, SolveOutcome, Solver, SolverConfigurator, Var};
+use croissant_solver_varisat::VarisatSolver;
+
+/// (1 ∨ 2) ∧ (¬1 ∨ 3) ∧ (¬2 ∨ 3)
+fn solver() -> VarisatSolver {
+    let mut solver = VarisatSolver::new();
+    solver.set_relevant_variables((1..=3).map(Var::new).collect());
+    solver.add_clause(&literals(&[1, 2]));
+    solver.add_clause(&literals(&[-1, 3]));
+    solver.add_clause(&literals(&[-2, 3]));
+    solver
+}
+
+#[test]
+fn sat() {
+    let mut solver = solver();
+
+    assert_eq!(
+        SolveOutcome::Sat(model(&[1, -2, 3])),
+        solver.solve_with_assumptions(&literals(&[1, -2]))
+    );
+    assert_eq!(None, solver.failed_assumptions());
+}
+
+#[test]
+fn unsat() {
+    let mut solver = solver();
+
+    assert_eq!(
+        SolveOutcome::Unsat,
+        solver.solve_with_assumptions(&literals(&[1, -3]))
+    );
+    assert_eq!(Some(literals(&[1, -3])), solver.failed_assumptions());
+}
+
+#[test]
+fn unsat_failed_assumptions_subset() {
+    let mut solver = solver();
+    let assumptions = literals(&[1, 2, -3]);
+
+    assert_eq!(
+        SolveOutcome::Unsat,
+        solver.solve_with_assumptions(&assumptions)
+    );
+    let failed_assumptions = solver.failed_assumptions().unwrap();
+    assert!(
+        failed_assumptions
+            .iter()
+            .all(|failed_assumption| assumptions.contains(failed_assumption)),
+        "Unexpected failed assumption"
+    );
+    assert_eq!(
+        SolveOutcome::Unsat,
+        solver.solve_with_assumptions(&failed_assumptions)
+    );
+}
+
+#[test]
+fn assumptions_are_temporary() {
+    let mut solver = solver();
+
+    assert_eq!(
+        SolveOutcome::Unsat,
+        solver.solve_with_assumptions(&literals(&[-3]))
+    );
+    assert!(
+        matches!(
+            solver.solve_with_assumptions(&literals(&[3])),
+            SolveOutcome::Sat(_)
+        ),
+        "No solution found"
+    );
+    assert!(solver.next().is_some(), "No solution found");
+}
+
+/// Returns the model of the given raw literals.
+fn model(literals: &[i32]) -> Model {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
+
+/// Returns the literals of the given raw representations.
+fn literals(literals: &[i32]) -> Vec<Lit> {
+    literals
+        .iter()
+        .map(|&literal| Lit::from_raw(literal))
+        .collect()
+}
@@ -0,0 +1,9 @@
+use croissant_solver::SolverFactory;
+use croissant_solver_varisat::VarisatSolver;
+
+croissant_testkit::viable_tests!(factory);
+
+/// Returns the factory of varisat solvers.
+fn factory() -> SolverFactory {
+    SolverFactory::Configurable(Box::new(|| Box::new(VarisatSolver::new())))
+}
@@ -0,0 +1,317 @@
+use crate::{Lit, SolverConfigurator, Var};
+
+/// The number of literals up to which [AtMostOneEncoding::auto] keeps the pairwise encoding, i.e. up to 496 binary
+/// clauses. It covers the values of a crossword cell.
+const PAIRWISE_MAX_LITERAL_COUNT: usize = 32;
+
+/// The number of literals of the groups of [AtMostOneEncoding::Commander].
+const COMMANDER_GROUP_SIZE: usize = 3;
+
+/// The number of literals of the groups of [AtMostOneEncoding::Bimander].
+const BIMANDER_GROUP_SIZE: usize = 2;
+
+/// An encoding of an *at-most-one* constraint into clauses, see [SolverConfigurator::add_at_most_one_with].
+///
+/// All encodings are equivalent: Once the auxiliary variables are projected away, they have the same solutions as the
+/// pairwise encoding. They differ in numbers of clauses and of auxiliary variables, given here for *n* literals.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AtMostOneEncoding {
+    /// One binary clause per pair of literals: *n(n-1)/2* clauses, no auxiliary variable.
+    Pairwise,
+    /// The sequential counter of Sinz: *3n-4* clauses and *n-1* auxiliary variables, the *i*-th one being true iff one
+    /// of the first *i* literals is true.
+    Sequential,
+    /// The commander encoding of Klieber and Kwon: Literals are split in groups of 3, each with a commander variable
+    /// true iff one of the literals of the group is true, and the commanders are constrained recursively. About *3n*
+    /// clauses and *n/2* auxiliary variables.
+    Commander,
+    /// The product encoding of Chen: Literals are laid out in a grid of about *√n* rows and columns, each literal
+    /// implying the auxiliary variables of its row and of its column, and the rows and columns are constrained
+    /// recursively. About *2n* clauses and *2√n* auxiliary variables.
+    Product,
+    /// The bimander encoding of Nguyen and Mai: Literals are split in pairs, each pair being identified by the binary
+    /// representation of its index over *log2(n/2)* auxiliary variables. About *n·log2(n/2)* clauses.
+    Bimander,
+}
+
+impl AtMostOneEncoding {
+    /// Returns the encoding suited to the given number of literals: [Pairwise](Self::Pairwise) for a few literals,
+    /// which needs no auxiliary variable, and [Product](Self::Product) above, which keeps the number of clauses linear
+    /// with very few auxiliary variables.
+    pub fn auto(literal_count: usize) -> Self {
+        if literal_count <= PAIRWISE_MAX_LITERAL_COUNT {
+            AtMostOneEncoding::Pairwise
+        } else {
+            AtMostOneEncoding::Product
+        }
+    }
+
+    /// Returns the number of auxiliary variables used to encode an *at-most-one* constraint over the given number of
+    /// literals.
+    pub fn auxiliary_variable_count(self, literal_count: usize) -> usize {
+        let literals: Vec<Lit> = (1..=literal_count)
+            .map(|index| Var::new(index).into())
+            .collect();
+        let next_variable = literal_count + 1;
+        self.add_to(&literals, next_variable, &mut ClauseSink) - next_variable
+    }
+
+    /// Adds the clauses of the *at-most-one* constraint over the given literals to the given configurator, using the
+    /// given free variable and the following ones as auxiliary variables.
+    ///
+    /// Returns the first free variable after the auxiliary variables used.
+    pub fn add_to<C: SolverConfigurator + ?Sized>(
+        self,
+        literals: &[Lit],
+        next_variable: usize,
+        configurator: &mut C,
+    ) -> usize {
+        if literals.len() <= 1 {
+            return next_variable;
+        }
+        match self {
+            AtMostOneEncoding::Pairwise => {
+                add_pairwise_to(literals, configurator);
+                next_variable
+            }
+            AtMostOneEncoding::Sequential => {
+                add_sequential_to(literals, next_variable, configurator)
+            }
+            AtMostOneEncoding::Commander => add_commander_to(literals, next_variable, configurator),
+            AtMostOneEncoding::Product => add_product_to(literals, next_variable, configurator),
+            AtMostOneEncoding::Bimander => add_bimander_to(literals, next_variable, configurator),
+        }
+    }
+}
+
+/// A configurator dropping the clauses, to count the auxiliary variables of an encoding.
+struct ClauseSink;
+
+impl SolverConfigurator for ClauseSink {
+    fn add_clause(&mut self, _literals: &[Lit]) {
+        // Only auxiliary variables are counted
+    }
+}
+
+/// Adds the pairwise encoding: *￢x ∨ ￢y* for each pair of literals.
+fn add_pairwise_to<C: SolverConfigurator + ?Sized>(literals: &[Lit], configurator: &mut C) {
+    for i in 0..literals.len() {
+        for j in (i + 1)..literals.len() {
+            configurator.add_clause(&[-literals[i], -literals[j]]);
+        }
+    }
+}
+
+/// Adds the sequential counter encoding, whose auxiliary variable *s(i)* is true if one of the literals up to *i* is:
+/// *x(i) ⇒ s(i)*, *s(i-1) ⇒ s(i)* and *x(i) ⇒ ￢s(i-1)*.
+fn add_sequential_to<C: SolverConfigurator + ?Sized>(
+    literals: &[Lit],
+    next_variable: usize,
+    configurator: &mut C,
+) -> usize {
+    let last = literals.len() - 1;
+    let counter = |index: usize| Lit::from(Var::new(next_variable + index));
+    for (index, &literal) in literals.iter().enumerate() {
+        if index < last {
+            configurator.add_clause(&[-literal, counter(index)]);
+        }
+        if index > 0 {
+            if index < last {
+                configurator.add_clause(&[-counter(index - 1), counter(index)]);
+            }
+            configurator.add_clause(&[-literal, -counter(index - 1)]);
+        }
+    }
+    next_variable + last
+}
+
+/// Adds the commander encoding: For each group, its commander is equivalent to the disjunction of its literals, which
+/// are pairwise exclusive, and the commanders are constrained recursively.
+fn add_commander_to<C: SolverConfigurator + ?Sized>(
+    literals: &[Lit],
+    mut next_variable: usize,
+    configurator: &mut C,
+) -> usize {
+    if literals.len() <= COMMANDER_GROUP_SIZE {
+        add_pairwise_to(literals, configurator);
+        return next_variable;
+    }
+    let mut commanders = Vec::with_capacity(literals.len().div_ceil(COMMANDER_GROUP_SIZE));
+    for group in literals.chunks(COMMANDER_GROUP_SIZE) {
+        let commander = Lit::from(Var::new(next_variable));
+        next_variable += 1;
+        add_pairwise_to(group, configurator);
+        let mut commander_clause = Vec::with_capacity(group.len() + 1);
+        commander_clause.push(-commander);
+        for &literal in group {
+            configurator.add_clause(&[-literal, commander]);
+            commander_clause.push(literal);
+        }
+        configurator.add_clause(&commander_clause);
+        commanders.push(commander);
+    }
+    add_commander_to(&commanders, next_variable, configurator)
+}
+
+/// Adds the product encoding: Each literal implies the variable of its row and the one of its column, and at most one
+/// row and one column are true.
+fn add_product_to<C: SolverConfigurator + ?Sized>(
+    literals: &[Lit],
+    next_variable: usize,
+    configurator: &mut C,
+) -> usize {
+    let column_count = (literals.len() as f64).sqrt().ceil() as usize;
+    let row_count = literals.len().div_ceil(column_count);
+    let rows: Vec<Lit> = (0..row_count)
+        .map(|row| Var::new(next_variable + row).into())
+        .collect();
+    let columns: Vec<Lit> = (0..column_count)
+        .map(|column| Var::new(next_variable + row_count + column).into())
+        .collect();
+    for (index, &literal) in literals.iter().enumerate() {
+        configurator.add_clause(&[-literal, rows[index / column_count]]);
+        configurator.add_clause(&[-literal, columns[index % column_count]]);
+    }
+    let next_variable = next_variable + row_count + column_count;
+    let next_variable =
+        AtMostOneEncoding::auto(row_count).add_to(&rows, next_variable, configurator);
+    AtMostOneEncoding::auto(column_count).add_to(&columns, next_variable, configurator)
+}
+
+/// Adds the bimander encoding: The literals of each group are pairwise exclusive, and each literal implies the binary
+/// representation of the index of its group over the auxiliary variables, so that two groups cannot both have a true
+/// literal.
+fn add_bimander_to<C: SolverConfigurator + ?Sized>(
+    literals: &[Lit],
+    next_variable: usize,
+    configurator: &mut C,
+) -> usize {
+    let group_count = literals.len().div_ceil(BIMANDER_GROUP_SIZE);
+    let bit_count = (usize::BITS - (group_count - 1).leading_zeros()) as usize;
+    for (group_index, group) in literals.chunks(BIMANDER_GROUP_SIZE).enumerate() {
+        add_pairwise_to(group, configurator);
+        for &literal in group {
+            for bit in 0..bit_count {
+                let bit_variable = Lit::from(Var::new(next_variable + bit));
+                if group_index & (1 << bit) == 0 {
+                    configurator.add_clause(&[-literal, -bit_variable]);
+                } else {
+                    configurator.add_clause(&[-literal, bit_variable]);
+                }
+            }
+        }
+    }
+    next_variable + bit_count
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ENCODINGS: [AtMostOneEncoding; 5] = [
+        AtMostOneEncoding::Pairwise,
+        AtMostOneEncoding::Sequential,
+        AtMostOneEncoding::Commander,
+        AtMostOneEncoding::Product,
+        AtMostOneEncoding::Bimander,
+    ];
+
+    #[derive(Default)]
+    struct ClauseRecorder {
+        clauses: Vec<Vec<i32>>,
+    }
+
+    impl SolverConfigurator for ClauseRecorder {
+        fn add_clause(&mut self, literals: &[Lit]) {
+            self.clauses
+                .push(literals.iter().map(|&literal| i32::from(literal)).collect());
+        }
+    }
+
+    /// Returns `true` iff the given clauses are satisfied by the given assignment, indexed by variable - 1.
+    fn satisfied(clauses: &[Vec<i32>], assignment: &[bool]) -> bool {
+        clauses.iter().all(|clause| {
+            clause
+                .iter()
+                .any(|&literal| assignment[literal.unsigned_abs() as usize - 1] == (literal > 0))
+        })
+    }
+
+    /// Returns the assignments of the first `variable_count` variables which can be extended to the following
+    /// `auxiliary_count` variables so as to satisfy the given clauses, as bit masks.
+    fn projected_models(
+        clauses: &[Vec<i32>],
+        variable_count: usize,
+        auxiliary_count: usize,
+    ) -> Vec<u32> {
+        let total_count = variable_count + auxiliary_count;
+        (0..1u32 << variable_count)
+            .filter(|&mask| {
+                (0..1u32 << auxiliary_count).any(|auxiliary_mask| {
+                    let assignment = mask | (auxiliary_mask << variable_count);
+                    let assignment: Vec<bool> = (0..total_count)
+                        .map(|variable| assignment & (1 << variable) != 0)
+                        .collect();
+                    satisfied(clauses, &assignment)
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the literals over the given number of variables, with alternating signs.
+    fn literals(variable_count: usize) -> Vec<Lit> {
+        (1..=variable_count)
+            .map(|index| Var::new(index).literal(index % 2 != 0))
+            .collect()
+    }
+
+    #[test]
+    fn equivalent_to_pairwise() {
+        for variable_count in 0..=10 {
+            let literals = literals(variable_count);
+            let mut pairwise = ClauseRecorder::default();
+            AtMostOneEncoding::Pairwise.add_to(&literals, variable_count + 1, &mut pairwise);
+            let expected = projected_models(&pairwise.clauses, variable_count, 0);
+            for encoding in ENCODINGS {
+                let mut recorder = ClauseRecorder::default();
+                let next_variable = encoding.add_to(&literals, variable_count + 1, &mut recorder);
+                let auxiliary_count = next_variable - variable_count - 1;
+                assert_eq!(
+                    expected,
+                    projected_models(&recorder.clauses, variable_count, auxiliary_count),
+                    "{encoding:?} on {variable_count} literals"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn auxiliary_variable_count() {
+        assert_eq!(0, AtMostOneEncoding::Pairwise.auxiliary_variable_count(100));
+        assert_eq!(
+            99,
+            AtMostOneEncoding::Sequential.auxiliary_variable_count(100)
+        );
+        assert_eq!(0, AtMostOneEncoding::Sequential.auxiliary_variable_count(1));
+        assert_eq!(
+            52,
+            AtMostOneEncoding::Commander.auxiliary_variable_count(100)
+        );
+        assert_eq!(20, AtMostOneEncoding::Product.auxiliary_variable_count(100));
+        assert_eq!(6, AtMostOneEncoding::Bimander.auxiliary_variable_count(100));
+    }
+
+    #[test]
+    fn product_clause_count() {
+        let literals: Vec<Lit> = (1..=30_000).map(|index| Var::new(index).into()).collect();
+        let mut recorder = ClauseRecorder::default();
+        AtMostOneEncoding::Product.add_to(&literals, 30_001, &mut recorder);
+        assert!(recorder.clauses.len() < 70_000);
+    }
+
+    #[test]
+    fn auto() {
+        assert_eq!(AtMostOneEncoding::Pairwise, AtMostOneEncoding::auto(27));
+        assert_eq!(AtMostOneEncoding::Product, AtMostOneEncoding::auto(30_000));
+    }
+}
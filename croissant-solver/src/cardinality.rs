@@ -0,0 +1,511 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+use crate::{Lit, SolverConfigurator, Var};
+
+/// Adds the clauses of a constraint to the given configurator: With the given encoding, whose auxiliary variables
+/// follow the [variables count](SolverConfigurator::variables_count), which are then declared using
+/// [allocate_variables](SolverConfigurator::allocate_variables), or with the given fallback, adding no auxiliary
+/// variable, if the variables count is unknown.
+pub(crate) fn add_with_auxiliary_variables<C: SolverConfigurator + ?Sized>(
+    configurator: &mut C,
+    encoding: impl FnOnce(usize, &mut C) -> usize,
+    fallback: impl FnOnce(&mut C),
+) {
+    match configurator.variables_count() {
+        Some(variables_count) => {
+            let next_variable = encoding(variables_count + 1, configurator);
+            configurator.allocate_variables(next_variable - 1);
+        }
+        None => fallback(configurator),
+    }
+}
+
+/// Adds the clauses stating that at most `k` of the given literals are true, using a totalizer (Bailleux and
+/// Boufkhad, 2003): A binary tree counting in unary the true literals below each node, up to `k + 1`. Auxiliary
+/// variables start at the given free variable.
+///
+/// Returns the first free variable after the auxiliary variables used.
+pub fn add_at_most_k_clauses_to<C: SolverConfigurator + ?Sized>(
+    literals: &[Lit],
+    k: usize,
+    next_variable: usize,
+    configurator: &mut C,
+) -> usize {
+    if k >= literals.len() {
+        return next_variable;
+    }
+    if k == 0 {
+        for &literal in literals {
+            configurator.add_clause(&[-literal]);
+        }
+        return next_variable;
+    }
+    let mut next_variable = next_variable;
+    let counts = add_totalizer_node_to(literals, k + 1, &mut next_variable, configurator);
+    configurator.add_clause(&[-counts[k]]);
+    next_variable
+}
+
+/// Adds the clauses of the totalizer node over the given literals, using the given free variable and the following
+/// ones as auxiliary variables.
+///
+/// Returns the unary count of the true literals, up to the given limit: Its *i*-th literal is implied by *i + 1* true
+/// literals.
+fn add_totalizer_node_to<C: SolverConfigurator + ?Sized>(
+    literals: &[Lit],
+    limit: usize,
+    next_variable: &mut usize,
+    configurator: &mut C,
+) -> Vec<Lit> {
+    if literals.len() == 1 {
+        return literals.to_vec();
+    }
+    let (left, right) = literals.split_at(literals.len() / 2);
+    let left = add_totalizer_node_to(left, limit, next_variable, configurator);
+    let right = add_totalizer_node_to(right, limit, next_variable, configurator);
+    let count_len = (left.len() + right.len()).min(limit);
+    let counts: Vec<Lit> = (0..count_len)
+        .map(|index| Var::new(*next_variable + index).into())
+        .collect();
+    *next_variable += count_len;
+    // left count ≥ i ∧ right count ≥ j ⇒ count ≥ i + j
+    for i in 0..=left.len() {
+        for j in 0..=right.len() {
+            let sum = i + j;
+            if sum == 0 || sum > count_len {
+                continue;
+            }
+            let mut clause = Vec::with_capacity(3);
+            if i > 0 {
+                clause.push(-left[i - 1]);
+            }
+            if j > 0 {
+                clause.push(-right[j - 1]);
+            }
+            clause.push(counts[sum - 1]);
+            configurator.add_clause(&clause);
+        }
+    }
+    counts
+}
+
+/// Adds the clauses stating that at least `k` of the given literals are true, i.e. that at most `n - k` of their
+/// negations are true, using a sequential counter (Sinz, 2005). Auxiliary variables start at the given free variable.
+///
+/// Returns the first free variable after the auxiliary variables used.
+pub fn add_at_least_k_clauses_to<C: SolverConfigurator + ?Sized>(
+    literals: &[Lit],
+    k: usize,
+    next_variable: usize,
+    configurator: &mut C,
+) -> usize {
+    let n = literals.len();
+    if k == 0 {
+        return next_variable;
+    }
+    if k > n {
+        // Impossible
+        let contradiction = Lit::from(Var::new(next_variable));
+        configurator.add_clause(&[contradiction]);
+        configurator.add_clause(&[-contradiction]);
+        return next_variable + 1;
+    }
+    let negations: Vec<Lit> = literals.iter().map(|&literal| -literal).collect();
+    let m = n - k;
+    if m == 0 {
+        for &literal in literals {
+            configurator.add_clause(&[literal]);
+        }
+        return next_variable;
+    }
+    // counter(i, j) ⇔ at least j + 1 of the first i + 1 negations are true
+    let counter = |i: usize, j: usize| Lit::from(Var::new(next_variable + i * m + j));
+    configurator.add_clause(&[-negations[0], counter(0, 0)]);
+    for j in 1..m {
+        configurator.add_clause(&[-counter(0, j)]);
+    }
+    for (i, &negation) in negations.iter().enumerate().take(n - 1).skip(1) {
+        configurator.add_clause(&[-negation, counter(i, 0)]);
+        configurator.add_clause(&[-counter(i - 1, 0), counter(i, 0)]);
+        for j in 1..m {
+            configurator.add_clause(&[-negation, -counter(i - 1, j - 1), counter(i, j)]);
+            configurator.add_clause(&[-counter(i - 1, j), counter(i, j)]);
+        }
+        configurator.add_clause(&[-negation, -counter(i - 1, m - 1)]);
+    }
+    configurator.add_clause(&[-negations[n - 1], -counter(n - 2, m - 1)]);
+    next_variable + (n - 1) * m
+}
+
+/// Adds the clauses stating that the sum of the weights of the true literals among the given ones is at most the given
+/// bound, using an adder network (Eén and Sörensson, 2006): The weighted literals are summed in binary by full and half
+/// adders, and the binary sum is compared to the bound. Auxiliary variables start at the given free variable.
+///
+/// Negative weights are supported, by weighting the negation of their literal instead.
+///
+/// Returns the first free variable after the auxiliary variables used.
+///
+/// ## Panics
+///
+/// Panics if the numbers of literals and of weights differ.
+pub fn add_pb_le_clauses_to<C: SolverConfigurator + ?Sized>(
+    literals: &[Lit],
+    weights: &[i64],
+    bound: i64,
+    next_variable: usize,
+    configurator: &mut C,
+) -> usize {
+    let (terms, bound) = normalized(literals, weights, bound);
+    let total: i128 = terms.iter().map(|&(_, weight)| weight as i128).sum();
+    if total <= bound {
+        return next_variable;
+    }
+    if bound < 0 {
+        // Impossible
+        let contradiction = Lit::from(Var::new(next_variable));
+        configurator.add_clause(&[contradiction]);
+        configurator.add_clause(&[-contradiction]);
+        return next_variable + 1;
+    }
+    let mut next_variable = next_variable;
+    let mut buckets: Vec<VecDeque<Lit>> = Vec::new();
+    for (literal, weight) in terms {
+        for bit in 0..u64::BITS as usize {
+            if weight & (1 << bit) != 0 {
+                if buckets.len() <= bit {
+                    buckets.resize(bit + 1, VecDeque::new());
+                }
+                buckets[bit].push_back(literal);
+            }
+        }
+    }
+    let mut sum_bits = Vec::with_capacity(buckets.len());
+    let mut bit = 0;
+    while bit < buckets.len() {
+        while buckets[bit].len() >= 2 {
+            let input_count = buckets[bit].len().min(3);
+            let inputs: Vec<Lit> = buckets[bit].drain(..input_count).collect();
+            let sum = Lit::from(Var::new(next_variable));
+            let carry = Lit::from(Var::new(next_variable + 1));
+            next_variable += 2;
+            add_adder_to(&inputs, sum, carry, configurator);
+            buckets[bit].push_back(sum);
+            if buckets.len() <= bit + 1 {
+                buckets.push(VecDeque::new());
+            }
+            buckets[bit + 1].push_back(carry);
+        }
+        sum_bits.push(buckets[bit].pop_front());
+        bit += 1;
+    }
+    add_at_most_bound_clauses_to(&sum_bits, bound as u128, configurator);
+    next_variable
+}
+
+/// Returns the terms of the given weighted literals with positive weights only, weighting the negation of the literals
+/// with a negative weight, and the bound adjusted accordingly. Terms with a null weight are dropped.
+///
+/// ## Panics
+///
+/// Panics if the numbers of literals and of weights differ.
+fn normalized(literals: &[Lit], weights: &[i64], bound: i64) -> (Vec<(Lit, u64)>, i128) {
+    assert_eq!(
+        literals.len(),
+        weights.len(),
+        "Literals and weights should have the same length"
+    );
+    let mut bound = bound as i128;
+    let mut terms = Vec::with_capacity(literals.len());
+    for (&literal, &weight) in literals.iter().zip(weights) {
+        // w·x = w + |w|·￢x when w < 0
+        if weight < 0 {
+            bound += weight.unsigned_abs() as i128;
+            terms.push((-literal, weight.unsigned_abs()));
+        } else if weight > 0 {
+            terms.push((literal, weight as u64));
+        }
+    }
+    (terms, bound)
+}
+
+/// Adds the clauses of a full adder - or of a half adder if there are two inputs: The sum is the parity of the inputs
+/// and the carry is true iff at least two inputs are true. There are two clauses per combination of the inputs values,
+/// giving the sum and the carry of this combination.
+fn add_adder_to<C: SolverConfigurator + ?Sized>(
+    inputs: &[Lit],
+    sum: Lit,
+    carry: Lit,
+    configurator: &mut C,
+) {
+    for values in 0..1u32 << inputs.len() {
+        // The clause holds unless the inputs have these values
+        let mut clause: Vec<Lit> = inputs
+            .iter()
+            .enumerate()
+            .map(|(index, &input)| {
+                if values & (1 << index) != 0 {
+                    -input
+                } else {
+                    input
+                }
+            })
+            .collect();
+        let true_count = values.count_ones();
+        clause.push(if true_count % 2 == 1 { sum } else { -sum });
+        configurator.add_clause(&clause);
+        clause.pop();
+        clause.push(if true_count >= 2 { carry } else { -carry });
+        configurator.add_clause(&clause);
+    }
+}
+
+/// Adds the clauses stating that the binary number of the given bits, from the least significant one, is at most the
+/// given bound. A missing bit is false.
+fn add_at_most_bound_clauses_to<C: SolverConfigurator + ?Sized>(
+    bits: &[Option<Lit>],
+    bound: u128,
+    configurator: &mut C,
+) {
+    let bound_bit = |index: usize| index < u128::BITS as usize && bound & (1 << index) != 0;
+    // Forbids each number equal to the bound on the bits above a given one, and greater on this one
+    'bits: for (index, &bit) in bits.iter().enumerate() {
+        let Some(bit) = bit else {
+            continue;
+        };
+        if bound_bit(index) {
+            continue;
+        }
+        let mut clause = vec![-bit];
+        for (higher_index, &higher_bit) in bits.iter().enumerate().skip(index + 1) {
+            match (higher_bit, bound_bit(higher_index)) {
+                (None, true) => continue 'bits,
+                (None, false) => {}
+                (Some(higher_bit), true) => clause.push(-higher_bit),
+                (Some(higher_bit), false) => clause.push(higher_bit),
+            }
+        }
+        configurator.add_clause(&clause);
+    }
+}
+
+/// Adds the clauses stating that the sum of the weights of the true literals among the given ones is at most the given
+/// bound, without auxiliary variable: One clause per minimal combination of literals exceeding the bound, whose number
+/// grows exponentially with the number of literals.
+///
+/// ## Panics
+///
+/// Panics if the numbers of literals and of weights differ.
+pub(crate) fn add_exceeding_combinations_clauses_to<C: SolverConfigurator + ?Sized>(
+    literals: &[Lit],
+    weights: &[i64],
+    bound: i64,
+    configurator: &mut C,
+) {
+    let (mut terms, bound) = normalized(literals, weights, bound);
+    terms.sort_by_key(|&(_, weight)| Reverse(weight));
+    let mut combination = Vec::new();
+    add_exceeding_combinations_from(&terms, bound, &mut combination, configurator);
+}
+
+/// Adds the clauses forbidding the given combination of literals, whose bound is left, extended with the given terms
+/// until it exceeds the bound.
+fn add_exceeding_combinations_from<C: SolverConfigurator + ?Sized>(
+    terms: &[(Lit, u64)],
+    bound_left: i128,
+    combination: &mut Vec<Lit>,
+    configurator: &mut C,
+) {
+    if bound_left < 0 {
+        configurator.add_clause(combination);
+        return;
+    }
+    let remaining: i128 = terms.iter().map(|&(_, weight)| weight as i128).sum();
+    if remaining <= bound_left {
+        return;
+    }
+    for (index, &(literal, weight)) in terms.iter().enumerate() {
+        combination.push(-literal);
+        add_exceeding_combinations_from(
+            &terms[index + 1..],
+            bound_left - weight as i128,
+            combination,
+            configurator,
+        );
+        combination.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct ClauseRecorder {
+        clauses: Vec<Vec<i32>>,
+    }
+
+    impl SolverConfigurator for ClauseRecorder {
+        fn add_clause(&mut self, literals: &[Lit]) {
+            self.clauses
+                .push(literals.iter().map(|&literal| i32::from(literal)).collect());
+        }
+    }
+
+    /// Returns `true` iff the given clauses are satisfiable with the given variables fixed, by brute force.
+    fn is_satisfiable(clauses: &[Vec<i32>], fixed: &[bool], variable_count: usize) -> bool {
+        let free_count = variable_count - fixed.len();
+        (0..1u64 << free_count).any(|free| {
+            let value = |variable: usize| {
+                if variable <= fixed.len() {
+                    fixed[variable - 1]
+                } else {
+                    free & (1 << (variable - fixed.len() - 1)) != 0
+                }
+            };
+            clauses.iter().all(|clause| {
+                clause
+                    .iter()
+                    .any(|&literal| value(literal.unsigned_abs() as usize) == (literal > 0))
+            })
+        })
+    }
+
+    /// Checks that the given clauses over the given literals, using the variables up to the given one excluded, accept
+    /// exactly the assignments of the literals satisfying the given predicate, given the values of the literals.
+    fn assert_equivalent(
+        clauses: &[Vec<i32>],
+        literals: &[Lit],
+        next_variable: usize,
+        expected: impl Fn(&[bool]) -> bool,
+        description: &str,
+    ) {
+        for assignment in 0..1 << literals.len() {
+            let fixed: Vec<bool> = (0..literals.len())
+                .map(|i| assignment & (1 << i) != 0)
+                .collect();
+            let values: Vec<bool> = literals
+                .iter()
+                .map(|&literal| fixed[literal.var().index() - 1] == literal.is_positive())
+                .collect();
+            assert_eq!(
+                expected(&values),
+                is_satisfiable(clauses, &fixed, next_variable - 1),
+                "{description}, assignment = {fixed:?}"
+            );
+        }
+    }
+
+    /// Returns the literals over the given number of variables, with alternating signs.
+    fn literals(variable_count: usize) -> Vec<Lit> {
+        (1..=variable_count)
+            .map(|index| Var::new(index).literal(index % 2 != 0))
+            .collect()
+    }
+
+    fn true_count(values: &[bool]) -> usize {
+        values.iter().filter(|&&value| value).count()
+    }
+
+    #[test]
+    fn at_least_k() {
+        let literals: Vec<Lit> = (1..=4).map(|index| Var::new(index).into()).collect();
+        for k in 0..=5 {
+            let mut configurator = ClauseRecorder::default();
+            let next_variable = add_at_least_k_clauses_to(&literals, k, 5, &mut configurator);
+            assert_equivalent(
+                &configurator.clauses,
+                &literals,
+                next_variable,
+                |values| true_count(values) >= k,
+                &format!("k = {k}"),
+            );
+        }
+    }
+
+    #[test]
+    fn at_most_k() {
+        for n in 0..=6 {
+            let literals = literals(n);
+            for k in 0..=n + 1 {
+                let mut configurator = ClauseRecorder::default();
+                let next_variable =
+                    add_at_most_k_clauses_to(&literals, k, n + 1, &mut configurator);
+                assert_equivalent(
+                    &configurator.clauses,
+                    &literals,
+                    next_variable,
+                    |values| true_count(values) <= k,
+                    &format!("n = {n}, k = {k}"),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pb_le() {
+        let weights = [3, -2, 5, 1, 0];
+        let literals = literals(weights.len());
+        for bound in -4..=10 {
+            let mut configurator = ClauseRecorder::default();
+            let next_variable =
+                add_pb_le_clauses_to(&literals, &weights, bound, 6, &mut configurator);
+            assert_equivalent(
+                &configurator.clauses,
+                &literals,
+                next_variable,
+                |values| weighted_sum(values, &weights) <= bound,
+                &format!("bound = {bound}"),
+            );
+        }
+    }
+
+    #[test]
+    fn pb_le_unit_weights() {
+        let literals = literals(5);
+        for bound in 0..=5 {
+            let mut configurator = ClauseRecorder::default();
+            let next_variable =
+                add_pb_le_clauses_to(&literals, &[1; 5], bound, 6, &mut configurator);
+            assert_equivalent(
+                &configurator.clauses,
+                &literals,
+                next_variable,
+                |values| true_count(values) as i64 <= bound,
+                &format!("bound = {bound}"),
+            );
+        }
+    }
+
+    #[test]
+    fn exceeding_combinations() {
+        let weights = [3, -2, 5, 1];
+        let literals = literals(weights.len());
+        for bound in -3..=8 {
+            let mut configurator = ClauseRecorder::default();
+            add_exceeding_combinations_clauses_to(&literals, &weights, bound, &mut configurator);
+            assert_equivalent(
+                &configurator.clauses,
+                &literals,
+                literals.len() + 1,
+                |values| weighted_sum(values, &weights) <= bound,
+                &format!("bound = {bound}"),
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Literals and weights should have the same length")]
+    fn pb_le_weights_mismatch() {
+        add_pb_le_clauses_to(&literals(2), &[1], 1, 3, &mut ClauseRecorder::default());
+    }
+
+    fn weighted_sum(values: &[bool], weights: &[i64]) -> i64 {
+        values
+            .iter()
+            .zip(weights)
+            .filter(|&(&value, _)| value)
+            .map(|(_, &weight)| weight)
+            .sum()
+    }
+}
@@ -0,0 +1,214 @@
+use std::io::{self, Seek, SeekFrom, Write};
+
+use crate::{Lit, SolverConfigurator, Var};
+
+/// The width of the header line, new line excluded: Enough for two 64-bit counts.
+const HEADER_WIDTH: usize = "p cnf 18446744073709551615 18446744073709551615".len();
+
+/// A [SolverConfigurator] writing the problem in [DIMACS CNF](https://jix.github.io/varisat/manual/0.2.0/formats/dimacs.html)
+/// format, e.g. to give it to another solver or to submit it to benchmarks.
+///
+/// Clauses are streamed to the writer as they are added, so that big problems are not kept in memory. Since the
+/// counts of the header are only known at the end, a blank header is written first, then overwritten by
+/// [finish](Self::finish): The writer must support seeking, e.g. a [File](std::fs::File) or a
+/// [Cursor](std::io::Cursor).
+///
+/// Relevant variables are written as a `c p show` comment, as in the projected model counting format. Limits, seed and
+/// progress callback are ignored.
+///
+/// Writing errors cannot be reported when adding clauses: The first one is kept and returned by
+/// [finish](Self::finish), and nothing is written after it.
+pub struct DimacsWriter<W: Write + Seek> {
+    /// The destination.
+    writer: W,
+    /// The position of the header in the destination.
+    header_position: u64,
+    /// The number of variables: The biggest allocated or used variable.
+    variables_count: usize,
+    /// The number of clauses written.
+    clauses_count: usize,
+    /// The first writing error, if any.
+    error: Option<io::Error>,
+}
+
+impl<W: Write + Seek> DimacsWriter<W> {
+    /// Creates an instance writing to the given writer, at its current position. Returns an error if the blank header
+    /// cannot be written.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        let header_position = writer.stream_position()?;
+        writeln!(writer, "{:HEADER_WIDTH$}", "")?;
+        Ok(DimacsWriter {
+            writer,
+            header_position,
+            variables_count: 0,
+            clauses_count: 0,
+            error: None,
+        })
+    }
+
+    /// Writes the header with the final counts and flushes the writer.
+    ///
+    /// Returns the writer, positioned after the last clause, or the first error encountered while writing.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let end = self.writer.stream_position()?;
+        let header = format!("p cnf {} {}", self.variables_count, self.clauses_count);
+        self.writer.seek(SeekFrom::Start(self.header_position))?;
+        write!(self.writer, "{header:HEADER_WIDTH$}")?;
+        self.writer.seek(SeekFrom::Start(end))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Returns the number of variables.
+    pub fn variables_count(&self) -> usize {
+        self.variables_count
+    }
+
+    /// Returns the number of clauses.
+    pub fn clauses_count(&self) -> usize {
+        self.clauses_count
+    }
+
+    /// Writes the given line, unless a previous write failed.
+    fn write_line(&mut self, line: &str) {
+        if self.error.is_none() {
+            if let Err(error) = self.writer.write_all(line.as_bytes()) {
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+impl<W: Write + Seek> SolverConfigurator for DimacsWriter<W> {
+    fn allocate_variables(&mut self, variables_count: usize) {
+        self.variables_count = self.variables_count.max(variables_count);
+    }
+
+    fn variables_count(&self) -> Option<usize> {
+        Some(self.variables_count)
+    }
+
+    fn set_relevant_variables(&mut self, relevant_variables: Vec<Var>) {
+        let mut line = String::from("c p show");
+        for variable in relevant_variables {
+            line.push_str(&format!(" {variable}"));
+        }
+        line.push_str(" 0\n");
+        self.write_line(&line);
+    }
+
+    fn add_clause(&mut self, literals: &[Lit]) {
+        let mut line = String::with_capacity(8 * (literals.len() + 1));
+        for &literal in literals {
+            self.variables_count = self.variables_count.max(literal.var().index());
+            line.push_str(&format!("{literal} "));
+        }
+        line.push_str("0\n");
+        self.write_line(&line);
+        self.clauses_count += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::AtMostOneEncoding;
+
+    #[test]
+    fn header_counts() {
+        let mut dimacs_writer = DimacsWriter::new(Cursor::new(Vec::new())).unwrap();
+
+        dimacs_writer.allocate_variables(3);
+        dimacs_writer.add_clause(&literals(&[1, -2]));
+        dimacs_writer.add_exactly_one(&literals(&[2, 4]));
+
+        let cnf = String::from_utf8(dimacs_writer.finish().unwrap().into_inner()).unwrap();
+        let lines: Vec<&str> = cnf.lines().collect();
+        assert_eq!(
+            vec!["p cnf 4 3", "1 -2 0", "2 4 0", "-2 -4 0"],
+            lines_trimmed(&lines)
+        );
+    }
+
+    #[test]
+    fn auxiliary_variables_after_allocated_ones() {
+        let mut dimacs_writer = DimacsWriter::new(Cursor::new(Vec::new())).unwrap();
+
+        dimacs_writer.allocate_variables(4);
+        dimacs_writer.add_at_most_one_with(&literals(&[1, 2, 3]), AtMostOneEncoding::Sequential);
+
+        let cnf = String::from_utf8(dimacs_writer.finish().unwrap().into_inner()).unwrap();
+        let lines: Vec<&str> = cnf.lines().collect();
+        assert_eq!(
+            vec![
+                "p cnf 6 5",
+                "-1 5 0",
+                "-2 6 0",
+                "-5 6 0",
+                "-2 -5 0",
+                "-3 -6 0"
+            ],
+            lines_trimmed(&lines)
+        );
+    }
+
+    #[test]
+    fn relevant_variables() {
+        let mut dimacs_writer = DimacsWriter::new(Cursor::new(Vec::new())).unwrap();
+
+        dimacs_writer.set_relevant_variables(vec![Var::new(1), Var::new(2)]);
+        dimacs_writer.add_clause(&literals(&[1]));
+
+        let cnf = String::from_utf8(dimacs_writer.finish().unwrap().into_inner()).unwrap();
+        let lines: Vec<&str> = cnf.lines().collect();
+        assert_eq!(
+            vec!["p cnf 1 1", "c p show 1 2 0", "1 0"],
+            lines_trimmed(&lines)
+        );
+    }
+
+    #[test]
+    fn empty_problem() {
+        let dimacs_writer = DimacsWriter::new(Cursor::new(Vec::new())).unwrap();
+
+        let cnf = String::from_utf8(dimacs_writer.finish().unwrap().into_inner()).unwrap();
+
+        assert_eq!("p cnf 0 0", cnf.trim_end());
+    }
+
+    #[test]
+    fn writer_positioned_after_clauses() {
+        let mut cursor = Cursor::new(Vec::new());
+        cursor.write_all(b"c Some comment\n").unwrap();
+        let mut dimacs_writer = DimacsWriter::new(cursor).unwrap();
+        dimacs_writer.add_clause(&literals(&[-1]));
+
+        let mut cursor = dimacs_writer.finish().unwrap();
+        cursor.write_all(b"c The end\n").unwrap();
+
+        let cnf = String::from_utf8(cursor.into_inner()).unwrap();
+        let lines: Vec<&str> = cnf.lines().collect();
+        assert_eq!(
+            vec!["c Some comment", "p cnf 1 1", "-1 0", "c The end"],
+            lines_trimmed(&lines)
+        );
+    }
+
+    /// Returns the literals of the given raw representations.
+    fn literals(literals: &[i32]) -> Vec<Lit> {
+        literals
+            .iter()
+            .map(|&literal| Lit::from_raw(literal))
+            .collect()
+    }
+
+    /// Returns the given lines without their trailing spaces, i.e. the padding of the header.
+    fn lines_trimmed<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+        lines.iter().map(|line| line.trim_end()).collect()
+    }
+}
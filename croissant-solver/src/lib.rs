@@ -1,20 +1,216 @@
 //! This library defines the interface of a SAT solver. It is meant to be consumed by
 //! [croissant-crossword](https://crates.io/crates/croissant-crossword/).
 
+mod at_most_one;
+mod cardinality;
+mod dimacs;
+mod model;
+
+pub use crate::at_most_one::AtMostOneEncoding;
+pub use crate::cardinality::{
+    add_at_least_k_clauses_to, add_at_most_k_clauses_to, add_pb_le_clauses_to,
+};
+pub use crate::dimacs::DimacsWriter;
+pub use crate::model::{Lit, Model, Var};
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
 /// Definition of a SAT solver.
 ///
-/// It is an iterator over the models satisfying the problem. A model is a vector indexed by the variables, whose values
-/// indicate the state of the corresponding variable. A positive value indicates that the corresponding variable is
-/// true; a negative value indicates that the corresponding variable is false.
+/// It is an iterator over the [Model]s satisfying the problem, which give the value of each variable.
 ///
-/// Implementation *may* return only the state of relevant variables defined by [SolverConfigurator::set_relevant_variables]
+/// Implementation *may* assign only the relevant variables defined by [SolverConfigurator::set_relevant_variables]
 /// instead of all the variables of the problems.
 ///
 /// A solver can either be mutable - a [ConfigurableSolver] - or immutable and built using a [SolverBuilder]. Implement
 /// one of these two traits, at your convenience: Both can be used by
 /// [croissant-crossword](https://crates.io/crates/croissant-crossword/).
-pub trait Solver: Iterator<Item = Vec<i32>> {
-    // Nothing more than an iterator on the solutions for now.
+///
+/// A solver may also support solving under assumptions, see [solve_with_assumptions](Self::solve_with_assumptions).
+pub trait Solver: Iterator<Item = Model> {
+    /// Searches the next model, like [Iterator::next], but tells an interrupted search apart from the absence of
+    /// model, see [SolverConfigurator::set_limits].
+    ///
+    /// Returns [SolveOutcome::Sat] with the next model, which is then excluded from the next calls, or
+    /// [SolveOutcome::Unsat] if there is no model left, or [SolveOutcome::Interrupted] if the search has been
+    /// interrupted before finding out, or [SolveOutcome::Failed] if the solver failed to search.
+    ///
+    /// Default implementation relies on [Iterator::next]: It never returns [SolveOutcome::Interrupted].
+    fn solve_next(&mut self) -> SolveOutcome {
+        match self.next() {
+            Some(model) => SolveOutcome::Sat(model),
+            None => SolveOutcome::Unsat,
+        }
+    }
+
+    /// Solves the problem under the given assumptions, i.e. literals which are considered true for this call only.
+    ///
+    /// Returns [SolveOutcome::Sat] with a model satisfying both the problem and the assumptions, if any. Contrary to
+    /// [Iterator::next], the returned model is *not* excluded from the next calls. Clauses learnt during a call are
+    /// kept for the next ones, which makes re-solving a slightly modified problem much faster than solving it from
+    /// scratch.
+    ///
+    /// Default implementation returns [SolveOutcome::Unsupported].
+    fn solve_with_assumptions(&mut self, _assumptions: &[Lit]) -> SolveOutcome {
+        SolveOutcome::Unsupported
+    }
+
+    /// Returns the assumptions which caused the last call to [solve_with_assumptions](Self::solve_with_assumptions)
+    /// to return [SolveOutcome::Unsat], i.e. a subset of the assumptions which is enough to make the problem
+    /// unsatisfiable. It is not necessarily minimal.
+    ///
+    /// Returns [None] if the last call did not return [SolveOutcome::Unsat] or if the solver is unable to tell.
+    ///
+    /// Default implementation returns [None].
+    fn failed_assumptions(&self) -> Option<Vec<Lit>> {
+        None
+    }
+
+    /// Returns this solver as a [SolverConfigurator], in order to add clauses to the problem between two calls, e.g.
+    /// to constrain the next solutions depending on the previous ones.
+    ///
+    /// Returns [None] if the solver does not support adding clauses once solving has started.
+    ///
+    /// Default implementation returns [None].
+    fn as_configurator(&mut self) -> Option<&mut dyn SolverConfigurator> {
+        None
+    }
+
+    /// Returns the statistics of this solver so far. Fields the solver is unable to tell are left to their default
+    /// value.
+    ///
+    /// Default implementation returns empty statistics.
+    fn stats(&self) -> SolverStats {
+        SolverStats::default()
+    }
+}
+
+/// The outcome of a search, i.e. of [Solver::solve_next] or of [Solver::solve_with_assumptions].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SolveOutcome {
+    /// The problem is satisfiable, under the assumptions if any. Contains the model found.
+    Sat(Model),
+    /// The problem is not satisfiable, under the assumptions if any.
+    Unsat,
+    /// The search has been interrupted before finding out, see [SolverConfigurator::set_limits].
+    Interrupted,
+    /// The solver does not support solving under assumptions.
+    Unsupported,
+    /// The solver failed to search, e.g. an external program crashed. Contains the error.
+    Failed(SolverError),
+}
+
+/// The limits of a search, see [SolverConfigurator::set_limits]. Each limit applies to each search separately, i.e. to
+/// each call to [Iterator::next], [Solver::solve_next] or [Solver::solve_with_assumptions]. No limit by default.
+#[derive(Clone, Debug, Default)]
+pub struct SearchLimits {
+    /// The maximal duration of a search
+    pub timeout: Option<Duration>,
+    /// The maximal number of conflicts of a search
+    pub conflicts: Option<u64>,
+    /// The token allowing to cancel a search, e.g. from another thread
+    pub cancellation: Option<CancellationToken>,
+}
+
+/// The statistics of a solver, see [Solver::stats]. Counters are [None] if the solver is unable to tell them.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolverStats {
+    /// The number of variables
+    pub variables: usize,
+    /// The number of clauses, or of constraints for the solvers supporting richer constraints than clauses
+    pub clauses: usize,
+    /// The number of conflicts
+    pub conflicts: Option<u64>,
+    /// The number of decisions
+    pub decisions: Option<u64>,
+    /// The number of propagations
+    pub propagations: Option<u64>,
+    /// The number of restarts
+    pub restarts: Option<u64>,
+    /// The time spent encoding the problem
+    pub encoding_time: Duration,
+    /// The time spent searching solutions
+    pub solving_time: Duration,
+}
+
+impl fmt::Display for SolverStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counter =
+            |counter: Option<u64>| counter.map_or("?".to_string(), |count| count.to_string());
+        write!(
+            f,
+            "variables: {}, clauses: {}, conflicts: {}, decisions: {}, propagations: {}, restarts: {}, \
+            encoding time: {:?}, solving time: {:?}",
+            self.variables,
+            self.clauses,
+            counter(self.conflicts),
+            counter(self.decisions),
+            counter(self.propagations),
+            counter(self.restarts),
+            self.encoding_time,
+            self.solving_time
+        )
+    }
+}
+
+/// An error of a solver or of a search, e.g. a failure to create the solver, see [SolverBuilder::build], or an
+/// interrupted search.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolverError {
+    /// The error details.
+    message: String,
+}
+
+impl SolverError {
+    /// Creates an error with the given details.
+    pub fn new(message: impl Into<String>) -> Self {
+        SolverError {
+            message: message.into(),
+        }
+    }
+
+    /// Returns the error details.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for SolverError {}
+
+/// A function receiving the statistics of a running search, see [SolverConfigurator::set_progress_callback].
+pub type ProgressCallback = Box<dyn FnMut(&SolverStats) + Send>;
+
+/// A token allowing to cancel a search cooperatively: The solver regularly checks whether the token is cancelled and
+/// stops searching if so. Clones share the same state.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new token, not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the current and future searches using this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` iff this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
 }
 
 /// Definition of a solver configurator.
@@ -29,14 +225,59 @@ pub trait SolverConfigurator {
         // Do nothing by default.
     }
 
+    /// Returns the number of variables known so far, i.e. the biggest variable either given to
+    /// [allocate_variables](Self::allocate_variables) or used by a clause, or [None] if unknown. The following variables
+    /// are free, e.g. for the auxiliary variables of [add_at_most_one_with](Self::add_at_most_one_with).
+    ///
+    /// Default implementation returns [None].
+    fn variables_count(&self) -> Option<usize> {
+        None
+    }
+
     /// Indicates which variables are relevant for the problem.
     /// It is a hint for the solver, that can help implementation to avoid duplica
-    fn set_relevant_variables(&mut self, _relevant_variables: Vec<usize>) {
+    fn set_relevant_variables(&mut self, _relevant_variables: Vec<Var>) {
+        // Do nothing by default.
+    }
+
+    /// Gives the seed to use to randomize the search, e.g. the initial phases of the variables or the order of the
+    /// decisions. The same seed on the same problem should lead to the same solutions. It is a hint: Solvers unable to
+    /// randomize their search may ignore it.
+    ///
+    /// Default implementation does nothing.
+    fn set_seed(&mut self, _seed: u64) {
+        // Do nothing by default.
+    }
+
+    /// Sets the limits of the searches. When a limit is reached, the search stops and returns
+    /// [SolveOutcome::Interrupted], see [Solver::solve_next]. Solvers may only support some limits, see
+    /// [supports_limits](Self::supports_limits), or check them only between two searches: See their documentation.
+    ///
+    /// Default implementation does nothing, i.e. searches are never interrupted.
+    fn set_limits(&mut self, _limits: SearchLimits) {
+        // Do nothing by default.
+    }
+
+    /// Returns whether all the given limits are supported, i.e. enforced rather than ignored once given to
+    /// [set_limits](Self::set_limits), e.g. to reject the limits a solver cannot honour.
+    ///
+    /// Default implementation returns `true` only if no limit is set, since the default
+    /// [set_limits](Self::set_limits) ignores them all.
+    fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        limits.timeout.is_none() && limits.conflicts.is_none() && limits.cancellation.is_none()
+    }
+
+    /// Sets the function to call with the current statistics while searching, in order to report progress. Solvers
+    /// call it periodically during a search, or only between two searches if unable to do otherwise: See their
+    /// documentation.
+    ///
+    /// Default implementation does nothing, i.e. the callback is never called.
+    fn set_progress_callback(&mut self, _callback: ProgressCallback) {
         // Do nothing by default.
     }
 
     /// Adds the given literals as an *at-least-one* clause, i.e. a disjunction (= or).
-    fn add_clause(&mut self, literals: &[i32]);
+    fn add_clause(&mut self, literals: &[Lit]);
 
     /// Adds the given literals as an *exactly-one* clause.
     ///
@@ -44,7 +285,7 @@ pub trait SolverConfigurator {
     ///
     /// Default implementation creates these corresponding clauses and add them using [add_clause](Self::add_clause) and
     /// [add_at_most_one](Self::add_at_most_one). Implementors may override this function for better performances.
-    fn add_exactly_one(&mut self, literals: &[i32]) {
+    fn add_exactly_one(&mut self, literals: &[Lit]) {
         self.add_clause(literals);
         self.add_at_most_one(literals);
     }
@@ -55,14 +296,122 @@ pub trait SolverConfigurator {
     /// This is equivalent to saying that for all pairs of literals, *at-least-one* is false. In other words, an
     /// *at-most-one* clause is equivalent to all the *at-least-one* clauses for each pair of negated literals.
     ///
-    /// Default implementation creates these corresponding clauses and add them using [add_clause](Self::add_clause).
-    /// Implementors may override this function for better performances
-    fn add_at_most_one(&mut self, literals: &[i32]) {
-        for i in 0..literals.len() {
-            for j in (i + 1)..literals.len() {
-                self.add_clause(&[-literals[i], -literals[j]]);
-            }
-        }
+    /// Default implementation uses [add_at_most_one_with](Self::add_at_most_one_with) with the encoding suited to the
+    /// number of literals, see [AtMostOneEncoding::auto]: The pairwise clauses for a few literals, an encoding with
+    /// auxiliary variables above. Implementors may override this function for better performances.
+    fn add_at_most_one(&mut self, literals: &[Lit]) {
+        self.add_at_most_one_with(literals, AtMostOneEncoding::auto(literals.len()));
+    }
+
+    /// Adds the given literals as an *exactly-one* clause, encoding its *at-most-one* part with the given encoding.
+    ///
+    /// Default implementation adds the *at-least-one* clause using [add_clause](Self::add_clause) and the *at-most-one*
+    /// clauses using [add_at_most_one_with](Self::add_at_most_one_with).
+    fn add_exactly_one_with(&mut self, literals: &[Lit], encoding: AtMostOneEncoding) {
+        self.add_clause(literals);
+        self.add_at_most_one_with(literals, encoding);
+    }
+
+    /// Adds the given literals as an *at-most-one* clause, using the given encoding.
+    ///
+    /// The auxiliary variables of the encoding, if any, are the variables following the
+    /// [variables count](Self::variables_count). Solvers returning all the variables in their models, rather than the
+    /// relevant ones only, may then return models differing only by these auxiliary variables.
+    ///
+    /// Default implementation adds the clauses of the encoding using [add_clause](Self::add_clause), then declares the
+    /// auxiliary variables using [allocate_variables](Self::allocate_variables). If the variables count is unknown, the
+    /// pairwise encoding is used instead, since it needs no auxiliary variable.
+    fn add_at_most_one_with(&mut self, literals: &[Lit], encoding: AtMostOneEncoding) {
+        cardinality::add_with_auxiliary_variables(
+            self,
+            |next_variable, configurator| encoding.add_to(literals, next_variable, configurator),
+            |configurator| {
+                AtMostOneEncoding::Pairwise.add_to(literals, 1, configurator);
+            },
+        );
+    }
+
+    /// Adds the constraint stating that at most `k` of the given literals are true.
+    ///
+    /// Default implementation adds the clauses of a totalizer using [add_clause](Self::add_clause), see
+    /// [add_at_most_k_clauses_to], its auxiliary variables following the [variables count](Self::variables_count), then
+    /// declares them using [allocate_variables](Self::allocate_variables). If the variables count is unknown, one clause
+    /// per combination of `k + 1` literals is added instead, whose number grows exponentially. Implementors may override
+    /// this function for better performances.
+    fn add_at_most_k(&mut self, literals: &[Lit], k: usize) {
+        cardinality::add_with_auxiliary_variables(
+            self,
+            |next_variable, configurator| {
+                add_at_most_k_clauses_to(literals, k, next_variable, configurator)
+            },
+            |configurator| {
+                let weights = vec![1; literals.len()];
+                let bound = k.min(literals.len()) as i64;
+                cardinality::add_exceeding_combinations_clauses_to(
+                    literals,
+                    &weights,
+                    bound,
+                    configurator,
+                );
+            },
+        );
+    }
+
+    /// Adds the constraint stating that at least `k` of the given literals are true.
+    ///
+    /// Default implementation adds the clauses of a sequential counter using [add_clause](Self::add_clause), see
+    /// [add_at_least_k_clauses_to], its auxiliary variables following the [variables count](Self::variables_count),
+    /// then declares them using [allocate_variables](Self::allocate_variables). If the variables count is unknown, one
+    /// clause per combination of `n - k + 1` literals is added instead, whose number grows exponentially. Implementors
+    /// may override this function for better performances.
+    fn add_at_least_k(&mut self, literals: &[Lit], k: usize) {
+        cardinality::add_with_auxiliary_variables(
+            self,
+            |next_variable, configurator| {
+                add_at_least_k_clauses_to(literals, k, next_variable, configurator)
+            },
+            |configurator| {
+                // At least k literals ⇔ at most n - k negations
+                let negations: Vec<Lit> = literals.iter().map(|&literal| -literal).collect();
+                let weights = vec![1; literals.len()];
+                let bound = literals.len() as i64 - k.min(literals.len() + 1) as i64;
+                cardinality::add_exceeding_combinations_clauses_to(
+                    &negations,
+                    &weights,
+                    bound,
+                    configurator,
+                );
+            },
+        );
+    }
+
+    /// Adds the pseudo-Boolean constraint stating that the sum of the weights of the true literals among the given ones
+    /// is at most the given bound. Weights may be negative.
+    ///
+    /// Default implementation adds the clauses of an adder network using [add_clause](Self::add_clause), see
+    /// [add_pb_le_clauses_to], its auxiliary variables following the [variables count](Self::variables_count), then
+    /// declares them using [allocate_variables](Self::allocate_variables). If the variables count is unknown, one clause
+    /// per minimal combination of literals exceeding the bound is added instead, whose number grows exponentially.
+    /// Implementors may override this function for better performances.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the numbers of literals and of weights differ.
+    fn add_pb_le(&mut self, literals: &[Lit], weights: &[i64], bound: i64) {
+        cardinality::add_with_auxiliary_variables(
+            self,
+            |next_variable, configurator| {
+                add_pb_le_clauses_to(literals, weights, bound, next_variable, configurator)
+            },
+            |configurator| {
+                cardinality::add_exceeding_combinations_clauses_to(
+                    literals,
+                    weights,
+                    bound,
+                    configurator,
+                );
+            },
+        );
     }
 
     /// Adds clauses describing the equivalence between the given literal and the given conjunction
@@ -74,7 +423,7 @@ pub trait SolverConfigurator {
     ///
     /// Default implementation adds these corresponding clauses using [add_clause](Self::add_clause). Implementors
     /// may override this function for better performance.
-    fn add_and(&mut self, literal: i32, conjunction: &[i32]) {
+    fn add_and(&mut self, literal: Lit, conjunction: &[Lit]) {
         let mut last_clause = Vec::with_capacity(conjunction.len() + 1);
         for &conjunction_literal in conjunction {
             self.add_clause(&[-literal, conjunction_literal]);
@@ -100,10 +449,54 @@ pub trait ConfigurableSolver: SolverConfigurator + Solver {
 /// upon call to the build function, and you'd better implement [ConfigurableSolver] instead.
 pub trait SolverBuilder: SolverConfigurator {
     /// Builds the solver.
-    fn build(&self) -> Box<dyn Solver<Item = Vec<i32>>>;
+    ///
+    /// Returns a [SolverError] if the solver cannot be created, e.g. if the underlying solver rejects the problem.
+    fn build(&self) -> Result<Box<dyn Solver<Item = Model>>, SolverError>;
+}
+
+/// A way to create a solver, either a [ConfigurableSolver] or a solver built by a [SolverBuilder], e.g. to create
+/// solvers in other threads.
+///
+/// Only the factory needs to cross threads, hence the `Send + Sync` bound on the factory but not on the solver: Solvers
+/// holding non thread-safe handles, e.g. [Rc](std::rc::Rc)s or raw pointers, can be created in the thread using them.
+pub enum SolverFactory {
+    /// Creates a [ConfigurableSolver].
+    Configurable(Box<dyn Fn() -> Box<dyn ConfigurableSolver<Item = Model>> + Send + Sync>),
+    /// Creates a [SolverBuilder], from which the solver is then built.
+    Builder(Box<dyn Fn() -> Box<dyn SolverBuilder> + Send + Sync>),
 }
 
-/// Tests for default [SolverConfigurator] function implementations.
+impl SolverFactory {
+    /// Creates a solver, giving the problem to it with the given function. Returns a [SolverError] if the solver cannot
+    /// be built, see [SolverBuilder::build].
+    pub fn create(
+        &self,
+        configure: impl FnOnce(&mut dyn SolverConfigurator),
+    ) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+        match self {
+            SolverFactory::Configurable(create) => {
+                let mut solver = create();
+                configure(solver.as_mut());
+                Ok(solver)
+            }
+            SolverFactory::Builder(create) => {
+                let mut solver_builder = create();
+                configure(solver_builder.as_mut());
+                solver_builder.build()
+            }
+        }
+    }
+
+    /// Returns whether the created solvers support all the given limits, see [SolverConfigurator::supports_limits].
+    pub fn supports_limits(&self, limits: &SearchLimits) -> bool {
+        match self {
+            SolverFactory::Configurable(create) => create().supports_limits(limits),
+            SolverFactory::Builder(create) => create().supports_limits(limits),
+        }
+    }
+}
+
+/// Tests for default [Solver] and [SolverConfigurator] function implementations.
 #[cfg(test)]
 mod test {
     use super::*;
@@ -113,15 +506,158 @@ mod test {
     }
 
     impl SolverConfigurator for TestSolverConfigurator {
-        fn add_clause(&mut self, literals: &[i32]) {
-            self.clauses.push(literals.to_vec())
+        fn add_clause(&mut self, literals: &[Lit]) {
+            self.clauses
+                .push(literals.iter().map(|&literal| i32::from(literal)).collect())
+        }
+    }
+
+    impl SolverBuilder for TestSolverConfigurator {
+        /// Builds a solver giving the first clause as its only model.
+        fn build(&self) -> Result<Box<dyn Solver<Item = Model>>, SolverError> {
+            let model = self.clauses.first().map(|clause| {
+                clause
+                    .iter()
+                    .map(|&literal| Lit::from_raw(literal))
+                    .collect()
+            });
+            Ok(Box::new(TestModels(model)))
+        }
+    }
+
+    /// Returns the literals of the given raw representations.
+    fn literals(literals: &[i32]) -> Vec<Lit> {
+        literals
+            .iter()
+            .map(|&literal| Lit::from_raw(literal))
+            .collect()
+    }
+
+    struct TestModels(Option<Model>);
+
+    impl Solver for TestModels {}
+
+    impl Iterator for TestModels {
+        type Item = Model;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.take()
         }
     }
 
+    struct TestSolver;
+
+    impl Iterator for TestSolver {
+        type Item = Model;
+        fn next(&mut self) -> Option<Self::Item> {
+            None
+        }
+    }
+
+    impl Solver for TestSolver {}
+
+    #[test]
+    fn solve_with_assumptions_unsupported() {
+        let mut solver = TestSolver;
+
+        assert_eq!(
+            SolveOutcome::Unsupported,
+            solver.solve_with_assumptions(&literals(&[1, -2]))
+        );
+        assert_eq!(None, solver.failed_assumptions());
+    }
+
+    #[test]
+    fn solve_next_default() {
+        let mut solver = TestSolver;
+
+        assert_eq!(SolveOutcome::Unsat, solver.solve_next());
+    }
+
+    #[test]
+    fn stats_default() {
+        let solver = TestSolver;
+
+        assert_eq!(SolverStats::default(), solver.stats());
+    }
+
+    #[test]
+    fn stats_display() {
+        let stats = SolverStats {
+            variables: 10,
+            clauses: 20,
+            conflicts: Some(3),
+            solving_time: Duration::from_millis(5),
+            ..SolverStats::default()
+        };
+
+        assert_eq!(
+            "variables: 10, clauses: 20, conflicts: 3, decisions: ?, propagations: ?, restarts: ?, \
+            encoding time: 0ns, solving time: 5ms",
+            stats.to_string()
+        );
+    }
+
+    #[test]
+    fn cancellation_token() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled(), "Token should not be cancelled");
+
+        token.cancel();
+
+        assert!(clone.is_cancelled(), "Token should be cancelled");
+    }
+
+    #[test]
+    fn as_configurator_unsupported() {
+        let mut solver = TestSolver;
+
+        assert!(
+            solver.as_configurator().is_none(),
+            "Unexpected configurator"
+        );
+    }
+
+    #[test]
+    fn solver_factory() {
+        let factory = SolverFactory::Builder(Box::new(|| {
+            Box::new(TestSolverConfigurator { clauses: vec![] })
+        }));
+
+        let mut solver = factory
+            .create(|configurator| configurator.add_clause(&literals(&[1, 2])))
+            .unwrap();
+
+        let literals: Vec<i32> = solver.next().unwrap().literals().map(i32::from).collect();
+        assert_eq!(vec![1, 2], literals);
+    }
+
+    #[test]
+    fn supports_limits() {
+        let factory = SolverFactory::Builder(Box::new(|| {
+            Box::new(TestSolverConfigurator { clauses: vec![] })
+        }));
+        let timeout = SearchLimits {
+            timeout: Some(Duration::from_secs(1)),
+            ..SearchLimits::default()
+        };
+
+        assert!(factory.supports_limits(&SearchLimits::default()));
+        assert!(!factory.supports_limits(&timeout));
+    }
+
+    #[test]
+    fn solver_error() {
+        let error = SolverError::new("Cannot create solver");
+
+        assert_eq!("Cannot create solver", error.to_string());
+        assert_eq!("Cannot create solver", error.message());
+    }
+
     #[test]
     fn add_exactly_one() {
         let mut solver_builder = TestSolverConfigurator { clauses: vec![] };
-        let literals = vec![1, 2, 3];
+        let literals = literals(&[1, 2, 3]);
 
         solver_builder.add_exactly_one(&literals);
 
@@ -134,7 +670,7 @@ mod test {
     #[test]
     fn add_at_most_one() {
         let mut solver_builder = TestSolverConfigurator { clauses: vec![] };
-        let literals = vec![1, 2, 3];
+        let literals = literals(&[1, 2, 3]);
 
         solver_builder.add_at_most_one(&literals);
 
@@ -144,13 +680,60 @@ mod test {
         );
     }
 
+    #[test]
+    fn add_at_most_one_with_unknown_variables_count() {
+        let mut solver_builder = TestSolverConfigurator { clauses: vec![] };
+        let literals = literals(&[1, 2, 3]);
+
+        solver_builder.add_at_most_one_with(&literals, AtMostOneEncoding::Sequential);
+
+        assert_eq!(
+            vec![vec![-1, -2], vec![-1, -3], vec![-2, -3],],
+            solver_builder.clauses
+        );
+    }
+
+    #[test]
+    fn add_at_most_k_with_unknown_variables_count() {
+        let mut solver_builder = TestSolverConfigurator { clauses: vec![] };
+
+        solver_builder.add_at_most_k(&literals(&[1, 2, 3]), 1);
+
+        assert_eq!(
+            vec![vec![-1, -2], vec![-1, -3], vec![-2, -3]],
+            solver_builder.clauses
+        );
+    }
+
+    #[test]
+    fn add_at_least_k_with_unknown_variables_count() {
+        let mut solver_builder = TestSolverConfigurator { clauses: vec![] };
+
+        solver_builder.add_at_least_k(&literals(&[1, 2, 3]), 2);
+
+        assert_eq!(
+            vec![vec![1, 2], vec![1, 3], vec![2, 3]],
+            solver_builder.clauses
+        );
+    }
+
+    #[test]
+    fn add_pb_le_with_unknown_variables_count() {
+        let mut solver_builder = TestSolverConfigurator { clauses: vec![] };
+
+        // 2·x1 + x2 + x3 ≤ 2
+        solver_builder.add_pb_le(&literals(&[1, 2, 3]), &[2, 1, 1], 2);
+
+        assert_eq!(vec![vec![-1, -2], vec![-1, -3]], solver_builder.clauses);
+    }
+
     #[test]
     fn add_and() {
         let mut solver_builder = TestSolverConfigurator { clauses: vec![] };
-        let conjunction = vec![-1, 6, -7];
+        let conjunction = literals(&[-1, 6, -7]);
 
         // 42 ⇔ -1 ∧ 6 ∧ -7
-        solver_builder.add_and(42, &conjunction);
+        solver_builder.add_and(Lit::from_raw(42), &conjunction);
 
         // (-42 ∨ -1) ∧ (-42 ∨ 6) ∧ (-42 ∨ -7) ∧ (1 ∨ -6 ∨ 7 ∨ 42)
         assert_eq!(
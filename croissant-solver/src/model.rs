@@ -0,0 +1,225 @@
+use std::fmt;
+use std::ops::Neg;
+
+/// A variable of the problem, identified by a strictly positive index.
+///
+/// Solvers and their [configurators](crate::SolverConfigurator) take variables and [literals](Lit): Their raw
+/// representations are only used at the boundary with the underlying solvers, e.g. as DIMACS literals.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Var(u32);
+
+impl Var {
+    /// Creates the variable of the given index.
+    ///
+    /// Panics if the index is zero or does not fit in a raw literal.
+    pub fn new(index: usize) -> Self {
+        assert!(
+            index > 0 && index <= i32::MAX as usize,
+            "Variable index should be in 1..=i32::MAX, was {index}"
+        );
+        Var(index as u32)
+    }
+
+    /// Returns the index of this variable.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Returns the literal saying this variable has the given value.
+    pub fn literal(self, value: bool) -> Lit {
+        let literal = Lit(self.0 as i32);
+        if value {
+            literal
+        } else {
+            -literal
+        }
+    }
+}
+
+impl From<Var> for i32 {
+    fn from(variable: Var) -> Self {
+        variable.0 as i32
+    }
+}
+
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A literal, i.e. a [Var] or its negation.
+///
+/// Its raw representation is the one of DIMACS: The index of the variable if the literal is positive, its opposite
+/// otherwise.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Lit(i32);
+
+impl Lit {
+    /// Creates the literal of the given raw representation.
+    ///
+    /// Panics if the raw literal is zero.
+    pub fn from_raw(literal: i32) -> Self {
+        assert_ne!(literal, 0, "Raw literal should not be zero");
+        Lit(literal)
+    }
+
+    /// Returns the variable of this literal.
+    pub fn var(self) -> Var {
+        Var(self.0.unsigned_abs())
+    }
+
+    /// Returns whether this literal says its variable is true.
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+}
+
+impl Neg for Lit {
+    type Output = Lit;
+
+    fn neg(self) -> Self::Output {
+        Lit(-self.0)
+    }
+}
+
+impl From<Var> for Lit {
+    fn from(variable: Var) -> Self {
+        variable.literal(true)
+    }
+}
+
+impl From<Lit> for i32 {
+    fn from(literal: Lit) -> Self {
+        literal.0
+    }
+}
+
+impl fmt::Display for Lit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A model of the problem, i.e. the values of its variables in a solution.
+///
+/// A model may not assign all the variables of the problem: Solvers may only assign the relevant variables defined by
+/// [SolverConfigurator::set_relevant_variables](crate::SolverConfigurator::set_relevant_variables). Variables not
+/// assigned have no value.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Model {
+    /// The values of the variables, indexed by variable index minus one.
+    values: Vec<Option<bool>>,
+}
+
+impl Model {
+    /// Creates a model assigning no variable.
+    pub fn new() -> Self {
+        Model::default()
+    }
+
+    /// Assigns the variable of the given literal so that the literal is true.
+    pub fn set(&mut self, literal: Lit) {
+        let index = literal.var().index() - 1;
+        if index >= self.values.len() {
+            self.values.resize(index + 1, None);
+        }
+        self.values[index] = Some(literal.is_positive());
+    }
+
+    /// Returns the value of the given variable, or [None] if the variable is not assigned.
+    pub fn value(&self, variable: Var) -> Option<bool> {
+        self.values.get(variable.index() - 1).copied().flatten()
+    }
+
+    /// Returns whether the given variable is assigned and true.
+    pub fn is_true(&self, variable: Var) -> bool {
+        self.value(variable) == Some(true)
+    }
+
+    /// Returns whether the variable of the given literal is assigned so that the literal is true.
+    pub fn satisfies(&self, literal: Lit) -> bool {
+        self.value(literal.var()) == Some(literal.is_positive())
+    }
+
+    /// Returns the literal of the given variable which is true in this model, or [None] if the variable is not
+    /// assigned.
+    pub fn literal(&self, variable: Var) -> Option<Lit> {
+        self.value(variable).map(|value| variable.literal(value))
+    }
+
+    /// Returns the true literals of the assigned variables, by increasing variable.
+    pub fn literals(&self) -> impl Iterator<Item = Lit> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| value.map(|value| Var::new(index + 1).literal(value)))
+    }
+}
+
+impl FromIterator<Lit> for Model {
+    fn from_iter<T: IntoIterator<Item = Lit>>(literals: T) -> Self {
+        let mut model = Model::new();
+        for literal in literals {
+            model.set(literal);
+        }
+        model
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn literals_of_variable() {
+        let variable = Var::new(3);
+
+        assert_eq!(3, i32::from(variable.literal(true)));
+        assert_eq!(-3, i32::from(variable.literal(false)));
+        assert_eq!(variable, Lit::from_raw(-3).var());
+        assert_eq!(Lit::from_raw(-3), -Lit::from(variable));
+    }
+
+    #[test]
+    #[should_panic(expected = "Variable index should be in 1..=i32::MAX, was 0")]
+    fn variable_zero() {
+        Var::new(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Raw literal should not be zero")]
+    fn literal_zero() {
+        Lit::from_raw(0);
+    }
+
+    #[test]
+    fn model_values() {
+        let model: Model = [1, -2, 4].into_iter().map(Lit::from_raw).collect();
+
+        assert_eq!(Some(true), model.value(Var::new(1)));
+        assert_eq!(Some(false), model.value(Var::new(2)));
+        assert_eq!(None, model.value(Var::new(3)));
+        assert_eq!(Some(true), model.value(Var::new(4)));
+        assert_eq!(None, model.value(Var::new(5)));
+        assert!(model.is_true(Var::new(1)));
+        assert!(!model.is_true(Var::new(2)));
+        assert!(!model.is_true(Var::new(3)));
+        assert_eq!(Some(Lit::from_raw(-2)), model.literal(Var::new(2)));
+        assert!(model.satisfies(Lit::from_raw(-2)));
+        assert!(!model.satisfies(Lit::from_raw(2)));
+        assert!(!model.satisfies(Lit::from_raw(-3)));
+    }
+
+    #[test]
+    fn model_literals() {
+        let mut model = Model::new();
+        model.set(Lit::from_raw(3));
+        model.set(Lit::from_raw(-1));
+        model.set(Lit::from_raw(-3));
+
+        let literals: Vec<i32> = model.literals().map(i32::from).collect();
+
+        assert_eq!(vec![-1, -3], literals);
+    }
+}
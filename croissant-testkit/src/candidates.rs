@@ -0,0 +1,95 @@
+//! Tests of [Crossword::candidate_letters_with].
+
+use croissant_crossword::crossword::{CandidateLetters, Crossword};
+use croissant_solver::SolverFactory;
+
+use crate::{words, WORDS};
+
+/// Defines the tests of this module, for the solvers created by the given factory function.
+#[macro_export]
+macro_rules! candidates_tests {
+    ($factory:expr) => {
+        $crate::tests!(
+            $factory,
+            candidates: backbone,
+            several_solutions,
+            approximation_contains_exact_candidates,
+            no_solution,
+            blocks
+        );
+    };
+}
+
+pub fn backbone(factory: fn() -> SolverFactory) {
+    let candidates = candidate_letters("A.\n..", &["AB", "BA", "BB", "CA"], factory);
+
+    assert_eq!(['A'], candidates.at(0, 0));
+    assert_eq!(['B'], candidates.at(0, 1));
+    assert_eq!(['B'], candidates.at(1, 0));
+    assert_eq!(['A', 'B'], candidates.at(1, 1));
+    assert!(candidates.is_forced(0, 1), "Cell should be forced");
+    assert!(!candidates.is_forced(1, 1), "Cell should not be forced");
+}
+
+pub fn several_solutions(factory: fn() -> SolverFactory) {
+    let candidates = candidate_letters("...\n...\n...", &WORDS, factory);
+
+    assert_eq!(['A', 'B'], candidates.at(0, 0));
+    assert_eq!(['A', 'B', 'C'], candidates.at(0, 2));
+    assert_eq!(['A', 'B'], candidates.at(1, 1));
+    assert_eq!(['A', 'B', 'D'], candidates.at(1, 2));
+    assert_eq!(['A', 'B', 'E'], candidates.at(2, 2));
+}
+
+pub fn approximation_contains_exact_candidates(factory: fn() -> SolverFactory) {
+    let words = words(&WORDS);
+    let crossword = Crossword::try_from("...\n...\n...", &words).unwrap();
+    let approximation = crossword.approximate_candidate_letters();
+    let candidates = candidate_letters("...\n...\n...", &WORDS, factory);
+
+    for row in 0..3 {
+        for column in 0..3 {
+            for letter in candidates.at(row, column) {
+                assert!(
+                    approximation.at(row, column).contains(letter),
+                    "Missing {letter} at ({row},{column})"
+                );
+            }
+        }
+    }
+}
+
+pub fn no_solution(factory: fn() -> SolverFactory) {
+    let candidates = candidate_letters("..\n..", &["ABC"], factory);
+
+    assert!(
+        candidates.at(0, 0).is_empty(),
+        "Cell should have no candidate"
+    );
+    assert!(
+        candidates.at(1, 1).is_empty(),
+        "Cell should have no candidate"
+    );
+}
+
+pub fn blocks(factory: fn() -> SolverFactory) {
+    let candidates = candidate_letters("#.\n..", &["AB"], factory);
+
+    assert!(
+        candidates.at(0, 0).is_empty(),
+        "Block should have no candidate"
+    );
+    assert_eq!(['A'], candidates.at(0, 1));
+    assert_eq!(['B'], candidates.at(1, 1));
+}
+
+/// Computes the candidate letters of the given grid, using a solver created by the given factory.
+fn candidate_letters(
+    grid: &str,
+    words: &[&str],
+    factory: fn() -> SolverFactory,
+) -> CandidateLetters {
+    let words = crate::words(words);
+    let crossword = Crossword::try_from(grid, &words).unwrap();
+    crate::candidate_letters(crossword, &factory())
+}
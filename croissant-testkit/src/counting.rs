@@ -0,0 +1,104 @@
+//! Tests of [CrosswordSolutions::count_solutions] and [Crossword::count_solutions_exactly_with].
+
+use croissant_crossword::crossword::{Crossword, CrosswordSolutions};
+use croissant_crossword::encoding::{Encoding, MddEncoding, WordLevelEncoding};
+use croissant_solver::SolverFactory;
+
+use crate::{words, WORDS};
+
+/// Defines the tests of this module, for the solvers created by the given factory function.
+#[macro_export]
+macro_rules! counting_tests {
+    ($factory:expr) => {
+        $crate::tests!(
+            $factory,
+            counting: count_solutions,
+            count_solutions_limit,
+            is_unique,
+            count_solutions_exactly,
+            count_solutions_exactly_projects_on_cells
+        );
+    };
+}
+
+pub fn count_solutions(factory: fn() -> SolverFactory) {
+    assert_eq!(
+        4,
+        solutions("...\n...\n...", Box::new(MddEncoding), factory)
+            .count_solutions(10)
+            .unwrap()
+    );
+    assert_eq!(
+        4,
+        solutions("...\n...\n...", Box::new(WordLevelEncoding), factory)
+            .count_solutions(10)
+            .unwrap()
+    );
+}
+
+pub fn count_solutions_limit(factory: fn() -> SolverFactory) {
+    assert_eq!(
+        2,
+        solutions("...\n...\n...", Box::new(MddEncoding), factory)
+            .count_solutions(2)
+            .unwrap()
+    );
+}
+
+pub fn is_unique(factory: fn() -> SolverFactory) {
+    assert!(
+        !solutions("...\n...\n...", Box::new(MddEncoding), factory)
+            .is_unique()
+            .unwrap(),
+        "Grid has several solutions"
+    );
+    assert!(
+        !solutions("...\n...\n..E", Box::new(MddEncoding), factory)
+            .is_unique()
+            .unwrap(),
+        "Grid has two solutions"
+    );
+    assert!(
+        solutions("...\n...\n.DE", Box::new(MddEncoding), factory)
+            .is_unique()
+            .unwrap(),
+        "Grid has one solution"
+    );
+    assert!(
+        !solutions("...\n...\nZ..", Box::new(MddEncoding), factory)
+            .is_unique()
+            .unwrap(),
+        "Grid has no solution"
+    );
+}
+
+pub fn count_solutions_exactly(factory: fn() -> SolverFactory) {
+    assert_eq!(4, count_exactly("...\n...\n...", factory));
+    assert_eq!(1, count_exactly("...\n...\n.DE", factory));
+    assert_eq!(0, count_exactly("...\n...\nZ..", factory));
+}
+
+pub fn count_solutions_exactly_projects_on_cells(factory: fn() -> SolverFactory) {
+    // The isolated cell may contain any letter
+    assert_eq!(26, count_exactly("AAA\n###\n.##", factory));
+}
+
+/// Returns the solutions of the given grid, using a solver created by the given factory.
+fn solutions(
+    grid: &str,
+    encoding: Box<dyn Encoding>,
+    factory: fn() -> SolverFactory,
+) -> CrosswordSolutions {
+    let words = words(&WORDS);
+    let crossword = Crossword::try_from(grid, &words)
+        .unwrap()
+        .with_encoding(encoding);
+    crate::solve(crossword, &factory())
+}
+
+/// Counts exactly the solutions of the given grid, using a solver created by the given factory.
+fn count_exactly(grid: &str, factory: fn() -> SolverFactory) -> u64 {
+    let words = words(&WORDS);
+    let crossword = Crossword::try_from(grid, &words).unwrap();
+    crate::count_solutions_exactly(crossword, &factory())
+}
@@ -0,0 +1,110 @@
+//! Tests of [Crossword::solve_by_cubes_with] and [Crossword::solve_all_cubes_with].
+
+use croissant_crossword::crossword::{Crossword, CubeAndConquer, Splitting};
+use croissant_crossword::encoding::MddEncoding;
+use croissant_solver::SolverFactory;
+
+use crate::{words, WORDS};
+
+/// Defines the tests of this module, for the solvers created by the given factory function.
+#[macro_export]
+macro_rules! cubes_tests {
+    ($factory:expr) => {
+        $crate::tests!(
+            $factory,
+            cubes: first_solution,
+            no_solution,
+            all_cubes,
+            single_thread_fully_split,
+            limit
+        );
+    };
+}
+
+pub fn first_solution(factory: fn() -> SolverFactory) {
+    let words = words(&WORDS);
+    for splitting in [Splitting::Words, Splitting::Letters] {
+        let solution = Crossword::try_from("...\n...\n.DE", &words)
+            .unwrap()
+            .solve_by_cubes_with(factory(), &options(splitting))
+            .unwrap();
+        assert_eq!(Some("AAA\nBBB\nCDE".to_string()), solution);
+    }
+}
+
+pub fn no_solution(factory: fn() -> SolverFactory) {
+    let words = words(&WORDS);
+    for splitting in [Splitting::Words, Splitting::Letters] {
+        let solution = Crossword::try_from("...\n...\nZ..", &words)
+            .unwrap()
+            .solve_by_cubes_with(factory(), &options(splitting))
+            .unwrap();
+        assert_eq!(None, solution);
+    }
+}
+
+pub fn all_cubes(factory: fn() -> SolverFactory) {
+    let words = words(&WORDS);
+    let all_solutions: Vec<String> = {
+        let crossword = Crossword::try_from("...\n...\n...", &words)
+            .unwrap()
+            .with_encoding(Box::new(MddEncoding));
+        crate::solve(crossword, &factory()).collect()
+    };
+    for splitting in [Splitting::Words, Splitting::Letters] {
+        let solutions = Crossword::try_from("...\n...\n...", &words)
+            .unwrap()
+            .solve_all_cubes_with(factory(), &options(splitting), usize::MAX)
+            .unwrap();
+        // One solution per satisfiable cube, cubes being disjoint
+        assert!(!solutions.is_empty(), "Splitting on {splitting:?}");
+        for (index, solution) in solutions.iter().enumerate() {
+            assert!(
+                all_solutions.contains(solution),
+                "Splitting on {splitting:?}"
+            );
+            assert!(
+                !solutions[..index].contains(solution),
+                "Splitting on {splitting:?}"
+            );
+        }
+    }
+}
+
+pub fn single_thread_fully_split(factory: fn() -> SolverFactory) {
+    let words = words(&WORDS);
+    let options = CubeAndConquer::default()
+        .with_thread_count(1)
+        .with_cube_count(usize::MAX);
+
+    let solutions = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .solve_all_cubes_with(factory(), &options, usize::MAX)
+        .unwrap();
+
+    // One cube per solution
+    assert_eq!(4, solutions.len());
+}
+
+pub fn limit(factory: fn() -> SolverFactory) {
+    let words = words(&WORDS);
+    let options = CubeAndConquer::default()
+        .with_thread_count(2)
+        .with_cube_count(usize::MAX);
+
+    let solutions = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .solve_all_cubes_with(factory(), &options, 2)
+        .unwrap();
+
+    assert_eq!(2, solutions.len());
+    assert_ne!(solutions[0], solutions[1]);
+}
+
+/// Returns options splitting as given on 4 threads, into at least 8 cubes.
+fn options(splitting: Splitting) -> CubeAndConquer {
+    CubeAndConquer::default()
+        .with_splitting(splitting)
+        .with_thread_count(4)
+        .with_cube_count(8)
+}
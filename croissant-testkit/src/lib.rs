@@ -0,0 +1,102 @@
+//! Tests shared by the solver implementations: Each solver crate runs them with its own [SolverFactory], e.g. in
+//! `tests/test_candidates.rs`:
+//!
+//! ```ignore
+//! croissant_testkit::candidates_tests!(factory);
+//!
+//! /// Returns the factory of the tested solvers.
+//! fn factory() -> SolverFactory {
+//!     SolverFactory::Configurable(Box::new(|| Box::new(MySolver::new())))
+//! }
+//! ```
+
+pub mod candidates;
+pub mod counting;
+pub mod cubes;
+pub mod simple;
+pub mod viable;
+
+use croissant_crossword::crossword::NativeSolverBuilder;
+use croissant_crossword::crossword::{
+    CandidateLetters, Crossword, CrosswordSolutions, ViableWords,
+};
+use croissant_crossword::slot::Slot;
+use croissant_solver::{SolverError, SolverFactory};
+
+/// The words of the 3x3 grids of the tests.
+pub const WORDS: [&str; 6] = ["AAA", "BBB", "CDE", "ABC", "ABD", "ABE"];
+
+/// The solvers of the tests.
+#[derive(Clone, Copy)]
+pub enum Solvers {
+    /// The solvers created by the given factory function.
+    Factory(fn() -> SolverFactory),
+    /// The native solvers built by the builders returned by the given function.
+    Native(fn() -> Box<dyn NativeSolverBuilder>),
+}
+
+/// Returns the given words as strings.
+pub fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|&word| word.to_string()).collect()
+}
+
+/// Solves the given crossword with a solver created by the given factory.
+pub fn solve(crossword: Crossword, factory: &SolverFactory) -> CrosswordSolutions {
+    match factory {
+        SolverFactory::Configurable(create) => crossword.solve_with(create()),
+        SolverFactory::Builder(create) => crossword.solve_with_solver_built_by(create()),
+    }
+    .unwrap()
+}
+
+/// Computes the candidate letters of the given crossword with a solver created by the given factory.
+pub fn candidate_letters(crossword: Crossword, factory: &SolverFactory) -> CandidateLetters {
+    match factory {
+        SolverFactory::Configurable(create) => crossword.candidate_letters_with(create()),
+        SolverFactory::Builder(create) => {
+            crossword.candidate_letters_with_solver_built_by(create())
+        }
+    }
+    .unwrap()
+}
+
+/// Returns the viable words of the given slot of the given crossword, checked with a solver created by the given
+/// factory.
+pub fn viable_words<'wordlist>(
+    crossword: Crossword<'wordlist>,
+    slot: &Slot,
+    factory: &SolverFactory,
+) -> Result<ViableWords<'wordlist>, SolverError> {
+    match factory {
+        SolverFactory::Configurable(create) => crossword.viable_words_with(slot, create()),
+        SolverFactory::Builder(create) => {
+            crossword.viable_words_with_solver_built_by(slot, create())
+        }
+    }
+}
+
+/// Counts exactly the solutions of the given crossword with a solver created by the given factory.
+pub fn count_solutions_exactly(crossword: Crossword, factory: &SolverFactory) -> u64 {
+    match factory {
+        SolverFactory::Configurable(create) => crossword.count_solutions_exactly_with(create()),
+        SolverFactory::Builder(create) => {
+            crossword.count_solutions_exactly_with_solver_built_by(create())
+        }
+    }
+    .unwrap()
+}
+
+/// Defines a test calling each of the given functions of the given module of this crate with the given argument, i.e.
+/// the factory function or the [Solvers] of the tests.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tests {
+    ($factory:expr, $module:ident: $($test:ident),+ $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                $crate::$module::$test($factory);
+            }
+        )+
+    };
+}
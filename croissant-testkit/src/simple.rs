@@ -0,0 +1,158 @@
+//! Tests of [Crossword::solve_with], [Crossword::solve_with_solver_built_by] and [Crossword::solve_natively_with] on
+//! small grids, with each [Encoding].
+
+use std::collections::HashSet;
+
+use croissant_crossword::crossword::{Crossword, CrosswordSolutions};
+use croissant_crossword::encoding::{DirectEncoding, Encoding, MddEncoding, WordLevelEncoding};
+
+use crate::{Solvers, WORDS};
+
+/// Defines the tests of this module, for the solvers created by the given factory function.
+#[macro_export]
+macro_rules! simple_tests {
+    ($factory:expr) => {
+        $crate::tests!(
+            $crate::Solvers::Factory($factory),
+            simple: empty,
+            trivial,
+            partially_prefilled_1x3,
+            partially_prefilled_3x3,
+            with_blocks,
+            impossible_no_solution,
+            impossible_no_candidate,
+            impossible_prefilled_word_not_in_list
+        );
+    };
+}
+
+/// Defines the tests of this module, for the native solvers built by the builders returned by the given function.
+#[macro_export]
+macro_rules! native_simple_tests {
+    ($builder:expr) => {
+        $crate::tests!(
+            $crate::Solvers::Native($builder),
+            simple: empty,
+            trivial,
+            partially_prefilled_1x3,
+            partially_prefilled_3x3,
+            with_blocks,
+            impossible_no_solution,
+            impossible_no_candidate,
+            impossible_prefilled_word_not_in_list,
+            duplicate_words
+        );
+    };
+}
+
+pub fn empty(solvers: Solvers) {
+    // The empty grid is its own solution
+    assert_solutions_eq([""], "", &[], solvers);
+}
+
+pub fn trivial(solvers: Solvers) {
+    assert_solutions_eq(
+        [
+            "BBB\nBBB\nBBB",
+            "ABC\nABD\nABE",
+            "AAA\nBBB\nCDE",
+            "AAA\nAAA\nAAA",
+        ],
+        "...\n...\n...",
+        &WORDS,
+        solvers,
+    );
+}
+
+pub fn partially_prefilled_1x3(solvers: Solvers) {
+    assert_solutions_eq(["ABC"], "AB.", &["ABC"], solvers);
+}
+
+pub fn partially_prefilled_3x3(solvers: Solvers) {
+    assert_solutions_eq(["ABC\nABD\nABE"], "ABC\n...\n...", &WORDS, solvers);
+}
+
+pub fn with_blocks(solvers: Solvers) {
+    let words = ["AA", "BBB", "ABC", "AB", "BE"];
+    assert_solutions_eq(["ABC\nAB#\n#BE"], "ABC\n..#\n#..", &words, solvers);
+}
+
+pub fn impossible_no_solution(solvers: Solvers) {
+    let words = [
+        "AAA", "BBB", "CDF", /* should be CDE */
+        "ABC", "ABD", "ABE",
+    ];
+    assert_solutions_eq([], "ABC\n...\n...", &words, solvers);
+}
+
+pub fn impossible_no_candidate(solvers: Solvers) {
+    assert_no_solution("...\n...\n...", &[], solvers);
+}
+
+pub fn impossible_prefilled_word_not_in_list(solvers: Solvers) {
+    let words = ["AAA", "BBB", "CDE", "ABC", "ABE"];
+    assert_no_solution("ABD\n...\n...", &words, solvers);
+}
+
+pub fn duplicate_words(solvers: Solvers) {
+    assert_solutions_eq(["ABC"], "AB.", &["ABC", "ABC"], solvers);
+}
+
+/// Returns the encodings of the tests.
+fn encodings() -> [Box<dyn Encoding>; 3] {
+    [
+        Box::new(DirectEncoding),
+        Box::new(MddEncoding),
+        Box::new(WordLevelEncoding),
+    ]
+}
+
+/// Verifies that the solutions of the given grid with the given words are the expected ones, in any order, with each
+/// encoding, using the given solvers.
+fn assert_solutions_eq<const N: usize>(
+    expected_solutions: [&str; N],
+    grid: &str,
+    words: &[&str],
+    solvers: Solvers,
+) {
+    for encoding in encodings() {
+        let mut expected_solutions = HashSet::from(expected_solutions);
+        for solution in solutions(grid, words, encoding, solvers) {
+            assert!(
+                expected_solutions.remove(solution.as_str()),
+                "Unexpected solution: {solution:?}"
+            );
+        }
+        assert!(
+            expected_solutions.is_empty(),
+            "Missing solutions: {expected_solutions:?}"
+        );
+    }
+}
+
+/// Verifies that the given grid has no solution with the given words, with each encoding, using the given solvers: A
+/// slot has no candidate, so that even an incomplete search is complete.
+fn assert_no_solution(grid: &str, words: &[&str], solvers: Solvers) {
+    for encoding in encodings() {
+        let mut solutions = solutions(grid, words, encoding, solvers);
+        assert_eq!(None, solutions.next());
+        assert!(!solutions.is_interrupted(), "Search should be complete");
+    }
+}
+
+/// Returns the solutions of the given grid with the given words and encoding, using the given solvers.
+fn solutions(
+    grid: &str,
+    words: &[&str],
+    encoding: Box<dyn Encoding>,
+    solvers: Solvers,
+) -> CrosswordSolutions {
+    let words = crate::words(words);
+    let crossword = Crossword::try_from(grid, &words)
+        .unwrap()
+        .with_encoding(encoding);
+    match solvers {
+        Solvers::Factory(factory) => crate::solve(crossword, &factory()),
+        Solvers::Native(builder) => crossword.solve_natively_with(builder()).unwrap(),
+    }
+}
@@ -0,0 +1,85 @@
+//! Tests of [Crossword::viable_words_with].
+
+use croissant_crossword::crossword::Crossword;
+use croissant_crossword::slot::Slot;
+use croissant_solver::SolverFactory;
+
+use crate::{words, WORDS};
+
+/// The scores of the [WORDS].
+const SCORES: [i32; 6] = [1, 2, 3, 4, 5, 6];
+
+/// Defines the tests of this module, for the solvers created by the given factory function.
+#[macro_export]
+macro_rules! viable_tests {
+    ($factory:expr) => {
+        $crate::tests!(
+            $factory,
+            viable: ranked_by_score,
+            lazy,
+            prefilled,
+            by_crossings_contains_viable_words,
+            unknown_slot
+        );
+    };
+}
+
+pub fn ranked_by_score(factory: fn() -> SolverFactory) {
+    let viable_words = viable_words("...\n...\n...", &Slot::across(0, 3, 0), usize::MAX, factory);
+
+    assert_eq!(vec!["ABC", "BBB", "AAA"], viable_words);
+}
+
+pub fn lazy(factory: fn() -> SolverFactory) {
+    let viable_words = viable_words("...\n...\n...", &Slot::across(0, 3, 0), 1, factory);
+
+    assert_eq!(vec!["ABC"], viable_words);
+}
+
+pub fn prefilled(factory: fn() -> SolverFactory) {
+    let viable_words = viable_words("...\n...\n..E", &Slot::down(0, 3, 1), usize::MAX, factory);
+
+    assert_eq!(vec!["ABD", "BBB"], viable_words);
+}
+
+pub fn by_crossings_contains_viable_words(factory: fn() -> SolverFactory) {
+    let words = words(&WORDS);
+    let crossword = Crossword::try_from("...\n...\n...", &words)
+        .unwrap()
+        .with_word_scores(&SCORES);
+    let slot = Slot::across(0, 3, 1);
+
+    let by_crossings = crossword.viable_words_by_crossings(&slot).unwrap();
+    let viable_words = viable_words("...\n...\n...", &slot, usize::MAX, factory);
+
+    for word in viable_words {
+        assert!(by_crossings.contains(&word), "Missing {word}");
+    }
+}
+
+pub fn unknown_slot(factory: fn() -> SolverFactory) {
+    let words = words(&WORDS);
+    let crossword = Crossword::try_from("...\n...\n...", &words).unwrap();
+    let slot = &Slot::across(0, 2, 0);
+
+    let result = crate::viable_words(crossword, slot, &factory());
+
+    assert!(result.is_err(), "Slot should be unknown");
+}
+
+/// Returns at most the given number of viable words for the given slot, using a solver created by the given factory.
+fn viable_words(
+    grid: &str,
+    slot: &Slot,
+    limit: usize,
+    factory: fn() -> SolverFactory,
+) -> Vec<String> {
+    let words = words(&WORDS);
+    let crossword = Crossword::try_from(grid, &words)
+        .unwrap()
+        .with_word_scores(&SCORES);
+    crate::viable_words(crossword, slot, &factory())
+        .unwrap()
+        .take(limit)
+        .collect()
+}
@@ -2,14 +2,74 @@ use std::io::{BufRead, BufReader, Read};
 use wasm_bindgen::prelude::wasm_bindgen;
 
 use croissant_crossword::crossword::Crossword;
+use croissant_crossword::grid::Grid;
 use croissant_solver_logicng::LogicngSolverBuilder;
+#[cfg(feature = "solver-varisat")]
+use croissant_solver_varisat::VarisatSolver;
 
 #[wasm_bindgen]
 pub fn solve(grid: String) -> Option<String> {
     let wordlist = ukacd();
     let crossword = Crossword::try_from(grid.as_str(), &wordlist).unwrap();
     let solver_builder = Box::new(LogicngSolverBuilder::new());
-    crossword.solve_with_solver_built_by(solver_builder).next()
+    crossword
+        .solve_with_solver_built_by(solver_builder)
+        .ok()?
+        .next()
+}
+
+/// Same as [solve], but with the varisat solver instead of the LogicNG one.
+#[cfg(feature = "solver-varisat")]
+#[wasm_bindgen]
+pub fn solve_with_varisat(grid: String) -> Option<String> {
+    let wordlist = ukacd();
+    let crossword = Crossword::try_from(grid.as_str(), &wordlist).unwrap();
+    crossword
+        .solve_with(Box::new(VarisatSolver::new()))
+        .ok()?
+        .next()
+}
+
+/// Returns at most `limit` words which can go in the slot starting at the given cell, in the given direction.
+///
+/// Unless `crossings_only` is set, returned words have at least one full completion of the grid. Otherwise, only the
+/// crossing slots are checked, which is much faster but may return words without completion.
+#[wasm_bindgen]
+pub fn viable_words(
+    grid: String,
+    row: usize,
+    column: usize,
+    down: bool,
+    crossings_only: bool,
+    limit: usize,
+) -> Result<Vec<String>, String> {
+    let wordlist = ukacd();
+    let slot = Grid::try_from(grid.as_str())?
+        .slots()
+        .into_iter()
+        .find(|slot| {
+            let start = slot.positions()[0];
+            slot.is_down() == down && start.row() == row && start.column() == column
+        })
+        .ok_or_else(|| format!("No slot at row #{row}, column #{column}"))?;
+    let crossword = Crossword::try_from(grid.as_str(), &wordlist)?;
+    if crossings_only {
+        let mut words = crossword.viable_words_by_crossings(&slot)?;
+        words.truncate(limit);
+        return Ok(words);
+    }
+    let solver_builder = Box::new(LogicngSolverBuilder::new());
+    let mut viable_words = crossword
+        .viable_words_with_solver_built_by(&slot, solver_builder)
+        .map_err(|error| error.to_string())?;
+    let words = viable_words.by_ref().take(limit).collect();
+    if let Some(error) = viable_words.error() {
+        return Err(error.to_string());
+    }
+    if viable_words.is_interrupted() {
+        return Err("Search interrupted".to_string());
+    }
+    Ok(words)
 }
 
 /// Reads words from bundled UKACD.
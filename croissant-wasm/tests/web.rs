@@ -3,7 +3,7 @@
 #![cfg(target_arch = "wasm32")]
 
 extern crate wasm_bindgen_test;
-use croissant_wasm::solve;
+use croissant_wasm::{solve, viable_words};
 use wasm_bindgen_test::*;
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -14,3 +14,13 @@ fn test_solve() {
     let solved_grid = solve(grid);
     assert_eq!(Some("CHIZ\nHE#O\nASIA".to_string()), solved_grid);
 }
+
+#[wasm_bindgen_test]
+fn test_viable_words() {
+    let grid = "....\n..#.\nA...".to_string();
+    let words = viable_words(grid, 0, 0, true, false, 3).unwrap();
+    assert_eq!(3, words.len());
+    assert!(words
+        .iter()
+        .all(|word| word.len() == 3 && word.ends_with('A')));
+}